@@ -69,6 +69,11 @@ extern "C" const uint8_t *sfImage_getPixelsPtr(const sf::Image *image) {
     return image->getPixelsPtr();
 }
 
+extern "C" uint8_t *sfImage_getPixelsMutPtr(sf::Image *image) {
+    // The pixels are owned by the image, which is not const here
+    return const_cast<uint8_t *>(image->getPixelsPtr());
+}
+
 extern "C" sfVector2u sfImage_getSize(const sf::Image *image) {
     return convertVector2(image->getSize());
 }
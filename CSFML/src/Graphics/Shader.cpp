@@ -2,7 +2,20 @@
 #include "System/Vector2.hpp"
 #include "System/Vector3.hpp"
 #include <SFML/Graphics/Shader.hpp>
+#include <SFML/OpenGL.hpp>
+#include <SFML/Window/Context.hpp>
+#include <SFML/Window/GlResource.hpp>
+#include <atomic>
 #include <cstddef>
+#include <cstdint>
+
+#ifndef APIENTRY
+#define APIENTRY
+#endif
+
+#ifndef GL_CURRENT_PROGRAM
+#define GL_CURRENT_PROGRAM 0x8B8D
+#endif
 
 // 2D vectors
 typedef sfVector2f sfGlslVec2;
@@ -59,12 +72,30 @@ struct sfGlslMat4 {
     float array[4 * 4];
 };
 
+namespace {
+
+// A shader with an id that is never reused, unlike OpenGL program names
+struct IdentifiedShader : sf::Shader {
+    std::uint64_t id = nextId();
+
+    static std::uint64_t nextId() {
+        static std::atomic<std::uint64_t> next{1};
+        return next++;
+    }
+};
+
+} // namespace
+
 extern "C" sf::Shader *sfShader_new() {
-    return new sf::Shader;
+    return new IdentifiedShader;
 }
 
 extern "C" void sfShader_del(sf::Shader *shader) {
-    delete shader;
+    delete static_cast<IdentifiedShader *>(shader);
+}
+
+extern "C" std::uint64_t sfShader_getId(const sf::Shader *shader) {
+    return static_cast<const IdentifiedShader *>(shader)->id;
 }
 
 extern "C" bool sfShader_loadFromMemory_1(sf::Shader *shader, const char *content, sf::Shader::Type type) {
@@ -191,6 +222,80 @@ extern "C" void sfShader_setMat4UniformArray(sf::Shader *shader, const char *nam
     shader->setUniformArray(name, matrixArray, length);
 }
 
+namespace {
+
+template <typename Function>
+Function glFunction(const char *name) {
+    return reinterpret_cast<Function>(sf::Context::getFunction(name));
+}
+
+// sf::Shader only sets uniforms by name, so setting them by location goes through OpenGL.
+// Like sf::Shader, this uses the context lock of SFML, and makes the program of the shader
+// current until it is destroyed.
+class ProgramBinder : sf::GlResource {
+  public:
+    explicit ProgramBinder(const sf::Shader &shader) {
+        glGetIntegerv(GL_CURRENT_PROGRAM, &m_previous);
+        m_useProgram = glFunction<UseProgram>("glUseProgram");
+        if (m_useProgram) {
+            m_useProgram(shader.getNativeHandle());
+        }
+    }
+
+    ~ProgramBinder() {
+        if (m_useProgram) {
+            m_useProgram(static_cast<GLuint>(m_previous));
+        }
+    }
+
+    ProgramBinder(const ProgramBinder &) = delete;
+    ProgramBinder &operator=(const ProgramBinder &) = delete;
+
+  private:
+    using UseProgram = void(APIENTRY *)(GLuint);
+
+    TransientContextLock m_lock;
+    GLint m_previous = 0;
+    UseProgram m_useProgram = nullptr;
+};
+
+} // namespace
+
+extern "C" void sfShader_getUniformLocations(const sf::Shader *shader, const char *const *names, size_t count, int *locations) {
+    using GetUniformLocation = GLint(APIENTRY *)(GLuint, const char *);
+    ProgramBinder binder(*shader);
+    auto getUniformLocation = glFunction<GetUniformLocation>("glGetUniformLocation");
+    for (size_t i = 0; i < count; ++i) {
+        locations[i] = getUniformLocation ? getUniformLocation(shader->getNativeHandle(), names[i]) : -1;
+    }
+}
+
+extern "C" void sfShader_setFloatUniformsAt(sf::Shader *shader, int location, const float *values, size_t components, size_t count) {
+    using Uniform = void(APIENTRY *)(GLint, GLsizei, const GLfloat *);
+    static const char *const names[] = {"glUniform1fv", "glUniform2fv", "glUniform3fv", "glUniform4fv"};
+    ProgramBinder binder(*shader);
+    if (auto uniform = glFunction<Uniform>(names[components - 1])) {
+        uniform(location, static_cast<GLsizei>(count), values);
+    }
+}
+
+extern "C" void sfShader_setIntUniformsAt(sf::Shader *shader, int location, const int *values, size_t components, size_t count) {
+    using Uniform = void(APIENTRY *)(GLint, GLsizei, const GLint *);
+    static const char *const names[] = {"glUniform1iv", "glUniform2iv", "glUniform3iv", "glUniform4iv"};
+    ProgramBinder binder(*shader);
+    if (auto uniform = glFunction<Uniform>(names[components - 1])) {
+        uniform(location, static_cast<GLsizei>(count), values);
+    }
+}
+
+extern "C" void sfShader_setMatrixUniformsAt(sf::Shader *shader, int location, const float *values, size_t size, size_t count) {
+    using Uniform = void(APIENTRY *)(GLint, GLsizei, GLboolean, const GLfloat *);
+    ProgramBinder binder(*shader);
+    if (auto uniform = glFunction<Uniform>(size == 3 ? "glUniformMatrix3fv" : "glUniformMatrix4fv")) {
+        uniform(location, static_cast<GLsizei>(count), GL_FALSE, values);
+    }
+}
+
 extern "C" unsigned int sfShader_getNativeHandle(const sf::Shader *shader) {
     return shader->getNativeHandle();
 }
@@ -0,0 +1,101 @@
+#include <SFML/System/Err.hpp>
+#include <atomic>
+#include <cstddef>
+#include <mutex>
+#include <streambuf>
+#include <string>
+#include <utility>
+
+typedef void (*sfErrCallback)(const char *message, size_t length);
+
+namespace {
+
+// Nesting depth of the captures of the current thread
+thread_local std::size_t captureDepth = 0;
+// What the current thread wrote to sf::err() since its outermost capture began
+thread_local std::string captured;
+// What the current thread wrote to sf::err() since its last flush
+thread_local std::string pending;
+
+std::atomic<bool> forwardOutput{true};
+std::atomic<sfErrCallback> outputCallback{nullptr};
+
+// Stream buffer installed behind sf::err(), which forwards the output to the original buffer
+// and/or to a callback, and also keeps it while the writing thread is capturing.
+//
+// SFML ends each diagnostic with std::endl, so the callback is called with a whole diagnostic
+// when the stream is flushed.
+class CaptureBuf : public std::streambuf {
+  public:
+    explicit CaptureBuf(std::streambuf *forward) : m_forward(forward) {
+    }
+
+  protected:
+    int overflow(int c) override {
+        if (traits_type::eq_int_type(c, traits_type::eof())) {
+            return traits_type::not_eof(c);
+        }
+        const char ch = traits_type::to_char_type(c);
+        xsputn(&ch, 1);
+        return c;
+    }
+
+    std::streamsize xsputn(const char *s, std::streamsize n) override {
+        const auto length = static_cast<std::size_t>(n);
+        if (captureDepth > 0) {
+            captured.append(s, length);
+        }
+        if (outputCallback.load() != nullptr) {
+            pending.append(s, length);
+        }
+        if (forwardOutput.load() && m_forward) {
+            m_forward->sputn(s, n);
+        }
+        return n;
+    }
+
+    int sync() override {
+        if (!pending.empty()) {
+            // The callback may write to sf::err() again, which appends to `pending`
+            std::string message = std::move(pending);
+            pending.clear();
+            if (sfErrCallback callback = outputCallback.load()) {
+                callback(message.data(), message.size());
+            }
+        }
+        return forwardOutput.load() && m_forward ? m_forward->pubsync() : 0;
+    }
+
+  private:
+    std::streambuf *m_forward;
+};
+
+void installCaptureBuf() {
+    static std::once_flag once;
+    std::call_once(once, [] {
+        static CaptureBuf buf(sf::err().rdbuf());
+        sf::err().rdbuf(&buf);
+    });
+}
+
+} // namespace
+
+extern "C" size_t sfErr_beginCapture() {
+    installCaptureBuf();
+    ++captureDepth;
+    return captured.size();
+}
+
+extern "C" std::string *sfErr_endCapture(size_t start) {
+    auto *text = new std::string(captured, start < captured.size() ? start : captured.size());
+    if (--captureDepth == 0) {
+        captured.clear();
+    }
+    return text;
+}
+
+extern "C" void sfErr_setOutput(bool forward, sfErrCallback callback) {
+    installCaptureBuf();
+    forwardOutput.store(forward);
+    outputCallback.store(callback);
+}
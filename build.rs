@@ -260,6 +260,7 @@ fn main() {
             "CSFML/src/System/SfStdString.cpp",
             "CSFML/src/System/SfStdVector.cpp",
             "CSFML/src/System/Buffer.cpp",
+            "CSFML/src/System/Err.cpp",
         ]
         .iter(),
     );
@@ -0,0 +1,197 @@
+//! Derive macros for [rust-sfml](https://docs.rs/sfml).
+//!
+//! Use them through the `derive` feature of `sfml`, which re-exports them.
+
+use {
+    proc_macro::TokenStream,
+    proc_macro2::{Literal, TokenStream as TokenStream2},
+    quote::quote,
+    syn::{
+        Data, DeriveInput, Fields, GenericParam, Lifetime, LifetimeParam, LitStr, ext::IdentExt,
+        parse_macro_input, parse_quote, spanned::Spanned,
+    },
+};
+
+/// Implement `sfml::graphics::Uniforms` for a struct with named fields.
+///
+/// Each field is a uniform named after the field, unless renamed with
+/// `#[uniform(name = "...")]`. A field marked with `#[uniform(skip)]` is not uploaded.
+#[proc_macro_derive(Uniforms, attributes(uniform))]
+pub fn derive_uniforms(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_uniforms(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Field<'a> {
+    member: &'a syn::Ident,
+    ty: &'a syn::Type,
+    name: String,
+}
+
+fn expand_uniforms(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "Uniforms can only be derived for structs",
+        ));
+    };
+    let Fields::Named(struct_fields) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "Uniforms can only be derived for structs with named fields",
+        ));
+    };
+    let mut fields = Vec::new();
+    for field in &struct_fields.named {
+        let member = field.ident.as_ref().expect("named field");
+        let mut name = member.unraw().to_string();
+        let mut skip = false;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("uniform"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("name") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    name = lit.value();
+                    if name.contains('\0') {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "uniform names can't contain nul",
+                        ));
+                    }
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name = \"...\"` or `skip`"))
+                }
+            })?;
+        }
+        if !skip {
+            fields.push(Field {
+                member,
+                ty: &field.ty,
+                name,
+            });
+        }
+    }
+
+    let texture = Lifetime::new("'__texture", proc_macro2::Span::call_site());
+    let mut generics = input.generics.clone();
+    generics.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeParam::new(texture.clone())),
+    );
+    let where_clause = generics.make_where_clause();
+    for field in &fields {
+        let ty = field.ty;
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::sfml::graphics::UniformValue<#texture>));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let ident = &input.ident;
+
+    let names = fields.iter().map(|field| c_string(&field.name));
+    let setters = fields.iter().enumerate().map(|(i, field)| {
+        let member = field.member;
+        quote! {
+            ::sfml::graphics::UniformValue::set_uniform(&self.#member, shader, locations[#i]);
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::sfml::graphics::Uniforms<#texture> for #ident #ty_generics
+        #where_clause
+        {
+            const NAMES: &'static [&'static ::core::ffi::CStr] = &[#(#names),*];
+
+            fn set_uniforms(
+                &self,
+                shader: &mut ::sfml::graphics::Shader<#texture>,
+                locations: &[::sfml::graphics::UniformLocation],
+            ) {
+                #(#setters)*
+            }
+        }
+    })
+}
+
+fn c_string(name: &str) -> Literal {
+    let name = std::ffi::CString::new(name).expect("checked for nul");
+    Literal::c_string(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(input: DeriveInput) -> String {
+        match expand_uniforms(&input) {
+            Ok(tokens) => tokens.to_string(),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn expansion() {
+        let expanded = expand(parse_quote! {
+            struct Wave<'t, T> {
+                texture: &'t Texture,
+                r#time: f32,
+                #[uniform(name = "tint_color")]
+                tint: Color,
+                #[uniform(skip)]
+                cache: T,
+            }
+        });
+        let expected = quote! {
+            impl<'__texture, 't, T> ::sfml::graphics::Uniforms<'__texture> for Wave<'t, T>
+            where
+                &'t Texture: ::sfml::graphics::UniformValue<'__texture>,
+                f32: ::sfml::graphics::UniformValue<'__texture>,
+                Color: ::sfml::graphics::UniformValue<'__texture>
+            {
+                const NAMES: &'static [&'static ::core::ffi::CStr] =
+                    &[c"texture", c"time", c"tint_color"];
+
+                fn set_uniforms(
+                    &self,
+                    shader: &mut ::sfml::graphics::Shader<'__texture>,
+                    locations: &[::sfml::graphics::UniformLocation],
+                ) {
+                    ::sfml::graphics::UniformValue::set_uniform(&self.texture, shader, locations[0usize]);
+                    ::sfml::graphics::UniformValue::set_uniform(&self.r#time, shader, locations[1usize]);
+                    ::sfml::graphics::UniformValue::set_uniform(&self.tint, shader, locations[2usize]);
+                }
+            }
+        };
+        assert_eq!(expanded, expected.to_string());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            expand(parse_quote! { struct Tuple(f32); }),
+            "Uniforms can only be derived for structs with named fields"
+        );
+        assert_eq!(
+            expand(parse_quote! { enum Enum { A } }),
+            "Uniforms can only be derived for structs"
+        );
+        assert_eq!(
+            expand(parse_quote! { struct S { #[uniform(rename = "x")] x: f32 } }),
+            "expected `name = \"...\"` or `skip`"
+        );
+        assert_eq!(
+            expand(parse_quote! { struct S { #[uniform(name = "a\0b")] x: f32 } }),
+            "uniform names can't contain nul"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use sfml::{
+    SfResult,
+    graphics::{
+        Color, RenderTarget, RenderWindow, Transformable,
+        particles::{
+            ColorOverLifetime, Drag, EmissionShape, Emitter, Gravity, ParticleSystem,
+            SizeOverLifetime, Vortex,
+        },
+    },
+    system::{Angle, Clock, Time, Vector2f},
+    window::{Event, Key, Style, mouse},
+};
+
+fn main() -> SfResult<()> {
+    let mut window = RenderWindow::new(
+        [800, 600],
+        "Particles",
+        Style::CLOSE,
+        Default::default(),
+        &Default::default(),
+    )?;
+    window.set_vertical_sync_enabled(true);
+
+    let mut system = ParticleSystem::new(1);
+
+    // A fountain in the bottom middle of the screen
+    let mut fountain = Emitter::new();
+    fountain.rate = 300.;
+    fountain.shape = EmissionShape::Line { length: 40. };
+    fountain.direction = Angle::degrees(-90.);
+    fountain.spread = Angle::degrees(15.);
+    fountain.speed = 250.0..=350.0;
+    fountain.lifetime = Time::seconds(2.)..=Time::seconds(3.);
+    fountain.set_position((400., 580.));
+    system.add_emitter(fountain);
+
+    // An emitter following the mouse, which bursts on click
+    let mut cursor = Emitter::new();
+    cursor.rate = 0.;
+    cursor.shape = EmissionShape::Circle { radius: 5. };
+    cursor.speed = 50.0..=200.0;
+    cursor.lifetime = Time::seconds(0.5)..=Time::seconds(1.5);
+    let cursor = system.add_emitter(cursor);
+
+    system.add_affector(Gravity(Vector2f::new(0., 200.)));
+    system.add_affector(Drag { coefficient: 0.5 });
+    system.add_affector(Vortex {
+        center: Vector2f::new(400., 300.),
+        strength: 2000.,
+    });
+    system.add_affector(ColorOverLifetime {
+        start: Color::rgb(255, 200, 50),
+        end: Color::rgba(200, 30, 30, 0),
+    });
+    system.add_affector(SizeOverLifetime { start: 6., end: 1. });
+
+    let mut clock = Clock::new()?;
+    clock.start();
+
+    'mainloop: loop {
+        while let Some(event) = window.poll_event() {
+            match event {
+                Event::Closed
+                | Event::KeyPressed {
+                    code: Key::Escape, ..
+                } => break 'mainloop,
+                Event::MouseButtonPressed {
+                    button: mouse::Button::Left,
+                    position,
+                } => {
+                    let emitter = &mut system.emitters_mut()[cursor];
+                    emitter.set_position(position.as_other::<f32>());
+                    emitter.burst(500);
+                }
+                _ => {}
+            }
+        }
+
+        system.update(clock.restart());
+
+        window.clear(Color::BLACK);
+        window.draw(&system);
+        window.display();
+    }
+    Ok(())
+}
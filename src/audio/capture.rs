@@ -4,6 +4,7 @@ use {
         audio::SoundBuffer,
         cpp::{CppString, CppVector, FBox},
         ffi::audio as ffi,
+        system::err::diagnose,
     },
     std::{ffi::CString, os::raw::c_void, ptr::NonNull},
 };
@@ -159,8 +160,10 @@ impl<'a, R: SoundRecorder> SoundRecorderDriver<'a, R> {
     ///
     /// [`set_device`]: SoundRecorderDriver::set_device
     pub fn start(&mut self, sample_rate: u32) -> SfResult<()> {
-        unsafe { ffi::sfCustomSoundRecorder_start(self.handle.as_ptr(), sample_rate) }
-            .into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::sfCustomSoundRecorder_start(self.handle.as_ptr(), sample_rate) }
+                .into_sf_result()
+        })
     }
     /// Stop the capture, lending out the underlying [`SoundRecorder`].
     pub fn stop(&mut self) -> &mut R {
@@ -269,8 +272,10 @@ impl SoundBufferRecorder {
     /// # Arguments
     /// * `sample_rate` - Desired capture rate, in number of samples per second
     pub fn start(&mut self, sample_rate: u32) -> SfResult<()> {
-        unsafe { ffi::sfSoundBufferRecorder_start(self.handle.as_ptr(), sample_rate) }
-            .into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::sfSoundBufferRecorder_start(self.handle.as_ptr(), sample_rate) }
+                .into_sf_result()
+        })
     }
 
     /// Stop the capture of a sound recorder
@@ -5,11 +5,11 @@ use {
         audio::TimeSpan,
         cpp::CppVector,
         ffi,
-        system::{InputStream, Time, Vector3f},
+        system::{InputStream, Time, Vector3f, err::diagnose},
     },
     std::{
         ffi::{CString, c_uint, c_void},
-        io::Empty
+        io::Empty,
     },
 };
 
@@ -84,12 +84,11 @@ impl<'src, S> Music<'src, S> {
     /// * stream - Your struct, implementing Read and Seek
     ///
     /// [`play`]: Music::play
-    pub fn open_from_stream(
-        &mut self,
-        stream: &'src mut InputStream<'src, S>,
-    ) -> SfResult<()> {
-        unsafe { ffi::audio::sfMusic_openFromStream(self.music, &raw mut *stream.stream) }
-            .into_sf_result()
+    pub fn open_from_stream(&mut self, stream: &'src mut InputStream<'src, S>) -> SfResult<()> {
+        diagnose(|| {
+            unsafe { ffi::audio::sfMusic_openFromStream(self.music, &raw mut *stream.stream) }
+                .into_sf_result()
+        })
     }
 
     /// Open music from a stream (a struct implementing Read and Seek), with ownership.
@@ -103,17 +102,15 @@ impl<'src, S> Music<'src, S> {
     /// * stream - Your struct, implementing Read and Seek
     ///
     /// [`play`]: Music::play
-    pub fn open_from_stream_owned(
-        &mut self,
-        stream: InputStream<'src, S>,
-    ) -> SfResult<()> {
-        unsafe { ffi::audio::sfMusic_openFromStream(self.music, stream.stream.0.as_ptr()) }
-            .into_sf_result()
-            .map(|_| {
-                self.stream = Some(stream);
-                ()
-            })
-
+    pub fn open_from_stream_owned(&mut self, stream: InputStream<'src, S>) -> SfResult<()> {
+        diagnose(|| {
+            unsafe { ffi::audio::sfMusic_openFromStream(self.music, stream.stream.0.as_ptr()) }
+                .into_sf_result()
+        })
+        .map(|_| {
+            self.stream = Some(stream);
+            ()
+        })
     }
 }
 
@@ -148,7 +145,9 @@ impl<'src> Music<'src> {
     /// [`play`]: Music::play
     pub fn open_from_file(&mut self, filename: &str) -> SfResult<()> {
         let c_str = CString::new(filename)?;
-        unsafe { ffi::audio::sfMusic_openFromFile(self.music, c_str.as_ptr()) }.into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::audio::sfMusic_openFromFile(self.music, c_str.as_ptr()) }.into_sf_result()
+        })
     }
 
     /// Create a new music and open it from memory
@@ -163,8 +162,10 @@ impl<'src> Music<'src> {
     ///
     /// [`play`]: Music::play
     pub fn open_from_memory(&mut self, data: &'src [u8]) -> SfResult<()> {
-        unsafe { ffi::audio::sfMusic_openFromMemory(self.music, data.as_ptr(), data.len()) }
-            .into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::audio::sfMusic_openFromMemory(self.music, data.as_ptr(), data.len()) }
+                .into_sf_result()
+        })
     }
 }
 
@@ -4,7 +4,7 @@ use {
         IntoSfResult, SfResult,
         cpp::{CppVector, FBox},
         ffi::{self},
-        system::{InputStream, Time},
+        system::{InputStream, Time, err::diagnose},
     },
     std::{
         ffi::CString,
@@ -100,18 +100,24 @@ impl SoundBuffer {
     /// * filename - Path of the sound file to load
     pub fn load_from_file(&mut self, filename: &str) -> SfResult<()> {
         let c_str = CString::new(filename)?;
-        unsafe { ffi::audio::sfSoundBuffer_loadFromFile(self, c_str.as_ptr()) }.into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::audio::sfSoundBuffer_loadFromFile(self, c_str.as_ptr()) }.into_sf_result()
+        })
     }
     /// Load the sound buffer from a file in memory.
     pub fn load_from_memory(&mut self, data: &[u8]) -> SfResult<()> {
-        unsafe { ffi::audio::sfSoundBuffer_loadFromMemory(self, data.as_ptr(), data.len()) }
-            .into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::audio::sfSoundBuffer_loadFromMemory(self, data.as_ptr(), data.len()) }
+                .into_sf_result()
+        })
     }
     /// Load the sound buffer from a custom stream.
     pub fn load_from_stream<T: Read + Seek>(&mut self, stream: &mut T) -> SfResult<()> {
         let mut stream = InputStream::new(stream);
-        unsafe { ffi::audio::sfSoundBuffer_loadFromStream(self, &raw mut *stream.stream) }
-            .into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::audio::sfSoundBuffer_loadFromStream(self, &raw mut *stream.stream) }
+                .into_sf_result()
+        })
     }
     /// Load the sound buffer from a slice of audio samples.
     ///
@@ -123,18 +129,20 @@ impl SoundBuffer {
         sample_rate: u32,
         channel_map: &[SoundChannel],
     ) -> SfResult<()> {
-        unsafe {
-            ffi::audio::sfSoundBuffer_loadFromSamples(
-                self,
-                samples.as_ptr(),
-                samples.len() as _,
-                channel_count,
-                sample_rate,
-                channel_map.as_ptr().cast(),
-                channel_map.len(),
-            )
-        }
-        .into_sf_result()
+        diagnose(|| {
+            unsafe {
+                ffi::audio::sfSoundBuffer_loadFromSamples(
+                    self,
+                    samples.as_ptr(),
+                    samples.len() as _,
+                    channel_count,
+                    sample_rate,
+                    channel_map.as_ptr().cast(),
+                    channel_map.len(),
+                )
+            }
+            .into_sf_result()
+        })
     }
 }
 
@@ -219,7 +227,9 @@ impl SoundBuffer {
     /// * filename - Path of the sound file to write
     pub fn save_to_file(&self, filename: &str) -> SfResult<()> {
         let c_str = CString::new(filename)?;
-        unsafe { ffi::audio::sfSoundBuffer_saveToFile(self, c_str.as_ptr()) }.into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::audio::sfSoundBuffer_saveToFile(self, c_str.as_ptr()) }.into_sf_result()
+        })
     }
 }
 
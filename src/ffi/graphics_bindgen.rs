@@ -138,6 +138,7 @@ pub fn sfImage_copy(image: *mut sfImage, source: *const sfImage, dest: sfVector2
 pub fn sfImage_setPixel(image: *mut sfImage, coords: sfVector2u, color: sfColor);
 pub fn sfImage_getPixel(image: *const sfImage, coords: sfVector2u) -> sfColor;
 pub fn sfImage_getPixelsPtr(image: *const sfImage) -> *const u8;
+pub fn sfImage_getPixelsMutPtr(image: *mut sfImage) -> *mut u8;
 pub fn sfImage_getSize(image: *const sfImage) -> sfVector2u;
 pub fn sfImage_flipHorizontally(image: *mut sfImage);
 pub fn sfImage_flipVertically(image: *mut sfImage);
@@ -273,6 +274,7 @@ pub fn sfTouch_getPositionRenderWindow(finger: c_uint, relativeTo: *const sfRend
 // Shader.cpp
 pub fn sfShader_new() -> *mut sfShader;
 pub fn sfShader_del(shader: *mut sfShader);
+pub fn sfShader_getId(shader: *const sfShader) -> u64;
 pub fn sfShader_loadFromMemory_1(shader: *mut sfShader, content: *const c_char, type_: sfShaderType) -> bool;
 pub fn sfShader_loadFromFile_1(shader: *mut sfShader, filename: *const c_char, type_: sfShaderType) -> bool;
 pub fn sfShader_loadFromStream_1(shader: *mut sfShader, stream: *mut sfInputStreamHelper, type_: sfShaderType) -> bool;
@@ -304,6 +306,10 @@ pub fn sfShader_setVec3UniformArray(shader: *mut sfShader, name: *const c_char,
 pub fn sfShader_setVec4UniformArray(shader: *mut sfShader, name: *const c_char, vectorArray: *const sfGlslVec4, length: usize);
 pub fn sfShader_setMat3UniformArray(shader: *mut sfShader, name: *const c_char, matrixArray: *const sfGlslMat3, length: usize);
 pub fn sfShader_setMat4UniformArray(shader: *mut sfShader, name: *const c_char, matrixArray: *const sfGlslMat4, length: usize);
+pub fn sfShader_getUniformLocations(shader: *const sfShader, names: *const *const c_char, count: usize, locations: *mut c_int);
+pub fn sfShader_setFloatUniformsAt(shader: *mut sfShader, location: c_int, values: *const f32, components: usize, count: usize);
+pub fn sfShader_setIntUniformsAt(shader: *mut sfShader, location: c_int, values: *const c_int, components: usize, count: usize);
+pub fn sfShader_setMatrixUniformsAt(shader: *mut sfShader, location: c_int, values: *const f32, size: usize, count: usize);
 pub fn sfShader_getNativeHandle(shader: *const sfShader) -> c_uint;
 pub fn sfShader_bind(shader: *const sfShader);
 pub fn sfShader_isAvailable() -> bool;
@@ -18,5 +18,6 @@ type sfInputStreamHelperSeekCb =
 type sfInputStreamHelperTellCb = Option<unsafe extern "C" fn(user_data: *mut c_void) -> i64>;
 type sfInputStreamHelperGetSizeCb = Option<unsafe extern "C" fn(user_data: *mut c_void) -> i64>;
 pub type sfBuffer = crate::system::buffer::Buffer;
+pub type sfErrCallback = Option<unsafe extern "C" fn(message: *const c_char, length: usize)>;
 
 include!("system_bindgen.rs");
@@ -16,6 +16,10 @@ pub fn sfClock_start(clock: *mut sfClock);
 pub fn sfClock_stop(clock: *mut sfClock);
 pub fn sfClock_restart(clock: *mut sfClock) -> i64;
 pub fn sfClock_reset(clock: *mut sfClock) -> i64;
+// Err.cpp
+pub fn sfErr_beginCapture() -> usize;
+pub fn sfErr_endCapture(start: usize) -> *mut sfStdString;
+pub fn sfErr_setOutput(forward: bool, callback: sfErrCallback);
 // InputStreamHelper.cpp
 pub fn sfInputStreamHelper_new(read: sfInputStreamHelperReadCb, seek: sfInputStreamHelperSeekCb, tell: sfInputStreamHelperTellCb, getSize: sfInputStreamHelperGetSizeCb, userData: *mut c_void) -> *mut sfInputStreamHelper;
 pub fn sfInputStreamHelper_del(stream: *mut sfInputStreamHelper);
@@ -0,0 +1,905 @@
+use {
+    crate::{
+        SfError,
+        cpp::FBox,
+        graphics::{
+            Color, Drawable, FloatRect, IntRect, PrimitiveType, RenderStates, RenderTarget,
+            Texture, Transformable, Vertex,
+            transformable::{Transformation, impl_transformable_via},
+        },
+        system::{Vector2f, Vector2i},
+    },
+    std::{
+        collections::{BTreeMap, HashMap},
+        error::Error,
+        fmt, io,
+        path::Path,
+    },
+};
+
+/// A character of a [`BitmapFont`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BitmapGlyph {
+    /// Rectangle of the character in its page texture
+    pub texture_rect: IntRect,
+    /// Offset from the pen position (on the top of the line) to the top-left of the character
+    pub offset: Vector2f,
+    /// Horizontal offset to move the pen to the next character
+    pub advance: f32,
+    /// Index of the page texture containing the character
+    pub page: usize,
+}
+
+/// A font made of pre-rendered characters, in the [AngelCode BMFont] format.
+///
+/// Unlike [`Font`], a bitmap font has a single size, and its characters are rendered
+/// exactly as they were drawn, which makes it a good fit for pixel art.
+///
+/// Both the text and binary `.fnt` formats are supported. The page textures are loaded
+/// from paths relative to the `.fnt` file.
+///
+/// Bitmap fonts are drawn with [`BitmapText`].
+///
+/// [AngelCode BMFont]: https://www.angelcode.com/products/bmfont/
+/// [`Font`]: crate::graphics::Font
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # let mut window: RenderWindow = unimplemented!();
+/// let font = BitmapFont::from_file("pixel.fnt").unwrap();
+/// let mut text = BitmapText::new("Hello, world!", &font);
+/// text.set_character_scale(2.);
+/// window.draw(&text);
+/// ```
+#[derive(Debug)]
+pub struct BitmapFont {
+    size: i32,
+    line_height: f32,
+    base: f32,
+    pages: Vec<FBox<Texture>>,
+    glyphs: HashMap<u32, BitmapGlyph>,
+    missing: Option<BitmapGlyph>,
+    kerning: HashMap<(u32, u32), f32>,
+}
+
+/// Creation and loading
+impl BitmapFont {
+    /// Load a bitmap font from a `.fnt` file, along with its page textures.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, BitmapFontError> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(BitmapFontError::Io)?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        Self::from_memory(&data, |file| {
+            let page_path = dir.join(file);
+            let page_path = page_path.to_str().ok_or(SfError::CallFailed)?;
+            Texture::from_file(page_path)
+        })
+    }
+
+    /// Load a bitmap font from the contents of a `.fnt` file.
+    ///
+    /// `load_page` is called with the file name of each page, as written in the descriptor,
+    /// and returns its texture.
+    pub fn from_memory(
+        data: &[u8],
+        mut load_page: impl FnMut(&str) -> Result<FBox<Texture>, SfError>,
+    ) -> Result<Self, BitmapFontError> {
+        let descriptor = if data.starts_with(b"BMF") {
+            parse_binary(data)?
+        } else {
+            let text = std::str::from_utf8(data)
+                .map_err(|_| BitmapFontError::Parse("descriptor is not valid UTF-8".into()))?;
+            parse_text(text)?
+        };
+        let pages = descriptor
+            .pages
+            .values()
+            .map(|file| {
+                load_page(file).map_err(|error| BitmapFontError::Page {
+                    file: file.clone(),
+                    error,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            size: descriptor.size,
+            line_height: descriptor.line_height,
+            base: descriptor.base,
+            pages,
+            glyphs: descriptor.glyphs,
+            missing: descriptor.missing,
+            kerning: descriptor.kerning,
+        })
+    }
+}
+
+/// Metrics and pages
+impl BitmapFont {
+    /// Get the size the font was rendered at, in pixels.
+    ///
+    /// It is negative if the font was rendered with a character height rather than
+    /// a cell height.
+    #[must_use]
+    pub fn size(&self) -> i32 {
+        self.size
+    }
+
+    /// Get the distance between two lines, in pixels.
+    #[must_use]
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    /// Get the distance from the top of a line to the baseline, in pixels.
+    #[must_use]
+    pub fn base(&self) -> f32 {
+        self.base
+    }
+
+    /// Get the character for `c`.
+    ///
+    /// If the font doesn't have it, its replacement character is returned if it has one.
+    #[must_use]
+    pub fn glyph(&self, c: char) -> Option<&BitmapGlyph> {
+        self.glyphs.get(&u32::from(c)).or(self.missing.as_ref())
+    }
+
+    /// Tell whether the font has a character for `c`.
+    #[must_use]
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.glyphs.contains_key(&u32::from(c))
+    }
+
+    /// Get the kerning offset between two characters, in pixels.
+    #[must_use]
+    pub fn kerning(&self, first: char, second: char) -> f32 {
+        self.kerning
+            .get(&(u32::from(first), u32::from(second)))
+            .copied()
+            .unwrap_or(0.)
+    }
+
+    /// Get the number of page textures.
+    #[must_use]
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Get a page texture.
+    #[must_use]
+    pub fn page(&self, index: usize) -> Option<&Texture> {
+        self.pages.get(index).map(|page| &**page)
+    }
+}
+
+/// Error that can happen when loading a [`BitmapFont`]
+#[derive(Debug)]
+pub enum BitmapFontError {
+    /// The descriptor could not be read
+    Io(io::Error),
+    /// The descriptor is malformed
+    Parse(String),
+    /// A page texture could not be loaded
+    Page {
+        /// File name of the page
+        file: String,
+        /// Error that happened when loading it
+        error: SfError,
+    },
+}
+
+impl Error for BitmapFontError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(_) => None,
+            Self::Page { error, .. } => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for BitmapFontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read bitmap font: {error}"),
+            Self::Parse(message) => write!(f, "malformed bitmap font: {message}"),
+            Self::Page { file, error } => {
+                write!(f, "failed to load bitmap font page `{file}`: {error}")
+            }
+        }
+    }
+}
+
+/// Contents of a `.fnt` file
+#[derive(Default)]
+struct Descriptor {
+    size: i32,
+    line_height: f32,
+    base: f32,
+    /// Page files by id. Ids come from the file, so they are only checked to have no gaps
+    /// once the whole file is parsed.
+    pages: BTreeMap<usize, String>,
+    glyphs: HashMap<u32, BitmapGlyph>,
+    missing: Option<BitmapGlyph>,
+    kerning: HashMap<(u32, u32), f32>,
+}
+
+impl Descriptor {
+    fn set_page(&mut self, id: usize, file: String) {
+        let _ = self.pages.insert(id, file);
+    }
+
+    /// Check that the pages are numbered without gaps, and that the characters are on them
+    fn validate(self) -> Result<Self, BitmapFontError> {
+        if let Some(id) = (0..)
+            .zip(self.pages.keys())
+            .find_map(|(i, &id)| (i != id).then_some(i))
+        {
+            return Err(parse_error(format!("page {id} is missing")));
+        }
+        if let Some(glyph) = self
+            .glyphs
+            .values()
+            .chain(self.missing.iter())
+            .find(|glyph| glyph.page >= self.pages.len())
+        {
+            return Err(parse_error(format!(
+                "character refers to missing page {}",
+                glyph.page
+            )));
+        }
+        Ok(self)
+    }
+
+    fn add_glyph(&mut self, id: i64, glyph: BitmapGlyph) {
+        match u32::try_from(id) {
+            Ok(id) => {
+                let _ = self.glyphs.insert(id, glyph);
+            }
+            // Some generators write the replacement character with id -1
+            Err(_) => self.missing = Some(glyph),
+        }
+    }
+}
+
+fn parse_error(message: impl Into<String>) -> BitmapFontError {
+    BitmapFontError::Parse(message.into())
+}
+
+/// Split a line of the text format into its tag and `key=value` attributes
+fn tokenize(line: &str) -> Option<(&str, Vec<(&str, &str)>)> {
+    let line = line.trim();
+    let (tag, mut rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let (key, after) = rest.split_once('=')?;
+        let (value, after) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"')?;
+                (&quoted[..end], &quoted[end + 1..])
+            }
+            None => after.split_once(char::is_whitespace).unwrap_or((after, "")),
+        };
+        attributes.push((key.trim(), value));
+        rest = after;
+    }
+    Some((tag, attributes))
+}
+
+fn parse_text(text: &str) -> Result<Descriptor, BitmapFontError> {
+    let mut descriptor = Descriptor::default();
+    for (line_number, line) in text.lines().enumerate() {
+        let line_number = line_number + 1;
+        let (tag, attributes) = tokenize(line)
+            .ok_or_else(|| parse_error(format!("invalid attributes on line {line_number}")))?;
+        let get = |key: &str| -> Result<i64, BitmapFontError> {
+            let value = attributes
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| *v)
+                .ok_or_else(|| parse_error(format!("missing `{key}` on line {line_number}")))?;
+            value
+                .parse()
+                .map_err(|_| parse_error(format!("invalid `{key}` value on line {line_number}")))
+        };
+        let get_or = |key: &str, default: i64| {
+            if attributes.iter().any(|(k, _)| *k == key) {
+                get(key)
+            } else {
+                Ok(default)
+            }
+        };
+        match tag {
+            "info" => descriptor.size = to_i32(get_or("size", 0)?)?,
+            "common" => {
+                descriptor.line_height = to_f32(get("lineHeight")?)?;
+                descriptor.base = to_f32(get("base")?)?;
+            }
+            "page" => {
+                let file = attributes
+                    .iter()
+                    .find(|(k, _)| *k == "file")
+                    .map(|(_, v)| (*v).to_owned())
+                    .ok_or_else(|| parse_error(format!("missing `file` on line {line_number}")))?;
+                let id = usize::try_from(get("id")?)
+                    .map_err(|_| parse_error(format!("invalid page id on line {line_number}")))?;
+                descriptor.set_page(id, file);
+            }
+            "char" => {
+                let glyph = BitmapGlyph {
+                    texture_rect: IntRect::new(
+                        Vector2i::new(to_i32(get("x")?)?, to_i32(get("y")?)?),
+                        Vector2i::new(to_i32(get("width")?)?, to_i32(get("height")?)?),
+                    ),
+                    offset: Vector2f::new(to_f32(get("xoffset")?)?, to_f32(get("yoffset")?)?),
+                    advance: to_f32(get("xadvance")?)?,
+                    page: usize::try_from(get_or("page", 0)?)
+                        .map_err(|_| parse_error(format!("invalid page on line {line_number}")))?,
+                };
+                descriptor.add_glyph(get("id")?, glyph);
+            }
+            "kerning" => {
+                let first = u32::try_from(get("first")?);
+                let second = u32::try_from(get("second")?);
+                if let (Ok(first), Ok(second)) = (first, second) {
+                    let _ = descriptor
+                        .kerning
+                        .insert((first, second), to_f32(get("amount")?)?);
+                }
+            }
+            // `chars`, `kernings` and unknown tags only carry information we don't need
+            _ => {}
+        }
+    }
+    descriptor.validate()
+}
+
+fn to_i32(value: i64) -> Result<i32, BitmapFontError> {
+    i32::try_from(value).map_err(|_| parse_error(format!("value {value} is out of range")))
+}
+
+fn to_f32(value: i64) -> Result<f32, BitmapFontError> {
+    to_i32(value).map(|value| value as f32)
+}
+
+/// Little-endian reader for the binary format
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], BitmapFontError> {
+        if self.data.len() < len {
+            return Err(parse_error("unexpected end of data"));
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], BitmapFontError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, BitmapFontError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, BitmapFontError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn i16(&mut self) -> Result<i16, BitmapFontError> {
+        Ok(i16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, BitmapFontError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+}
+
+fn parse_binary(data: &[u8]) -> Result<Descriptor, BitmapFontError> {
+    let mut reader = Reader { data };
+    let header = reader.array::<4>()?;
+    if header[3] != 3 {
+        return Err(parse_error(format!(
+            "unsupported binary format version {}",
+            header[3]
+        )));
+    }
+    let mut descriptor = Descriptor::default();
+    while !reader.data.is_empty() {
+        let block_type = reader.u8()?;
+        let len = usize::try_from(reader.u32()?).map_err(|_| parse_error("block is too large"))?;
+        let mut block = Reader {
+            data: reader.bytes(len)?,
+        };
+        match block_type {
+            1 => descriptor.size = i32::from(block.i16()?),
+            2 => {
+                descriptor.line_height = f32::from(block.u16()?);
+                descriptor.base = f32::from(block.u16()?);
+            }
+            3 => {
+                // Nul-terminated file names
+                for (id, file) in block.data.split(|&b| b == 0).enumerate() {
+                    if !file.is_empty() {
+                        let file = String::from_utf8(file.to_vec())
+                            .map_err(|_| parse_error("page file name is not valid UTF-8"))?;
+                        descriptor.set_page(id, file);
+                    }
+                }
+            }
+            4 => {
+                while !block.data.is_empty() {
+                    let id = block.u32()?;
+                    let x = block.u16()?;
+                    let y = block.u16()?;
+                    let width = block.u16()?;
+                    let height = block.u16()?;
+                    let xoffset = block.i16()?;
+                    let yoffset = block.i16()?;
+                    let xadvance = block.i16()?;
+                    let page = block.u8()?;
+                    let _channel = block.u8()?;
+                    let glyph = BitmapGlyph {
+                        texture_rect: IntRect::new(
+                            Vector2i::new(x.into(), y.into()),
+                            Vector2i::new(width.into(), height.into()),
+                        ),
+                        offset: Vector2f::new(xoffset.into(), yoffset.into()),
+                        advance: xadvance.into(),
+                        page: page.into(),
+                    };
+                    // -1 is stored as u32::MAX
+                    let id = if id == u32::MAX { -1 } else { i64::from(id) };
+                    descriptor.add_glyph(id, glyph);
+                }
+            }
+            5 => {
+                while !block.data.is_empty() {
+                    let first = block.u32()?;
+                    let second = block.u32()?;
+                    let amount = block.i16()?;
+                    let _ = descriptor
+                        .kerning
+                        .insert((first, second), f32::from(amount));
+                }
+            }
+            _ => {}
+        }
+    }
+    descriptor.validate()
+}
+
+/// Text rendered with a [`BitmapFont`].
+///
+/// `BitmapText` mirrors the API of [`Text`], except that the size of the characters
+/// is set with a scale factor, since bitmap fonts only have one size.
+///
+/// [`Text`]: crate::graphics::Text
+#[derive(Debug, Clone)]
+pub struct BitmapText<'s> {
+    string: String,
+    font: &'s BitmapFont,
+    character_scale: f32,
+    fill_color: Color,
+    letter_spacing: f32,
+    line_spacing: f32,
+    transformation: Transformation,
+    /// Vertices for each page of the font
+    pages: Vec<Vec<Vertex>>,
+    bounds: FloatRect,
+}
+
+/// Creation
+impl<'s> BitmapText<'s> {
+    /// Create a new bitmap text, at the native size of the font.
+    #[must_use]
+    pub fn new(string: &str, font: &'s BitmapFont) -> Self {
+        let mut text = Self {
+            string: string.to_owned(),
+            font,
+            character_scale: 1.,
+            fill_color: Color::WHITE,
+            letter_spacing: 1.,
+            line_spacing: 1.,
+            transformation: Transformation::default(),
+            pages: Vec::new(),
+            bounds: FloatRect::default(),
+        };
+        text.update();
+        text
+    }
+}
+
+/// Properties
+impl<'s> BitmapText<'s> {
+    /// Set the string of the text.
+    pub fn set_string(&mut self, string: &str) {
+        string.clone_into(&mut self.string);
+        self.update();
+    }
+
+    /// Get the string of the text.
+    #[must_use]
+    pub fn string(&self) -> &str {
+        &self.string
+    }
+
+    /// Set the font of the text.
+    pub fn set_font(&mut self, font: &'s BitmapFont) {
+        self.font = font;
+        self.update();
+    }
+
+    /// Get the font of the text.
+    #[must_use]
+    pub fn font(&self) -> &'s BitmapFont {
+        self.font
+    }
+
+    /// Set the scale of the characters, relative to the size of the font.
+    ///
+    /// Unlike [`Transformable::set_scale`], this doesn't scale the letter and line spacing
+    /// factors. Use integer scales to keep pixel art crisp. The default scale is 1.
+    pub fn set_character_scale(&mut self, scale: f32) {
+        self.character_scale = scale;
+        self.update();
+    }
+
+    /// Get the scale of the characters.
+    #[must_use]
+    pub fn character_scale(&self) -> f32 {
+        self.character_scale
+    }
+
+    /// Set the fill color of the text.
+    ///
+    /// The color multiplies the colors of the font pages. By default, it is opaque white.
+    pub fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+        for vertex in self.pages.iter_mut().flatten() {
+            vertex.color = color;
+        }
+    }
+
+    /// Get the fill color of the text.
+    #[must_use]
+    pub fn fill_color(&self) -> Color {
+        self.fill_color
+    }
+
+    /// Set the letter spacing factor. See [`Text::set_letter_spacing`].
+    ///
+    /// [`Text::set_letter_spacing`]: crate::graphics::Text::set_letter_spacing
+    pub fn set_letter_spacing(&mut self, factor: f32) {
+        self.letter_spacing = factor;
+        self.update();
+    }
+
+    /// Get the letter spacing factor.
+    #[must_use]
+    pub fn letter_spacing(&self) -> f32 {
+        self.letter_spacing
+    }
+
+    /// Set the line spacing factor. See [`Text::set_line_spacing`].
+    ///
+    /// [`Text::set_line_spacing`]: crate::graphics::Text::set_line_spacing
+    pub fn set_line_spacing(&mut self, factor: f32) {
+        self.line_spacing = factor;
+        self.update();
+    }
+
+    /// Get the line spacing factor.
+    #[must_use]
+    pub fn line_spacing(&self) -> f32 {
+        self.line_spacing
+    }
+}
+
+/// Geometry
+impl BitmapText<'_> {
+    /// Return the position of the `index`-th character, in global coordinates.
+    ///
+    /// If `index` is out of range, the position of the end of the string is returned.
+    #[must_use]
+    pub fn find_character_pos(&self, index: usize) -> Vector2f {
+        let layout = self.layout();
+        let mut position = Vector2f::default();
+        let mut prev = None;
+        for c in self.string.chars().take(index) {
+            position = layout.advance(position, prev, c);
+            prev = Some(c);
+        }
+        self.transform().transform_point(position)
+    }
+
+    /// Get the local bounding rectangle of the text.
+    #[must_use]
+    pub fn local_bounds(&self) -> FloatRect {
+        self.bounds
+    }
+
+    /// Get the global bounding rectangle of the text.
+    #[must_use]
+    pub fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(self.bounds)
+    }
+
+    fn layout(&self) -> Layout<'_> {
+        let scale = self.character_scale;
+        let space_advance = self.font.glyph(' ').map_or(0., |glyph| glyph.advance);
+        Layout {
+            font: self.font,
+            scale,
+            letter_spacing: (space_advance / 3.) * (self.letter_spacing - 1.) * scale,
+            line_height: self.font.line_height * self.line_spacing * scale,
+        }
+    }
+
+    fn update(&mut self) {
+        let layout = self.layout();
+        let mut pages = vec![Vec::new(); self.font.pages.len()];
+        let mut min = Vector2f::new(f32::INFINITY, f32::INFINITY);
+        let mut max = Vector2f::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+        let mut pen = Vector2f::default();
+        let mut prev = None;
+        for c in self.string.chars() {
+            let glyph = if c.is_whitespace() {
+                None
+            } else {
+                self.font.glyph(c)
+            };
+            if let Some(glyph) = glyph {
+                let pen = pen + Vector2f::new(layout.kerning(prev, c), 0.);
+                let rect = glyph.texture_rect.as_other::<f32>();
+                let top_left = pen + glyph.offset * layout.scale;
+                let bottom_right = top_left + rect.size * layout.scale;
+                let uv_min = rect.position;
+                let uv_max = rect.position + rect.size;
+                let vertex = |x: f32, y: f32, u: f32, v: f32| {
+                    Vertex::new(Vector2f::new(x, y), self.fill_color, Vector2f::new(u, v))
+                };
+                let tl = vertex(top_left.x, top_left.y, uv_min.x, uv_min.y);
+                let tr = vertex(bottom_right.x, top_left.y, uv_max.x, uv_min.y);
+                let bl = vertex(top_left.x, bottom_right.y, uv_min.x, uv_max.y);
+                let br = vertex(bottom_right.x, bottom_right.y, uv_max.x, uv_max.y);
+                pages[glyph.page].extend([tl, tr, bl, bl, tr, br]);
+                min = Vector2f::new(min.x.min(top_left.x), min.y.min(top_left.y));
+                max = Vector2f::new(max.x.max(bottom_right.x), max.y.max(bottom_right.y));
+            }
+            pen = layout.advance(pen, prev, c);
+            prev = Some(c);
+        }
+        self.bounds = if min.x <= max.x {
+            FloatRect::new(min, max - min)
+        } else {
+            FloatRect::default()
+        };
+        self.pages = pages;
+    }
+}
+
+/// Pen movement rules shared by geometry and character positions
+struct Layout<'f> {
+    font: &'f BitmapFont,
+    scale: f32,
+    letter_spacing: f32,
+    line_height: f32,
+}
+
+impl Layout<'_> {
+    fn kerning(&self, prev: Option<char>, c: char) -> f32 {
+        prev.map_or(0., |prev| self.font.kerning(prev, c) * self.scale)
+    }
+
+    /// Position of the pen after `c`
+    fn advance(&self, pen: Vector2f, prev: Option<char>, c: char) -> Vector2f {
+        let space = || {
+            self.font
+                .glyph(' ')
+                .map_or(0., |glyph| glyph.advance * self.scale)
+                + self.letter_spacing
+        };
+        match c {
+            '\n' => Vector2f::new(0., pen.y + self.line_height),
+            '\r' => pen,
+            '\t' => Vector2f::new(pen.x + space() * 4., pen.y),
+            ' ' => Vector2f::new(pen.x + space(), pen.y),
+            _ => {
+                let advance = self.font.glyph(c).map_or(0., |glyph| glyph.advance);
+                Vector2f::new(
+                    pen.x + self.kerning(prev, c) + advance * self.scale + self.letter_spacing,
+                    pen.y,
+                )
+            }
+        }
+    }
+}
+
+impl_transformable_via!(BitmapText<'_>, transformation);
+
+impl Drawable for BitmapText<'_> {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        let transform = states.transform * *self.transform();
+        for (vertices, page) in self.pages.iter().zip(&self.font.pages) {
+            if vertices.is_empty() {
+                continue;
+            }
+            let states = RenderStates {
+                transform,
+                texture: Some(page),
+                ..*states
+            };
+            target.draw_primitives(vertices, PrimitiveType::TRIANGLES, &states);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = r#"info face="Pixel" size=16 bold=0 italic=0 charset="" padding=0,0,0,0
+common lineHeight=18 base=14 scaleW=64 scaleH=64 pages=2 packed=0
+page id=1 file="pixel 1.png"
+page id=0 file="pixel_0.png"
+chars count=3
+char id=65   x=0     y=0     width=8     height=10    xoffset=0     yoffset=4     xadvance=9     page=0  chnl=15
+char id=66   x=8     y=0     width=8     height=10    xoffset=1     yoffset=-2    xadvance=9     page=1  chnl=15
+char id=-1   x=16    y=0     width=6     height=10    xoffset=0     yoffset=4     xadvance=7     page=0  chnl=15
+kernings count=2
+kerning first=65  second=66  amount=-2
+kerning first=66  second=65  amount=1
+"#;
+
+    fn glyph(x: i32, offset: (f32, f32), advance: f32, page: usize) -> BitmapGlyph {
+        BitmapGlyph {
+            texture_rect: IntRect::new(Vector2i::new(x, 0), Vector2i::new(8, 10)),
+            offset: offset.into(),
+            advance,
+            page,
+        }
+    }
+
+    fn check_fixture(descriptor: &Descriptor) {
+        assert_eq!(descriptor.size, 16);
+        assert_eq!(descriptor.line_height, 18.);
+        assert_eq!(descriptor.base, 14.);
+        let pages: Vec<_> = descriptor.pages.values().collect();
+        assert_eq!(pages, ["pixel_0.png", "pixel 1.png"]);
+        assert_eq!(descriptor.glyphs.len(), 2);
+        assert_eq!(descriptor.glyphs.get(&65), Some(&glyph(0, (0., 4.), 9., 0)));
+        assert_eq!(
+            descriptor.glyphs.get(&66),
+            Some(&glyph(8, (1., -2.), 9., 1))
+        );
+        let missing = descriptor
+            .missing
+            .map(|glyph| (glyph.texture_rect.size.x, glyph.advance));
+        assert_eq!(missing, Some((6, 7.)));
+        assert_eq!(descriptor.kerning.get(&(65, 66)), Some(&-2.));
+        assert_eq!(descriptor.kerning.get(&(66, 65)), Some(&1.));
+        assert_eq!(descriptor.kerning.len(), 2);
+    }
+
+    fn block(data: &mut Vec<u8>, block_type: u8, content: &[u8]) {
+        data.push(block_type);
+        data.extend(
+            u32::try_from(content.len())
+                .unwrap_or(u32::MAX)
+                .to_le_bytes(),
+        );
+        data.extend(content);
+    }
+
+    fn binary_char(
+        id: u32,
+        x: u16,
+        width: u16,
+        offset: (i16, i16),
+        advance: i16,
+        page: u8,
+    ) -> Vec<u8> {
+        let mut data = id.to_le_bytes().to_vec();
+        for value in [x, 0, width, 10] {
+            data.extend(value.to_le_bytes());
+        }
+        for value in [offset.0, offset.1, advance] {
+            data.extend(value.to_le_bytes());
+        }
+        data.extend([page, 15]);
+        data
+    }
+
+    #[test]
+    fn text_format() {
+        let descriptor = parse_text(TEXT);
+        assert!(descriptor.is_ok());
+        if let Ok(descriptor) = descriptor {
+            check_fixture(&descriptor);
+        }
+    }
+
+    #[test]
+    fn binary_format() {
+        let mut data = b"BMF\x03".to_vec();
+        // Font size, then other info we don't read, then the font name
+        block(
+            &mut data,
+            1,
+            b"\x10\x00\x00\x00\x64\x00\x01\x00\x00\x00\x00\x00\x00\x00Pixel\x00",
+        );
+        // Line height and base, then other common info we don't read
+        block(
+            &mut data,
+            2,
+            b"\x12\x00\x0e\x00\x40\x00\x40\x00\x02\x00\x00\x00\x00\x00\x00",
+        );
+        block(&mut data, 3, b"pixel_0.png\0pixel 1.png\0");
+        let chars = [
+            binary_char(65, 0, 8, (0, 4), 9, 0),
+            binary_char(66, 8, 8, (1, -2), 9, 1),
+            binary_char(u32::MAX, 16, 6, (0, 4), 7, 0),
+        ];
+        block(&mut data, 4, &chars.concat());
+        let mut kernings = Vec::new();
+        for (first, second, amount) in [(65u32, 66u32, -2i16), (66, 65, 1)] {
+            kernings.extend(first.to_le_bytes());
+            kernings.extend(second.to_le_bytes());
+            kernings.extend(amount.to_le_bytes());
+        }
+        block(&mut data, 5, &kernings);
+        let descriptor = parse_binary(&data);
+        assert!(descriptor.is_ok());
+        if let Ok(descriptor) = descriptor {
+            check_fixture(&descriptor);
+        }
+        assert!(parse_binary(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn invalid_pages() {
+        let sparse = "page id=0 file=\"a.png\"\npage id=2 file=\"c.png\"\n";
+        let error = parse_text(sparse).err().map(|error| error.to_string());
+        assert_eq!(
+            error.as_deref(),
+            Some("malformed bitmap font: page 1 is missing")
+        );
+        // Huge ids are not allocated for
+        for id in ["4000000000", &i64::MAX.to_string()] {
+            let huge = format!("page id=0 file=a.png\npage id={id} file=b.png\n");
+            let error = parse_text(&huge).err().map(|error| error.to_string());
+            assert_eq!(
+                error.as_deref(),
+                Some("malformed bitmap font: page 1 is missing")
+            );
+        }
+        let missing = "page id=0 file=a.png\nchar id=65 x=0 y=0 width=1 height=1 xoffset=0 \
+                       yoffset=0 xadvance=1 page=1\n";
+        assert!(matches!(
+            parse_text(missing),
+            Err(BitmapFontError::Parse(_))
+        ));
+        let mut data = b"BMF\x03".to_vec();
+        block(&mut data, 3, b"a.png\0\0c.png\0");
+        assert!(matches!(
+            parse_binary(&data),
+            Err(BitmapFontError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,414 @@
+use crate::{
+    cpp::FBox,
+    graphics::{FloatRect, RenderTarget, View},
+    system::{Angle, Time, Vector2f, Vector2i},
+};
+
+/// A camera that drives a [`View`].
+///
+/// The camera can follow a target with smoothing and a dead zone, stay inside the bounds of
+/// the world, shake, and zoom around a point of the screen.
+/// Call [`update`] once per frame with the elapsed time, then draw with [`view`].
+///
+/// [`update`]: Camera::update
+/// [`view`]: Camera::view
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::{graphics::*, system::*, window::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let player_position = Vector2f::new(0., 0.);
+/// let mut camera = Camera::new((0., 0.), (800., 600.));
+/// camera.set_smooth_time(Time::milliseconds(250));
+/// camera.set_dead_zone((80., 60.));
+/// camera.set_bounds(Some(FloatRect::new((0., 0.).into(), (4000., 3000.).into())));
+/// let mut clock = Clock::new().unwrap();
+/// loop {
+///     while let Some(event) = window.poll_event() {
+///         if let Event::MouseWheelScrolled { delta, position, .. } = event {
+///             camera.zoom_at(position, 0.9_f32.powf(delta), &window);
+///         }
+///     }
+///     camera.follow(player_position);
+///     camera.update(clock.restart());
+///     window.set_view(camera.view());
+///     // Draw the world...
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Camera {
+    view: FBox<View>,
+    position: Vector2f,
+    size: Vector2f,
+    zoom: f32,
+    rotation: Angle,
+    target: Option<Vector2f>,
+    velocity: Vector2f,
+    smooth_time: Time,
+    dead_zone: Vector2f,
+    bounds: Option<FloatRect>,
+    trauma: f32,
+    trauma_decay: f32,
+    shake_offset: Vector2f,
+    shake_angle: Angle,
+    shake_frequency: f32,
+    shake_time: f32,
+}
+
+/// Creation
+impl Camera {
+    /// Create a camera showing an area of `size` centered on `center`.
+    #[must_use]
+    pub fn new<C: Into<Vector2f>, S: Into<Vector2f>>(center: C, size: S) -> Self {
+        let (center, size) = (center.into(), size.into());
+        Self {
+            view: View::with_center_and_size(center, size),
+            position: center,
+            size,
+            zoom: 1.,
+            rotation: Angle::default(),
+            target: None,
+            velocity: Vector2f::default(),
+            smooth_time: Time::ZERO,
+            dead_zone: Vector2f::default(),
+            bounds: None,
+            trauma: 0.,
+            trauma_decay: 1.,
+            shake_offset: Vector2f::new(16., 16.),
+            shake_angle: Angle::degrees(3.),
+            shake_frequency: 15.,
+            shake_time: 0.,
+        }
+    }
+}
+
+/// Position and zoom
+impl Camera {
+    /// Get the view to draw with.
+    ///
+    /// The view is only changed by [`Camera::update`] and [`Camera::zoom_at`].
+    #[must_use]
+    pub fn view(&self) -> &View {
+        &self.view
+    }
+
+    /// Move the camera to `position` at once, without smoothing.
+    pub fn set_position<P: Into<Vector2f>>(&mut self, position: P) {
+        self.position = position.into();
+        self.velocity = Vector2f::default();
+        self.clamp_to_bounds();
+    }
+
+    /// Get the center of the camera, without the shake.
+    #[must_use]
+    pub fn position(&self) -> Vector2f {
+        self.position
+    }
+
+    /// Set the size of the area shown by the camera at a zoom of 1.
+    pub fn set_size<S: Into<Vector2f>>(&mut self, size: S) {
+        self.size = size.into();
+        self.clamp_to_bounds();
+    }
+
+    /// Get the size of the area shown by the camera at a zoom of 1.
+    #[must_use]
+    pub fn size(&self) -> Vector2f {
+        self.size
+    }
+
+    /// Set the zoom factor. The shown area is the size multiplied by the factor, so a factor
+    /// greater than 1 zooms out, like [`View::zoom`].
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom;
+        self.clamp_to_bounds();
+    }
+
+    /// Get the zoom factor.
+    #[must_use]
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Zoom by `factor` around a pixel of `target`, so that the world point under this pixel
+    /// stays under it, like zooming on the mouse cursor.
+    ///
+    /// If the camera follows a target, it moves back towards it on the next update.
+    pub fn zoom_at(&mut self, pixel: Vector2i, factor: f32, target: &dyn RenderTarget) {
+        let before = target.map_pixel_to_coords(pixel, &self.view);
+        self.zoom *= factor;
+        self.view.set_size(self.size * self.zoom);
+        let after = target.map_pixel_to_coords(pixel, &self.view);
+        self.position += before - after;
+        self.clamp_to_bounds();
+        self.apply();
+    }
+
+    /// Set the rotation of the camera.
+    pub fn set_rotation(&mut self, angle: Angle) {
+        self.rotation = angle;
+    }
+
+    /// Get the rotation of the camera, without the shake.
+    #[must_use]
+    pub fn rotation(&self) -> Angle {
+        self.rotation
+    }
+
+    /// Set the rectangle of the world that the camera must stay in, or `None` to let it go
+    /// anywhere.
+    ///
+    /// If the shown area is larger than the bounds, the camera is centered on them.
+    /// The rotation of the camera is not taken into account.
+    pub fn set_bounds(&mut self, bounds: Option<FloatRect>) {
+        self.bounds = bounds;
+        self.clamp_to_bounds();
+    }
+
+    /// Get the rectangle of the world that the camera must stay in.
+    #[must_use]
+    pub fn bounds(&self) -> Option<FloatRect> {
+        self.bounds
+    }
+}
+
+/// Following
+impl Camera {
+    /// Set the point to follow. Call it every frame with the position of what is followed.
+    pub fn follow<P: Into<Vector2f>>(&mut self, target: P) {
+        self.target = Some(target.into());
+    }
+
+    /// Stop following the target.
+    pub fn stop_following(&mut self) {
+        self.target = None;
+        self.velocity = Vector2f::default();
+    }
+
+    /// Get the point that is followed, if any.
+    #[must_use]
+    pub fn target(&self) -> Option<Vector2f> {
+        self.target
+    }
+
+    /// Set the time the camera takes to catch up with its target.
+    ///
+    /// The camera follows its target like a critically damped spring, which reaches the target
+    /// without overshooting it. [`Time::ZERO`], the default, makes the camera snap to it.
+    pub fn set_smooth_time(&mut self, time: Time) {
+        self.smooth_time = time;
+    }
+
+    /// Get the time the camera takes to catch up with its target.
+    #[must_use]
+    pub fn smooth_time(&self) -> Time {
+        self.smooth_time
+    }
+
+    /// Set the size of the dead zone, a rectangle at the center of the camera in which the
+    /// target can move without the camera following. The default size is zero.
+    ///
+    /// Negative and NaN sizes are treated as zero.
+    pub fn set_dead_zone<S: Into<Vector2f>>(&mut self, size: S) {
+        self.dead_zone = size.into();
+    }
+
+    /// Get the size of the dead zone.
+    #[must_use]
+    pub fn dead_zone(&self) -> Vector2f {
+        self.dead_zone
+    }
+}
+
+/// Shake
+impl Camera {
+    /// Add trauma, which makes the camera shake. Trauma is kept between 0 and 1.
+    ///
+    /// The shake grows with the square of the trauma, so small hits shake a little and big
+    /// hits a lot. Trauma decreases over time, see [`Camera::set_trauma_decay`].
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0., 1.);
+    }
+
+    /// Set the trauma directly.
+    pub fn set_trauma(&mut self, trauma: f32) {
+        self.trauma = trauma.clamp(0., 1.);
+    }
+
+    /// Get the current trauma.
+    #[must_use]
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Set how much trauma is removed per second. The default is 1.
+    pub fn set_trauma_decay(&mut self, per_second: f32) {
+        self.trauma_decay = per_second;
+    }
+
+    /// Set the largest offset and rotation of the shake, reached at a trauma of 1.
+    ///
+    /// The defaults are 16 units on each axis and 3 degrees.
+    pub fn set_shake_amplitude<O: Into<Vector2f>>(&mut self, offset: O, angle: Angle) {
+        self.shake_offset = offset.into();
+        self.shake_angle = angle;
+    }
+
+    /// Set how fast the camera shakes, in oscillations per second. The default is 15.
+    pub fn set_shake_frequency(&mut self, frequency: f32) {
+        self.shake_frequency = frequency;
+    }
+}
+
+/// Update
+impl Camera {
+    /// Advance the camera by `delta`: follow the target, decrease the trauma, and update the
+    /// view.
+    pub fn update(&mut self, delta: Time) {
+        let dt = delta.as_seconds();
+        if let Some(target) = self.target {
+            let goal = dead_zone_goal(self.position, target, self.dead_zone);
+            let smooth_time = self.smooth_time.as_seconds();
+            self.position = smooth_damp(self.position, &mut self.velocity, goal, smooth_time, dt);
+        }
+        self.clamp_to_bounds();
+        self.shake_time += dt;
+        self.trauma = (self.trauma - self.trauma_decay * dt).max(0.);
+        self.apply();
+    }
+
+    fn clamp_to_bounds(&mut self) {
+        if let Some(bounds) = self.bounds {
+            self.position = clamp_center(self.position, self.size * self.zoom.abs() / 2., bounds);
+        }
+    }
+
+    fn apply(&mut self) {
+        let shake = self.trauma * self.trauma;
+        let t = self.shake_time * self.shake_frequency;
+        let offset = Vector2f::new(
+            self.shake_offset.x * shake * noise(t, 0.),
+            self.shake_offset.y * shake * noise(t, 17.),
+        );
+        let angle = self.shake_angle * (shake * noise(t, 43.));
+        self.view.set_center(self.position + offset);
+        self.view.set_size(self.size * self.zoom);
+        self.view.set_rotation(self.rotation + angle);
+    }
+}
+
+/// Get the point the camera must move to so that `target` is inside the dead zone.
+fn dead_zone_goal(position: Vector2f, target: Vector2f, dead_zone: Vector2f) -> Vector2f {
+    // `clamp` panics if the minimum is above the maximum, or if either is NaN
+    let half = Vector2f::new(dead_zone.x.max(0.), dead_zone.y.max(0.)) / 2.;
+    let axis = |position: f32, target: f32, half: f32| position.clamp(target - half, target + half);
+    Vector2f::new(
+        axis(position.x, target.x, half.x),
+        axis(position.y, target.y, half.y),
+    )
+}
+
+/// Move from `position` towards `goal` like a critically damped spring.
+fn smooth_damp(
+    position: Vector2f,
+    velocity: &mut Vector2f,
+    goal: Vector2f,
+    smooth_time: f32,
+    dt: f32,
+) -> Vector2f {
+    if smooth_time <= 0. {
+        *velocity = Vector2f::default();
+        return goal;
+    }
+    // Approximation of the exact solution, from Game Programming Gems 4, chapter 1.10
+    let omega = 2. / smooth_time;
+    let x = omega * dt;
+    let decay = 1. / (1. + x + 0.48 * x * x + 0.235 * x * x * x);
+    let change = position - goal;
+    let temp = (*velocity + change * omega) * dt;
+    *velocity = (*velocity - temp * omega) * decay;
+    goal + (change + temp) * decay
+}
+
+/// Clamp the center of a view of half size `half` so that the view stays inside `bounds`, or
+/// center it on `bounds` if it is larger.
+fn clamp_center(position: Vector2f, half: Vector2f, bounds: FloatRect) -> Vector2f {
+    let axis = |position: f32, start: f32, length: f32, half: f32| {
+        if 2. * half >= length {
+            start + length / 2.
+        } else {
+            position.clamp(start + half, start + length - half)
+        }
+    };
+    Vector2f::new(
+        axis(position.x, bounds.position.x, bounds.size.x, half.x),
+        axis(position.y, bounds.position.y, bounds.size.y, half.y),
+    )
+}
+
+/// Smooth pseudo-random noise between -1 and 1.
+///
+/// The sum of sines of unrelated frequencies doesn't look periodic, and, unlike random
+/// offsets, doesn't jitter when the frame rate is high.
+fn noise(t: f32, seed: f32) -> f32 {
+    let t = t * std::f32::consts::TAU;
+    ((t + seed).sin() + 0.6 * (2.37 * t + 1.7 * seed).sin() + 0.3 * (5.13 * t + 2.9 * seed).sin())
+        / 1.9
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smooth_damp_converges_without_overshooting() {
+        let goal = Vector2f::new(100., -50.);
+        let mut position = Vector2f::default();
+        let mut velocity = Vector2f::default();
+        for _ in 0..120 {
+            position = smooth_damp(position, &mut velocity, goal, 0.25, 1. / 60.);
+            assert!(position.x <= goal.x && position.y >= goal.y);
+        }
+        assert!((position - goal).length() < 0.01);
+        assert!(velocity.length() < 0.1);
+        // A smooth time of 0 snaps to the goal
+        position = smooth_damp(Vector2f::default(), &mut velocity, goal, 0., 1. / 60.);
+        assert_eq!((position, velocity), (goal, Vector2f::default()));
+    }
+
+    #[test]
+    fn bounds() {
+        let bounds = FloatRect::new(Vector2f::new(0., 0.), Vector2f::new(1000., 100.));
+        let half = Vector2f::new(200., 75.);
+        // Larger than the bounds vertically, so centered on them
+        let position = clamp_center(Vector2f::new(50., 10.), half, bounds);
+        assert_eq!(position, Vector2f::new(200., 50.));
+        let position = clamp_center(Vector2f::new(990., 90.), half, bounds);
+        assert_eq!(position, Vector2f::new(800., 50.));
+        let inside = Vector2f::new(500., 50.);
+        let small = Vector2f::new(20., 20.);
+        assert_eq!(clamp_center(inside, small, bounds), inside);
+    }
+
+    #[test]
+    fn dead_zone() {
+        let dead_zone = Vector2f::new(100., 50.);
+        let position = Vector2f::new(0., 0.);
+        // Targets inside the dead zone don't move the camera
+        for target in [(0., 0.), (49., 24.), (-50., -25.)] {
+            assert_eq!(dead_zone_goal(position, target.into(), dead_zone), position);
+        }
+        // Targets outside move it just enough to be on the edge
+        let goal = dead_zone_goal(position, Vector2f::new(80., -40.), dead_zone);
+        assert_eq!(goal, Vector2f::new(30., -15.));
+        // Negative and NaN sizes follow the target exactly
+        for dead_zone in [
+            Vector2f::new(-100., -50.),
+            Vector2f::new(f32::NAN, f32::NAN),
+        ] {
+            let goal = dead_zone_goal(position, Vector2f::new(80., -40.), dead_zone);
+            assert_eq!(goal, Vector2f::new(80., -40.));
+        }
+    }
+}
@@ -0,0 +1,871 @@
+//! Collision and geometry queries between shapes.
+//!
+//! A [`Collider`] is the outline of a shape in world coordinates: a convex polygon, or a
+//! circle. Two colliders can be tested for intersection with the separating axis theorem,
+//! which also gives the minimum translation needed to separate them, as a [`Contact`].
+//! Colliders can also be tested against points, rays and segments.
+//!
+//! The shapes implementing [`Collidable`] create their collider on the fly from their points
+//! and transformations, which makes the queries on them convenient, but allocates. To test a
+//! shape against many others, create its collider once with [`Collidable::collider`].
+//!
+//! The outline thickness of the shapes is not taken into account.
+//!
+//! For sprites, whose textures have transparent pixels, [`CollisionMask`] and
+//! [`sprites_overlap`] test the collisions at pixel level.
+//!
+//! # Usage example
+//!
+//! ```no_run
+//! use sfml::{
+//!     graphics::{CircleShape, RectangleShape, Transformable, collision::Collidable},
+//!     system::Angle,
+//! };
+//!
+//! let mut wall = RectangleShape::with_size((100., 20.).into());
+//! wall.set_rotation(Angle::degrees(30.));
+//! let mut ball = CircleShape::new(10., 30);
+//! ball.set_position((40., 30.));
+//! if let Some(contact) = wall.collide(&ball) {
+//!     // Push the ball out of the wall
+//!     ball.move_(contact.translation());
+//! }
+//! ```
+
+use crate::{
+    SfResult,
+    graphics::{
+        CircleShape, ConvexShape, FloatRect, Image, IntRect, RectangleShape, Shape, Sprite,
+        Texture, Transform, Transformable,
+    },
+    system::{Vector2f, Vector2i, Vector2u},
+};
+
+/// The outline of a shape in world coordinates, used for collision queries
+#[derive(Debug, Clone, PartialEq)]
+pub enum Collider {
+    /// A convex polygon, with its points in order, clockwise or counter-clockwise
+    ///
+    /// The intersection tests assume the polygon is convex, and give wrong results otherwise.
+    /// [`Collider::contains`] and the ray casts work with any simple polygon.
+    Polygon(Vec<Vector2f>),
+    /// A circle
+    Circle {
+        /// Center of the circle
+        center: Vector2f,
+        /// Radius of the circle
+        radius: f32,
+    },
+}
+
+/// The overlap of two intersecting colliders
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// Unit vector along which the colliders overlap the least, pointing from the first
+    /// collider towards the second one
+    pub normal: Vector2f,
+    /// Length of the overlap along `normal`
+    pub depth: f32,
+}
+
+impl Contact {
+    /// The minimum translation vector: moving the second collider by this vector, or the
+    /// first one by its opposite, separates them.
+    #[must_use]
+    pub fn translation(&self) -> Vector2f {
+        self.normal * self.depth
+    }
+}
+
+/// Where a ray or a segment hits a collider
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// Distance from the origin of the ray to the hit point
+    pub distance: f32,
+    /// The hit point
+    pub point: Vector2f,
+    /// Unit normal of the outline at the hit point, facing the origin of the ray
+    pub normal: Vector2f,
+}
+
+/// Creation
+impl Collider {
+    /// Create a polygon collider from the points of `shape`, in world coordinates.
+    ///
+    /// The points of a [`CircleShape`] give a polygon approximating the circle: use
+    /// [`Collidable::collider`] to get an exact circle.
+    pub fn from_shape<'texture>(shape: &impl Shape<'texture>) -> Self {
+        Self::Polygon(shape.world_points().collect())
+    }
+}
+
+/// Queries
+impl Collider {
+    /// Returns the smallest axis-aligned rectangle containing the collider.
+    #[must_use]
+    pub fn bounds(&self) -> FloatRect {
+        match self {
+            Self::Polygon(points) => {
+                FloatRect::from_points(points.iter().copied()).unwrap_or_default()
+            }
+            Self::Circle { center, radius } => {
+                FloatRect::new(*center, Vector2f::default()).inflate(*radius)
+            }
+        }
+    }
+
+    /// Check if a point is inside the collider.
+    #[must_use]
+    pub fn contains(&self, point: Vector2f) -> bool {
+        match self {
+            Self::Polygon(points) => point_in_polygon(point, points),
+            Self::Circle { center, radius } => (point - *center).length_sq() <= radius * radius,
+        }
+    }
+
+    /// Test if two colliders intersect, and return how they overlap.
+    ///
+    /// Colliders that only touch don't intersect.
+    #[must_use]
+    pub fn collide(&self, other: &Collider) -> Option<Contact> {
+        match (self, other) {
+            (Self::Polygon(a), Self::Polygon(b)) => {
+                let axes = edge_normals(a).chain(edge_normals(b));
+                separating_axis(axes, |axis| project(a, axis), |axis| project(b, axis))
+                    .map(|contact| orient(contact, centroid(a), centroid(b)))
+            }
+            (Self::Polygon(polygon), Self::Circle { center, radius }) => {
+                polygon_circle(polygon, *center, *radius)
+            }
+            (Self::Circle { center, radius }, Self::Polygon(polygon)) => {
+                polygon_circle(polygon, *center, *radius).map(|contact| Contact {
+                    normal: -contact.normal,
+                    ..contact
+                })
+            }
+            (
+                Self::Circle {
+                    center: center_a,
+                    radius: radius_a,
+                },
+                Self::Circle {
+                    center: center_b,
+                    radius: radius_b,
+                },
+            ) => {
+                let offset = *center_b - *center_a;
+                let distance = offset.length();
+                let depth = radius_a + radius_b - distance;
+                if depth <= 0. {
+                    return None;
+                }
+                let normal = if distance > 0. {
+                    offset / distance
+                } else {
+                    Vector2f::new(1., 0.)
+                };
+                Some(Contact { normal, depth })
+            }
+        }
+    }
+
+    /// Test if two colliders intersect.
+    #[must_use]
+    pub fn intersects(&self, other: &Collider) -> bool {
+        self.collide(other).is_some()
+    }
+
+    /// Cast a ray from `origin` in `direction`, and return where it first hits the outline of
+    /// the collider, if it does within `max_distance`.
+    ///
+    /// `direction` doesn't need to be normalized. A ray starting inside the collider hits it at
+    /// its origin, with a normal opposite to `direction`. A zero `direction` never hits.
+    #[must_use]
+    pub fn cast_ray(
+        &self,
+        origin: Vector2f,
+        direction: Vector2f,
+        max_distance: f32,
+    ) -> Option<RayHit> {
+        if direction.length_sq() == 0. {
+            return None;
+        }
+        let direction = direction.normalized();
+        if self.contains(origin) {
+            return Some(RayHit {
+                distance: 0.,
+                point: origin,
+                normal: -direction,
+            });
+        }
+        let (distance, normal) = match self {
+            Self::Polygon(points) => ray_polygon(points, origin, direction)?,
+            Self::Circle { center, radius } => {
+                let to_origin = origin - *center;
+                let b = to_origin.dot(direction);
+                let discriminant = b * b - (to_origin.length_sq() - radius * radius);
+                if discriminant < 0. {
+                    return None;
+                }
+                let distance = -b - discriminant.sqrt();
+                if distance < 0. {
+                    return None;
+                }
+                let normal = (origin + direction * distance - *center) / *radius;
+                (distance, normal)
+            }
+        };
+        (distance <= max_distance).then(|| RayHit {
+            distance,
+            point: origin + direction * distance,
+            normal,
+        })
+    }
+
+    /// Return where the segment from `start` to `end` first hits the outline of the collider,
+    /// if it does.
+    ///
+    /// See [`Collider::cast_ray`].
+    #[must_use]
+    pub fn cast_segment(&self, start: Vector2f, end: Vector2f) -> Option<RayHit> {
+        let offset = end - start;
+        self.cast_ray(start, offset, offset.length())
+    }
+}
+
+impl From<FloatRect> for Collider {
+    fn from(rect: FloatRect) -> Self {
+        let Vector2f { x, y } = rect.position;
+        let Vector2f { x: w, y: h } = rect.size;
+        Self::Polygon(vec![
+            Vector2f::new(x, y),
+            Vector2f::new(x + w, y),
+            Vector2f::new(x + w, y + h),
+            Vector2f::new(x, y + h),
+        ])
+    }
+}
+
+/// Something that has a [`Collider`], and can be used in collision queries
+///
+/// The provided methods create the colliders on each call.
+pub trait Collidable {
+    /// Returns the collider of the object, in world coordinates.
+    fn collider(&self) -> Collider;
+
+    /// Test if two objects intersect, and return how they overlap.
+    ///
+    /// See [`Collider::collide`].
+    fn collide(&self, other: &impl Collidable) -> Option<Contact> {
+        self.collider().collide(&other.collider())
+    }
+
+    /// Test if two objects intersect.
+    fn intersects(&self, other: &impl Collidable) -> bool {
+        self.collide(other).is_some()
+    }
+
+    /// Check if a point, in world coordinates, is inside the object.
+    fn contains_point(&self, point: Vector2f) -> bool {
+        self.collider().contains(point)
+    }
+
+    /// Cast a ray against the object.
+    ///
+    /// See [`Collider::cast_ray`].
+    fn cast_ray(&self, origin: Vector2f, direction: Vector2f, max_distance: f32) -> Option<RayHit> {
+        self.collider().cast_ray(origin, direction, max_distance)
+    }
+
+    /// Cast a segment against the object.
+    ///
+    /// See [`Collider::cast_segment`].
+    fn cast_segment(&self, start: Vector2f, end: Vector2f) -> Option<RayHit> {
+        self.collider().cast_segment(start, end)
+    }
+}
+
+impl Collidable for Collider {
+    fn collider(&self) -> Collider {
+        self.clone()
+    }
+}
+
+impl Collidable for FloatRect {
+    fn collider(&self) -> Collider {
+        (*self).into()
+    }
+}
+
+impl Collidable for ConvexShape<'_> {
+    fn collider(&self) -> Collider {
+        Collider::from_shape(self)
+    }
+}
+
+impl Collidable for RectangleShape<'_> {
+    fn collider(&self) -> Collider {
+        Collider::from_shape(self)
+    }
+}
+
+impl Collidable for CircleShape<'_> {
+    /// Returns an exact circle if the circle is scaled uniformly, or the polygon of its points
+    /// otherwise.
+    fn collider(&self) -> Collider {
+        let scale = self.get_scale();
+        let (scale_x, scale_y) = (scale.x.abs(), scale.y.abs());
+        if (scale_x - scale_y).abs() > f32::EPSILON * scale_x.max(scale_y) {
+            return Collider::from_shape(self);
+        }
+        let radius = self.radius();
+        Collider::Circle {
+            center: self
+                .transform()
+                .transform_point(Vector2f::new(radius, radius)),
+            radius: radius * scale_x,
+        }
+    }
+}
+
+/// Check if a point is inside a polygon, convex or not.
+///
+/// The polygon is closed automatically, from its last point to its first one. Self-intersecting
+/// polygons use the even-odd rule.
+///
+/// # Usage example
+///
+/// ```
+/// # use sfml::{graphics::collision::point_in_polygon, system::Vector2f};
+/// let l_shape = [(0., 0.), (2., 0.), (2., 1.), (1., 1.), (1., 2.), (0., 2.)].map(Vector2f::from);
+/// assert!(point_in_polygon(Vector2f::new(0.5, 1.5), &l_shape));
+/// assert!(!point_in_polygon(Vector2f::new(1.5, 1.5), &l_shape));
+/// ```
+#[must_use]
+pub fn point_in_polygon(point: Vector2f, polygon: &[Vector2f]) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(polygon) {
+        if (a.y > point.y) != (b.y > point.y) {
+            let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn edges(points: &[Vector2f]) -> impl Iterator<Item = (Vector2f, Vector2f)> + '_ {
+    points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+fn edge_normals(points: &[Vector2f]) -> impl Iterator<Item = Vector2f> + '_ {
+    edges(points)
+        .map(|(a, b)| b - a)
+        .filter(|edge| edge.length_sq() > 0.)
+        .map(|edge| edge.perpendicular().normalized())
+}
+
+fn project(points: &[Vector2f], axis: Vector2f) -> (f32, f32) {
+    points
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), point| {
+            let projection = point.dot(axis);
+            (min.min(projection), max.max(projection))
+        })
+}
+
+fn centroid(points: &[Vector2f]) -> Vector2f {
+    #[expect(clippy::cast_precision_loss, reason = "shapes have few points")]
+    let count = points.len().max(1) as f32;
+    points
+        .iter()
+        .fold(Vector2f::default(), |sum, point| sum + *point)
+        / count
+}
+
+/// Find the axis along which two projected shapes overlap the least, or `None` if they are
+/// separated along one of the axes.
+///
+/// The normal of the result isn't oriented.
+fn separating_axis(
+    axes: impl Iterator<Item = Vector2f>,
+    project_a: impl Fn(Vector2f) -> (f32, f32),
+    project_b: impl Fn(Vector2f) -> (f32, f32),
+) -> Option<Contact> {
+    let mut best: Option<Contact> = None;
+    for axis in axes {
+        let (min_a, max_a) = project_a(axis);
+        let (min_b, max_b) = project_b(axis);
+        let depth = (max_a - min_b).min(max_b - min_a);
+        if depth <= 0. {
+            return None;
+        }
+        if best.is_none_or(|best| depth < best.depth) {
+            best = Some(Contact {
+                normal: axis,
+                depth,
+            });
+        }
+    }
+    best
+}
+
+/// Make the normal of `contact` point from `from` towards `to`.
+fn orient(contact: Contact, from: Vector2f, to: Vector2f) -> Contact {
+    if contact.normal.dot(to - from) < 0. {
+        Contact {
+            normal: -contact.normal,
+            ..contact
+        }
+    } else {
+        contact
+    }
+}
+
+fn polygon_circle(polygon: &[Vector2f], center: Vector2f, radius: f32) -> Option<Contact> {
+    let closest = polygon.iter().copied().min_by(|a, b| {
+        (*a - center)
+            .length_sq()
+            .total_cmp(&(*b - center).length_sq())
+    })?;
+    let to_center = center - closest;
+    let vertex_axis = (to_center.length_sq() > 0.).then(|| to_center.normalized());
+    let axes = edge_normals(polygon).chain(vertex_axis);
+    let project_circle = |axis: Vector2f| {
+        let projection = center.dot(axis);
+        (projection - radius, projection + radius)
+    };
+    separating_axis(axes, |axis| project(polygon, axis), project_circle)
+        .map(|contact| orient(contact, centroid(polygon), center))
+}
+
+fn ray_polygon(
+    points: &[Vector2f],
+    origin: Vector2f,
+    direction: Vector2f,
+) -> Option<(f32, Vector2f)> {
+    let mut nearest: Option<(f32, Vector2f)> = None;
+    for (a, b) in edges(points) {
+        let edge = b - a;
+        let denominator = direction.cross(edge);
+        if denominator == 0. {
+            continue;
+        }
+        let to_edge = a - origin;
+        let distance = to_edge.cross(edge) / denominator;
+        let along_edge = to_edge.cross(direction) / denominator;
+        if distance < 0. || !(0. ..=1.).contains(&along_edge) {
+            continue;
+        }
+        if nearest.is_none_or(|(nearest, _)| distance < nearest) {
+            let normal = edge.perpendicular().normalized();
+            let normal = if normal.dot(direction) > 0. {
+                -normal
+            } else {
+                normal
+            };
+            nearest = Some((distance, normal));
+        }
+    }
+    nearest
+}
+
+/// Which pixels of an image are solid, for pixel-perfect collisions between sprites
+///
+/// A pixel is solid if its alpha is greater than the threshold given at creation. The pixels
+/// are packed in a bitset, one bit per pixel, each row starting on a new 64-bit word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollisionMask {
+    size: Vector2u,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+/// Creation
+impl CollisionMask {
+    /// Create a mask from the alpha channel of an image.
+    ///
+    /// The pixels with an alpha greater than `alpha_threshold` are solid.
+    #[must_use]
+    pub fn from_image(image: &Image, alpha_threshold: u8) -> Self {
+        let alphas = image.pixel_data().chunks_exact(4).map(|pixel| pixel[3]);
+        Self::from_alphas(image.size(), alphas, alpha_threshold)
+    }
+
+    /// Create a mask from the alpha channel of a texture.
+    ///
+    /// The texture is copied to an image with [`Texture::copy_to_image`], which is slow: create
+    /// the masks of the textures once, when loading them.
+    pub fn from_texture(texture: &Texture, alpha_threshold: u8) -> SfResult<Self> {
+        let image = texture.copy_to_image()?;
+        Ok(Self::from_image(&image, alpha_threshold))
+    }
+
+    fn from_alphas(size: Vector2u, alphas: impl Iterator<Item = u8>, threshold: u8) -> Self {
+        let width = size.x as usize;
+        let words_per_row = width.div_ceil(64);
+        let mut bits = vec![0; words_per_row * size.y as usize];
+        for (index, alpha) in alphas.enumerate() {
+            if alpha > threshold {
+                let (y, x) = (index / width, index % width);
+                bits[y * words_per_row + x / 64] |= 1 << (x % 64);
+            }
+        }
+        Self {
+            size,
+            words_per_row,
+            bits,
+        }
+    }
+}
+
+/// Queries
+impl CollisionMask {
+    /// Returns the size of the mask, which is the size of the image it was created from.
+    #[must_use]
+    pub fn size(&self) -> Vector2u {
+        self.size
+    }
+
+    /// Check if the pixel at `(x, y)` is solid.
+    ///
+    /// Pixels outside of the mask aren't solid.
+    #[must_use]
+    pub fn is_solid(&self, x: u32, y: u32) -> bool {
+        if x >= self.size.x || y >= self.size.y {
+            return false;
+        }
+        let (x, y) = (x as usize, y as usize);
+        self.bits[y * self.words_per_row + x / 64] & (1 << (x % 64)) != 0
+    }
+
+    /// Returns `count` bits of row `y`, from column `x`, in the lowest bits.
+    ///
+    /// The columns must be inside the mask, and `count` at most 64.
+    fn bits(&self, x: usize, y: usize, count: usize) -> u64 {
+        let row = &self.bits[y * self.words_per_row..][..self.words_per_row];
+        let (word, shift) = (x / 64, x % 64);
+        let mut bits = row[word] >> shift;
+        if shift > 0 && word + 1 < row.len() {
+            bits |= row[word + 1] << (64 - shift);
+        }
+        if count < 64 {
+            bits &= (1 << count) - 1;
+        }
+        bits
+    }
+}
+
+/// Test if two sprites overlap at pixel level, using the collision masks of their textures.
+///
+/// The sprites are first tested with their global bounds. Then, each pixel of the world inside
+/// both bounds is transformed into both sprites with their inverse transforms, and looked up
+/// in the masks, taking the texture rectangles of the sprites into account. If both sprites
+/// are neither rotated nor scaled, and are at integer coordinates, whole rows of the masks
+/// are compared at once instead.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::graphics::{
+///     Sprite, Texture, Transformable,
+///     collision::{CollisionMask, sprites_overlap},
+/// };
+///
+/// let texture = Texture::from_file("ship.png").unwrap();
+/// let mask = CollisionMask::from_texture(&texture, 0).unwrap();
+/// let player = Sprite::with_texture(&texture);
+/// let mut enemy = Sprite::with_texture(&texture);
+/// enemy.set_position((20., 10.));
+/// if sprites_overlap(&player, &mask, &enemy, &mask) {
+///     println!("Crash!");
+/// }
+/// ```
+#[must_use]
+pub fn sprites_overlap(
+    a: &Sprite,
+    mask_a: &CollisionMask,
+    b: &Sprite,
+    mask_b: &CollisionMask,
+) -> bool {
+    Placement::of_sprite(a, mask_a).overlaps(&Placement::of_sprite(b, mask_b))
+}
+
+/// A collision mask placed in the world
+struct Placement<'mask> {
+    mask: &'mask CollisionMask,
+    texture_rect: IntRect,
+    inverse_transform: Transform,
+    bounds: FloatRect,
+    /// Offset from the mask pixels to the world pixels, if the mask is neither rotated, scaled
+    /// nor flipped, and is at integer coordinates
+    offset: Option<Vector2i>,
+}
+
+impl<'mask> Placement<'mask> {
+    fn of_sprite(sprite: &Sprite, mask: &'mask CollisionMask) -> Self {
+        let texture_rect = sprite.texture_rect();
+        let translation = sprite.position() - sprite.origin();
+        let aligned = sprite.rotation().wrap_unsigned().as_degrees() == 0.
+            && sprite.get_scale() == Vector2f::new(1., 1.)
+            && texture_rect.size.x > 0
+            && texture_rect.size.y > 0
+            && translation.x.fract() == 0.
+            && translation.y.fract() == 0.;
+        let offset = aligned.then(|| translation.as_other::<i32>() - texture_rect.position);
+        Self {
+            mask,
+            texture_rect,
+            inverse_transform: *sprite.inverse_transform(),
+            bounds: sprite.global_bounds(),
+            offset,
+        }
+    }
+
+    fn overlaps(&self, other: &Placement) -> bool {
+        let Some(area) = self.bounds.intersection(&other.bounds) else {
+            return false;
+        };
+        if let (Some(offset), Some(other_offset)) = (self.offset, other.offset) {
+            return self.overlaps_aligned(offset, other, other_offset);
+        }
+        #[expect(clippy::cast_possible_truncation, reason = "rounded to pixels")]
+        let (left, top, right, bottom) = (
+            area.position.x.floor() as i32,
+            area.position.y.floor() as i32,
+            (area.position.x + area.size.x).ceil() as i32,
+            (area.position.y + area.size.y).ceil() as i32,
+        );
+        (top..bottom).any(|y| {
+            (left..right).any(|x| {
+                #[expect(clippy::cast_precision_loss, reason = "pixel coordinates are small")]
+                let center = Vector2f::new(x as f32 + 0.5, y as f32 + 0.5);
+                self.is_solid_at(center) && other.is_solid_at(center)
+            })
+        })
+    }
+
+    /// Check if the mask is solid at a point, in world coordinates.
+    fn is_solid_at(&self, point: Vector2f) -> bool {
+        let local = self.inverse_transform.transform_point(point);
+        let IntRect { position, size } = self.texture_rect;
+        #[expect(clippy::cast_precision_loss, reason = "texture sizes are small")]
+        let (width, height) = (size.x.abs() as f32, size.y.abs() as f32);
+        if local.x < 0. || local.y < 0. || local.x >= width || local.y >= height {
+            return false;
+        }
+        // Negative sizes of the texture rectangle flip the texture
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "inside the texture rectangle"
+        )]
+        let (x, y) = (local.x as i32, local.y as i32);
+        let x = if size.x > 0 {
+            position.x + x
+        } else {
+            position.x - 1 - x
+        };
+        let y = if size.y > 0 {
+            position.y + y
+        } else {
+            position.y - 1 - y
+        };
+        match (u32::try_from(x), u32::try_from(y)) {
+            (Ok(x), Ok(y)) => self.mask.is_solid(x, y),
+            _ => false,
+        }
+    }
+
+    /// The pixels of the texture rectangle that are inside the mask, in world coordinates
+    fn aligned_area(&self, offset: Vector2i) -> Option<IntRect> {
+        let mask_size = self.mask.size.try_into_other().ok()?;
+        let mask = IntRect::new(Vector2i::default(), mask_size);
+        let visible = mask.intersection(&self.texture_rect)?;
+        Some(IntRect::new(visible.position + offset, visible.size))
+    }
+
+    fn overlaps_aligned(
+        &self,
+        offset: Vector2i,
+        other: &Placement,
+        other_offset: Vector2i,
+    ) -> bool {
+        let Some(area) = self
+            .aligned_area(offset)
+            .zip(other.aligned_area(other_offset))
+            .and_then(|(area, other_area)| area.intersection(&other_area))
+        else {
+            return false;
+        };
+        // The area is inside both masks, so the coordinates in the masks are positive
+        let to_mask = |world: i32, offset: i32| usize::try_from(world - offset).unwrap_or(0);
+        let (left, width) = (area.position.x, area.size.x);
+        (area.position.y..area.position.y + area.size.y).any(|y| {
+            let (row, other_row) = (to_mask(y, offset.y), to_mask(y, other_offset.y));
+            (left..left + width).step_by(64).any(|x| {
+                let count = usize::try_from(left + width - x).unwrap_or(0).min(64);
+                let bits = self.mask.bits(to_mask(x, offset.x), row, count);
+                let other_bits = other
+                    .mask
+                    .bits(to_mask(x, other_offset.x), other_row, count);
+                bits & other_bits != 0
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32, size: f32) -> Collider {
+        FloatRect::new(Vector2f::new(x, y), Vector2f::new(size, size)).into()
+    }
+
+    fn close(a: Vector2f, b: Vector2f) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    #[test]
+    fn polygons_minimum_translation() {
+        let a = square(0., 0., 10.);
+        let b = square(8., 1., 10.);
+        let contact = a.collide(&b);
+        assert!(
+            contact.is_some_and(|contact| { close(contact.translation(), Vector2f::new(2., 0.)) })
+        );
+        let reversed = b.collide(&a);
+        assert!(
+            reversed
+                .is_some_and(|contact| { close(contact.translation(), Vector2f::new(-2., 0.)) })
+        );
+        assert!(!a.intersects(&square(10., 0., 10.)));
+        // A diamond next to the corner of the square, separated along its own edge normal
+        let diamond = Collider::Polygon(vec![
+            Vector2f::new(12., 9.),
+            Vector2f::new(15., 12.),
+            Vector2f::new(12., 15.),
+            Vector2f::new(9., 12.),
+        ]);
+        assert!(!a.intersects(&diamond));
+    }
+
+    #[test]
+    fn circles_and_polygons() {
+        let circle = Collider::Circle {
+            center: Vector2f::new(13., 5.),
+            radius: 5.,
+        };
+        let contact = square(0., 0., 10.).collide(&circle);
+        assert!(
+            contact.is_some_and(|contact| { close(contact.translation(), Vector2f::new(2., 0.)) })
+        );
+        let contact = circle.collide(&square(0., 0., 10.));
+        assert!(
+            contact.is_some_and(|contact| { close(contact.translation(), Vector2f::new(-2., 0.)) })
+        );
+        // Close to the corner, but outside of it
+        let corner = Collider::Circle {
+            center: Vector2f::new(13., 13.),
+            radius: 4.,
+        };
+        assert!(!square(0., 0., 10.).intersects(&corner));
+        let other = Collider::Circle {
+            center: Vector2f::new(13., 9.),
+            radius: 1.,
+        };
+        let contact = circle.collide(&other);
+        assert!(contact.is_some_and(|contact| {
+            close(contact.normal, Vector2f::new(0., 1.)) && (contact.depth - 2.).abs() < 1e-4
+        }));
+    }
+
+    #[test]
+    fn ray_casts() {
+        let square = square(0., 0., 10.);
+        let hit = square.cast_ray(Vector2f::new(-5., 5.), Vector2f::new(2., 0.), 100.);
+        assert!(hit.is_some_and(|hit| {
+            (hit.distance - 5.).abs() < 1e-4
+                && close(hit.point, Vector2f::new(0., 5.))
+                && close(hit.normal, Vector2f::new(-1., 0.))
+        }));
+        assert!(
+            square
+                .cast_ray(Vector2f::new(-5., 5.), Vector2f::new(1., 0.), 4.)
+                .is_none()
+        );
+        assert!(
+            square
+                .cast_segment(Vector2f::new(-5., 5.), Vector2f::new(-5., 20.))
+                .is_none()
+        );
+        let circle = Collider::Circle {
+            center: Vector2f::new(0., 0.),
+            radius: 2.,
+        };
+        let hit = circle.cast_segment(Vector2f::new(0., -10.), Vector2f::new(0., 10.));
+        assert!(hit.is_some_and(|hit| {
+            (hit.distance - 8.).abs() < 1e-4 && close(hit.normal, Vector2f::new(0., -1.))
+        }));
+        let inside = circle.cast_ray(Vector2f::new(0.5, 0.), Vector2f::new(1., 0.), 1.);
+        assert!(inside.is_some_and(|hit| hit.distance == 0.));
+    }
+
+    /// A mask of `size` with a solid square of `solid` pixels in its bottom right corner
+    fn corner_mask(size: u32, solid: u32) -> CollisionMask {
+        let alphas = (0..size * size).map(|index| {
+            let (x, y) = (index % size, index / size);
+            if x >= size - solid && y >= size - solid {
+                255
+            } else {
+                0
+            }
+        });
+        CollisionMask::from_alphas(Vector2u::new(size, size), alphas, 127)
+    }
+
+    /// Place a mask at `(x, y)`, optionally allowing the aligned fast path
+    fn place(mask: &CollisionMask, x: i32, y: i32, aligned: bool) -> Placement<'_> {
+        let size = mask.size().as_other::<i32>();
+        let position = Vector2i::new(x, y).as_other::<f32>();
+        Placement {
+            mask,
+            texture_rect: IntRect::new(Vector2i::default(), size),
+            inverse_transform: Transform::new(1., 0., -position.x, 0., 1., -position.y, 0., 0., 1.),
+            bounds: FloatRect::new(position, size.as_other()),
+            offset: aligned.then_some(Vector2i::new(x, y)),
+        }
+    }
+
+    #[test]
+    fn mask_bits() {
+        let mask = corner_mask(100, 40);
+        assert!(mask.is_solid(60, 60) && mask.is_solid(99, 99));
+        assert!(!mask.is_solid(59, 60) && !mask.is_solid(100, 99));
+        assert_eq!(mask.bits(58, 70, 4), 0b1100);
+        assert_eq!(mask.bits(36, 70, 64), u64::MAX << 24);
+    }
+
+    #[test]
+    fn aligned_and_transformed_overlaps_agree() {
+        let a = corner_mask(100, 40);
+        let b = corner_mask(30, 5);
+        // b's solid pixels are at 25..30 in b
+        for (x, y, expected) in [
+            (36, 36, true),
+            (40, 40, true),
+            (30, 36, false),
+            (84, 84, false),
+            (60, 20, false),
+        ] {
+            for aligned in [false, true] {
+                let overlap = place(&a, 0, 0, aligned).overlaps(&place(&b, x, y, aligned));
+                assert_eq!(overlap, expected, "b at ({x}, {y}), aligned: {aligned}");
+            }
+        }
+    }
+}
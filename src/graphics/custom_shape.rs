@@ -32,6 +32,15 @@ pub trait CustomShapePoints {
     fn point(&self, point: usize) -> Vector2f;
 }
 
+impl CustomShapePoints for Vec<Vector2f> {
+    fn point_count(&self) -> usize {
+        self.len()
+    }
+    fn point(&self, point: usize) -> Vector2f {
+        self[point]
+    }
+}
+
 /// A custom textured shape with outline.
 #[derive(Debug)]
 pub struct CustomShape<'s> {
@@ -103,6 +112,16 @@ impl<'s> CustomShape<'s> {
     pub fn update(&mut self) {
         unsafe { ffi::sfCustomShape_update(self.handle.as_ptr()) }
     }
+
+    /// Replace the points of the shape, and recompute its geometry.
+    ///
+    /// # Arguments
+    /// * points - Implementation of [`CustomShapePoints`]
+    pub fn set_points(&mut self, points: Box<dyn CustomShapePoints + Send>) {
+        // SAFETY: SFML only reads the points through the callbacks, which are not running
+        unsafe { *self.points.as_ptr() = points };
+        self.update();
+    }
     pub(super) fn raw(&self) -> *const ffi::sfCustomShape {
         self.handle.as_ptr()
     }
@@ -219,3 +238,74 @@ impl Drop for CustomShape<'_> {
         }
     }
 }
+
+/// Implements [`Shape`], [`Drawable`] and [`Transformable`] for a shape type by forwarding
+/// to one of its [`CustomShape`] fields.
+macro_rules! impl_shape_via {
+    ($ty:ident, $field:ident) => {
+        impl<'s> $crate::graphics::Shape<'s> for $ty<'s> {
+            fn set_texture(&mut self, texture: &'s $crate::graphics::Texture, reset_rect: bool) {
+                self.$field.set_texture(texture, reset_rect);
+            }
+            fn disable_texture(&mut self) {
+                self.$field.disable_texture();
+            }
+            fn set_texture_rect(&mut self, rect: $crate::graphics::IntRect) {
+                self.$field.set_texture_rect(rect);
+            }
+            fn set_fill_color(&mut self, color: $crate::graphics::Color) {
+                self.$field.set_fill_color(color);
+            }
+            fn set_outline_color(&mut self, color: $crate::graphics::Color) {
+                self.$field.set_outline_color(color);
+            }
+            fn set_outline_thickness(&mut self, thickness: f32) {
+                self.$field.set_outline_thickness(thickness);
+            }
+            fn texture(&self) -> Option<&'s $crate::graphics::Texture> {
+                self.$field.texture()
+            }
+            fn texture_rect(&self) -> $crate::graphics::IntRect {
+                self.$field.texture_rect()
+            }
+            fn fill_color(&self) -> $crate::graphics::Color {
+                self.$field.fill_color()
+            }
+            fn outline_color(&self) -> $crate::graphics::Color {
+                self.$field.outline_color()
+            }
+            fn outline_thickness(&self) -> f32 {
+                self.$field.outline_thickness()
+            }
+            fn point_count(&self) -> usize {
+                self.$field.point_count()
+            }
+            fn geometric_center(&self) -> $crate::system::Vector2f {
+                self.$field.geometric_center()
+            }
+            fn point(&self, index: usize) -> $crate::system::Vector2f {
+                self.$field.point(index)
+            }
+            fn local_bounds(&self) -> $crate::graphics::FloatRect {
+                self.$field.local_bounds()
+            }
+            fn global_bounds(&self) -> $crate::graphics::FloatRect {
+                self.$field.global_bounds()
+            }
+        }
+
+        impl $crate::graphics::Drawable for $ty<'_> {
+            fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+                &'a self,
+                target: &mut dyn $crate::graphics::RenderTarget,
+                states: &$crate::graphics::RenderStates<'texture, 'shader, 'shader_texture>,
+            ) {
+                target.draw_shape(&self.$field, states);
+            }
+        }
+
+        $crate::graphics::transformable::impl_transformable_via!($ty<'_>, $field);
+    };
+}
+
+pub(crate) use impl_shape_via;
@@ -0,0 +1,114 @@
+use {
+    crate::{
+        graphics::{CustomShape, Shape, Texture, custom_shape::impl_shape_via},
+        system::Vector2f,
+    },
+    std::f32::consts::{FRAC_PI_2, TAU},
+};
+
+/// Specialized shape representing an ellipse.
+///
+/// Like [`CircleShape`], the local origin of the ellipse is the top-left corner of its
+/// bounding box, not its center.
+///
+/// [`CircleShape`]: crate::graphics::CircleShape
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut shadow = EllipseShape::new((40., 12.), 30);
+/// shadow.set_fill_color(Color::rgba(0, 0, 0, 96));
+/// window.draw(&shadow);
+/// ```
+#[derive(Debug)]
+pub struct EllipseShape<'s> {
+    shape: CustomShape<'s>,
+    radius: Vector2f,
+    point_count: usize,
+}
+
+/// Creation
+impl<'s> EllipseShape<'s> {
+    /// Create an ellipse with the given horizontal and vertical radii, approximated with
+    /// `point_count` points.
+    #[must_use]
+    pub fn new<R: Into<Vector2f>>(radius: R, point_count: usize) -> Self {
+        let mut shape = Self {
+            shape: CustomShape::new(Box::new(Vec::new())),
+            radius: radius.into(),
+            point_count,
+        };
+        shape.update();
+        shape
+    }
+
+    /// Create an ellipse with a texture.
+    #[must_use]
+    pub fn with_texture<R: Into<Vector2f>>(
+        radius: R,
+        point_count: usize,
+        texture: &'s Texture,
+    ) -> Self {
+        let mut shape = Self::new(radius, point_count);
+        shape.set_texture(texture, true);
+        shape
+    }
+}
+
+/// Properties
+impl EllipseShape<'_> {
+    /// Set the horizontal and vertical radii of the ellipse.
+    pub fn set_radius<R: Into<Vector2f>>(&mut self, radius: R) {
+        self.radius = radius.into();
+        self.update();
+    }
+
+    /// Get the horizontal and vertical radii of the ellipse.
+    #[must_use]
+    pub fn radius(&self) -> Vector2f {
+        self.radius
+    }
+
+    /// Set the number of points of the ellipse.
+    pub fn set_point_count(&mut self, count: usize) {
+        self.point_count = count;
+        self.update();
+    }
+
+    fn update(&mut self) {
+        self.shape
+            .set_points(Box::new(points(self.radius, self.point_count)));
+    }
+}
+
+impl_shape_via!(EllipseShape, shape);
+
+fn points(radius: Vector2f, count: usize) -> Vec<Vector2f> {
+    // Like sf::CircleShape, the first point is at the top
+    (0..count)
+        .map(|i| {
+            let (sin, cos) = (i as f32 * TAU / count as f32 - FRAC_PI_2).sin_cos();
+            radius + Vector2f::new(radius.x * cos, radius.y * sin)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_on_the_ellipse() {
+        let radius = Vector2f::new(20., 10.);
+        let points = points(radius, 32);
+        assert_eq!(points.len(), 32);
+        assert!((points[0] - Vector2f::new(20., 0.)).length() < 1e-4);
+        assert!((points[8] - Vector2f::new(40., 10.)).length() < 1e-4);
+        for point in points {
+            let relative = (point - radius).cwise_div(radius);
+            assert!((relative.length() - 1.).abs() < 1e-4);
+        }
+    }
+}
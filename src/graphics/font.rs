@@ -4,7 +4,7 @@ use {
         cpp::FBox,
         ffi::graphics as ffi,
         graphics::{Glyph, Texture},
-        system::InputStream,
+        system::{InputStream, err::diagnose},
     },
     std::{
         ffi::{CStr, CString},
@@ -132,7 +132,7 @@ impl Font {
     /// ```
     pub fn open_from_file(&mut self, path: &str) -> SfResult<()> {
         let c_str = CString::new(path)?;
-        unsafe { ffi::sfFont_openFromFile(self, c_str.as_ptr()) }.into_sf_result()
+        diagnose(|| unsafe { ffi::sfFont_openFromFile(self, c_str.as_ptr()) }.into_sf_result())
     }
 
     /// Load the font from a custom stream.
@@ -148,7 +148,10 @@ impl Font {
     /// [`Font::from_file`], [`Font::from_memory`]
     pub unsafe fn open_from_stream<T: Read + Seek>(&mut self, stream: &mut T) -> SfResult<()> {
         let mut input_stream = InputStream::new(stream);
-        unsafe { ffi::sfFont_openFromStream(self, &raw mut *input_stream.stream) }.into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::sfFont_openFromStream(self, &raw mut *input_stream.stream) }
+                .into_sf_result()
+        })
     }
 
     /// Load the font from a file in memory.
@@ -166,7 +169,9 @@ impl Font {
     ///
     /// [`Font::from_file`], [`Font::from_stream`]
     pub unsafe fn open_from_memory(&mut self, data: &[u8]) -> SfResult<()> {
-        unsafe { ffi::sfFont_openFromMemory(self, data.as_ptr(), data.len()) }.into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::sfFont_openFromMemory(self, data.as_ptr(), data.len()) }.into_sf_result()
+        })
     }
     /// Load the font from a file in static memory.
     ///
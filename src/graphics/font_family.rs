@@ -0,0 +1,98 @@
+use crate::{cpp::FBox, graphics::Font};
+
+/// A font with a chain of fallback fonts for the characters it doesn't have.
+///
+/// Each character is rendered with the first font of the chain that has a glyph for it.
+/// If none has, the primary font is used, which usually renders a replacement glyph.
+///
+/// The layout metrics (line spacing, whitespace width, underline) are those of the
+/// primary font.
+///
+/// [`TextLayout`] and [`RichText`] accept a `FontFamily` anywhere they accept a [`Font`],
+/// and render characters from different fonts of the family with one draw call per font.
+/// A [`Text`] uses one with [`Text::set_font_family`].
+///
+/// [`TextLayout`]: crate::graphics::TextLayout
+/// [`RichText`]: crate::graphics::RichText
+/// [`Text`]: crate::graphics::Text
+/// [`Text::set_font_family`]: crate::graphics::Text::set_font_family
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// let latin = Font::from_file("latin.ttf").unwrap();
+/// let cjk = Font::from_file("cjk.ttf").unwrap();
+/// let emoji = Font::from_file("emoji.ttf").unwrap();
+/// let family = FontFamily::new(&latin).with_fallback(&cjk).with_fallback(&emoji);
+/// let layout = TextLayout::new("Hello, 世界 👋", family, 24);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FontFamily<'s> {
+    fonts: Vec<&'s Font>,
+}
+
+impl<'s> FontFamily<'s> {
+    /// Create a family with only a primary font.
+    #[must_use]
+    pub fn new(primary: &'s Font) -> Self {
+        Self {
+            fonts: vec![primary],
+        }
+    }
+
+    /// Add a fallback font at the end of the chain, and return the family.
+    #[must_use]
+    pub fn with_fallback(mut self, font: &'s Font) -> Self {
+        self.push_fallback(font);
+        self
+    }
+
+    /// Add a fallback font at the end of the chain.
+    pub fn push_fallback(&mut self, font: &'s Font) {
+        self.fonts.push(font);
+    }
+
+    /// Get the primary font of the family.
+    #[must_use]
+    pub fn primary(&self) -> &'s Font {
+        self.fonts[0]
+    }
+
+    /// Get the fonts of the family, starting with the primary font.
+    #[must_use]
+    pub fn fonts(&self) -> &[&'s Font] {
+        &self.fonts
+    }
+
+    /// Get the font used to render `c`.
+    ///
+    /// This is the first font of the chain that has a glyph for `c`, or the primary font
+    /// if no font has one.
+    #[must_use]
+    pub fn resolve(&self, c: char) -> &'s Font {
+        self.fonts
+            .iter()
+            .copied()
+            .find(|font| font.has_glyph(u32::from(c)))
+            .unwrap_or(self.fonts[0])
+    }
+
+    /// Tell whether any font of the family has a glyph for `c`.
+    #[must_use]
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.fonts.iter().any(|font| font.has_glyph(u32::from(c)))
+    }
+}
+
+impl<'s> From<&'s Font> for FontFamily<'s> {
+    fn from(font: &'s Font) -> Self {
+        Self::new(font)
+    }
+}
+
+impl<'s> From<&'s FBox<Font>> for FontFamily<'s> {
+    fn from(font: &'s FBox<Font>) -> Self {
+        Self::new(font)
+    }
+}
@@ -1,17 +1,17 @@
 use {
     crate::{
-        IntoSfResult, SfResult,
+        IntoSfResult, SfError, SfResult,
         cpp::FBox,
         ffi::graphics::{self as ffi, sfImage_saveToMemory},
         graphics::{Color, IntRect},
-        system::{InputStream, Vector2u, buffer::Buffer},
+        system::{InputStream, Vector2u, buffer::Buffer, err::diagnose},
     },
     std::{
         error::Error,
         ffi::CString,
         fmt,
         io::{Read, Seek},
-        slice,
+        slice::{self, ChunksExact, ChunksExactMut},
     },
 };
 
@@ -34,14 +34,11 @@ impl Image {
     }
     /// Create a new `Image` from the provided RGBA pixel data.
     ///
-    /// See [`Self::recreate_from_pixels`].
-    ///
-    /// # Safety
-    ///
-    /// Also see [`Self::recreate_from_pixels`].
-    pub unsafe fn from_pixels(size: Vector2u, data: &[u8]) -> SfResult<FBox<Self>> {
+    /// `data` must contain the 4 bytes of each of the `size.x * size.y` pixels, row by row.
+    /// Fails with [`PixelDataError::Length`] if it doesn't.
+    pub fn from_pixels(size: Vector2u, data: &[u8]) -> Result<FBox<Self>, PixelDataError> {
         let mut img = Self::new()?;
-        img.resize_with_pixels(size, data);
+        img.resize_with_pixels(size, data)?;
         Ok(img)
     }
     /// Create a new `Image` from an image file on the filesystem.
@@ -76,7 +73,7 @@ impl Image {
     /// If this function fails, the image is left unchanged.
     pub fn load_from_file(&mut self, path: &str) -> SfResult<()> {
         let c_path = CString::new(path)?;
-        unsafe { ffi::sfImage_loadFromFile(self, c_path.as_ptr()) }.into_sf_result()
+        diagnose(|| unsafe { ffi::sfImage_loadFromFile(self, c_path.as_ptr()) }.into_sf_result())
     }
     /// Load from image file data in memory.
     ///
@@ -87,7 +84,9 @@ impl Image {
     /// # Arguments
     /// * mem - Pointer to the file data in memory
     pub fn load_from_memory(&mut self, data: &[u8]) -> SfResult<()> {
-        unsafe { ffi::sfImage_loadFromMemory(self, data.as_ptr(), data.len()) }.into_sf_result()
+        diagnose(|| {
+            unsafe { ffi::sfImage_loadFromMemory(self, data.as_ptr(), data.len()) }.into_sf_result()
+        })
     }
     /// Load from image file data coming from a custom stream.
     ///
@@ -96,7 +95,10 @@ impl Image {
     /// If this function fails, the image is left unchanged.
     pub fn load_from_stream<T: Read + Seek>(&mut self, stream: &mut T) -> SfResult<()> {
         let mut input_stream = InputStream::new(stream);
-        unsafe { ffi::sfImage_loadFromStream(self, &raw mut *input_stream.stream) }.into_sf_result()?;
+        diagnose(|| {
+            unsafe { ffi::sfImage_loadFromStream(self, &raw mut *input_stream.stream) }
+                .into_sf_result()
+        })?;
         Ok(())
     }
 }
@@ -183,14 +185,97 @@ impl Image {
     }
 
     /// Return the memory buffer of this image.
+    ///
+    /// It contains the 4 RGBA bytes of each pixel, row by row.
     #[must_use]
     pub fn pixel_data(&self) -> &[u8] {
-        unsafe {
-            let size = self.size();
-            let pixels = ffi::sfImage_getPixelsPtr(self);
+        let len = self.pixel_data_len();
+        if len == 0 {
+            // The pointer is null for an empty image
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(ffi::sfImage_getPixelsPtr(self), len) }
+    }
 
-            slice::from_raw_parts(pixels, (size.x * size.y * 4) as usize)
+    /// Return the memory buffer of this image, to change the pixels directly.
+    ///
+    /// It contains the 4 RGBA bytes of each pixel, row by row.
+    ///
+    /// # Usage example
+    ///
+    /// ```no_run
+    /// # use sfml::graphics::Image;
+    /// # let mut image = Image::new().unwrap();
+    /// // Invert the colors
+    /// for pixel in image.pixel_data_mut().chunks_exact_mut(4) {
+    ///     for component in &mut pixel[..3] {
+    ///         *component = 255 - *component;
+    ///     }
+    /// }
+    /// ```
+    pub fn pixel_data_mut(&mut self) -> &mut [u8] {
+        let len = self.pixel_data_len();
+        if len == 0 {
+            return &mut [];
         }
+        unsafe { slice::from_raw_parts_mut(ffi::sfImage_getPixelsMutPtr(self), len) }
+    }
+
+    /// Return the pixels of this image, row by row.
+    #[must_use]
+    pub fn pixels(&self) -> &[Color] {
+        let data = self.pixel_data();
+        // `Color` is 4 bytes, without padding nor alignment requirement
+        unsafe { slice::from_raw_parts(data.as_ptr().cast::<Color>(), data.len() / 4) }
+    }
+
+    /// Return the pixels of this image, row by row, to change them directly.
+    pub fn pixels_mut(&mut self) -> &mut [Color] {
+        let data = self.pixel_data_mut();
+        unsafe { slice::from_raw_parts_mut(data.as_mut_ptr().cast::<Color>(), data.len() / 4) }
+    }
+
+    /// Return the pixels of row `y`, or `None` if it's outside of the image.
+    #[must_use]
+    pub fn row(&self, y: u32) -> Option<&[Color]> {
+        self.rows().nth(y as usize)
+    }
+
+    /// Return the pixels of row `y` to change them, or `None` if it's outside of the image.
+    pub fn row_mut(&mut self, y: u32) -> Option<&mut [Color]> {
+        self.rows_mut().nth(y as usize)
+    }
+
+    /// Iterate over the rows of pixels of this image, from top to bottom.
+    pub fn rows(&self) -> ChunksExact<'_, Color> {
+        let width = self.size().x as usize;
+        self.pixels().chunks_exact(width.max(1))
+    }
+
+    /// Iterate over the rows of pixels of this image, from top to bottom, to change them.
+    ///
+    /// The rows are disjoint, so they can be processed in parallel, for example with
+    /// [`std::thread::scope`], or `rayon`'s `par_chunks_exact_mut` on [`Image::pixels_mut`].
+    ///
+    /// # Usage example
+    ///
+    /// ```no_run
+    /// # use sfml::graphics::{Color, Image};
+    /// # let mut image = Image::new().unwrap();
+    /// // A vertical gradient
+    /// for (y, row) in image.rows_mut().enumerate() {
+    ///     let shade = (y * 255 / 100).min(255) as u8;
+    ///     row.fill(Color::rgb(shade, shade, shade));
+    /// }
+    /// ```
+    pub fn rows_mut(&mut self) -> ChunksExactMut<'_, Color> {
+        let width = self.size().x as usize;
+        self.pixels_mut().chunks_exact_mut(width.max(1))
+    }
+
+    fn pixel_data_len(&self) -> usize {
+        let size = self.size();
+        size.x as usize * size.y as usize * 4
     }
 }
 
@@ -260,10 +345,30 @@ impl Image {
     }
     /// Resize the image from an array of pixels
     ///
-    /// The pixel array is assumed to contain 32-bits RGBA pixels,
-    /// and have the given `size`. If not, this is an undefined behavior.
-    /// If `pixels` is `nullptr`, an empty image is created.
-    pub fn resize_with_pixels(&mut self, size: Vector2u, data: &[u8]) {
+    /// `data` must contain the 4 bytes of each of the `size.x * size.y` RGBA pixels, row by row.
+    /// Fails with [`PixelDataError::Length`] if it doesn't.
+    pub fn resize_with_pixels(
+        &mut self,
+        size: Vector2u,
+        data: &[u8],
+    ) -> Result<(), PixelDataError> {
+        let expected = size.x as usize * size.y as usize * 4;
+        if data.len() != expected {
+            return Err(PixelDataError::Length {
+                expected,
+                actual: data.len(),
+            });
+        }
+        unsafe { self.resize_with_pixels_unchecked(size, data) };
+        Ok(())
+    }
+    /// Resize the image from an array of pixels, without checking its length
+    ///
+    /// # Safety
+    ///
+    /// `data` must contain at least the 4 bytes of each of the `size.x * size.y`
+    /// RGBA pixels, otherwise this is an undefined behaviour.
+    pub unsafe fn resize_with_pixels_unchecked(&mut self, size: Vector2u, data: &[u8]) {
         unsafe { ffi::sfImage_resizeWithPixels(self, size, data.as_ptr()) }
     }
     /// Save an image to a file on disk
@@ -279,7 +384,7 @@ impl Image {
     /// Return true if saving was successful
     pub fn save_to_file(&self, filename: &str) -> SfResult<()> {
         let c_str = CString::new(filename)?;
-        unsafe { ffi::sfImage_saveToFile(self, c_str.as_ptr()) }.into_sf_result()
+        diagnose(|| unsafe { ffi::sfImage_saveToFile(self, c_str.as_ptr()) }.into_sf_result())
     }
 
     /// Save the image to a buffer in memory
@@ -293,7 +398,9 @@ impl Image {
     #[must_use = "The returned buffer contains the saved data. Ignoring this result will discard the exported memory."]
     pub fn save_to_memory(&self, format: &str) -> SfResult<FBox<Buffer>> {
         let c_str = CString::new(format)?;
-        FBox::new(unsafe { sfImage_saveToMemory(self, c_str.as_ptr()) }).into_sf_result()
+        diagnose(|| {
+            FBox::new(unsafe { sfImage_saveToMemory(self, c_str.as_ptr()) }).into_sf_result()
+        })
     }
 
     /// Return the size of an image
@@ -358,3 +465,43 @@ impl fmt::Display for PixelAccessError {
         }
     }
 }
+
+/// Error that can happen when creating or resizing an image from pixel data
+#[derive(Debug, Clone)]
+pub enum PixelDataError {
+    /// The length of the pixel data doesn't match the size of the image
+    Length {
+        /// Length required by the size of the image, 4 bytes per pixel
+        expected: usize,
+        /// Length of the pixel data
+        actual: usize,
+    },
+    /// The image couldn't be created
+    Sf(SfError),
+}
+
+impl From<SfError> for PixelDataError {
+    fn from(error: SfError) -> Self {
+        Self::Sf(error)
+    }
+}
+
+impl Error for PixelDataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Length { .. } => None,
+            Self::Sf(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for PixelDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length { expected, actual } => {
+                write!(f, "pixel data has {actual} bytes, expected {expected}")
+            }
+            Self::Sf(error) => write!(f, "failed to create image: {error}"),
+        }
+    }
+}
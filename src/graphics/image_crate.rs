@@ -0,0 +1,208 @@
+use {
+    crate::{
+        SfError,
+        cpp::FBox,
+        graphics::{Image, IntRect, Texture},
+        system::Vector2u,
+    },
+    ::image::{DynamicImage, ImageError, ImageFormat, RgbaImage},
+    std::{error::Error, fmt, io::Cursor, path::Path},
+};
+
+impl From<&Image> for RgbaImage {
+    fn from(image: &Image) -> Self {
+        let size = image.size();
+        Self::from_raw(size.x, size.y, image.pixel_data().to_vec())
+            .expect("the pixel data of an image matches its size")
+    }
+}
+
+impl From<&Image> for DynamicImage {
+    fn from(image: &Image) -> Self {
+        Self::ImageRgba8(image.into())
+    }
+}
+
+impl TryFrom<&RgbaImage> for FBox<Image> {
+    type Error = SfError;
+
+    fn try_from(image: &RgbaImage) -> Result<Self, Self::Error> {
+        let mut new = Image::new()?;
+        // SAFETY: the buffer of an `RgbaImage` has at least 4 bytes for each of its pixels
+        unsafe {
+            new.resize_with_pixels_unchecked(
+                Vector2u::new(image.width(), image.height()),
+                image.as_raw(),
+            );
+        }
+        Ok(new)
+    }
+}
+
+impl TryFrom<&DynamicImage> for FBox<Image> {
+    type Error = SfError;
+
+    /// Converts the image to 8-bit RGBA first, if needed.
+    fn try_from(image: &DynamicImage) -> Result<Self, Self::Error> {
+        match image {
+            DynamicImage::ImageRgba8(image) => image.try_into(),
+            image => (&image.to_rgba8()).try_into(),
+        }
+    }
+}
+
+/// Loading and saving through the `image` crate
+impl Image {
+    /// Load an image file with the `image` crate.
+    ///
+    /// Unlike [`Image::from_file`], this supports all the formats enabled on the `image`
+    /// crate, like `WebP`, TIFF or AVIF. The format is guessed from the content of the file,
+    /// then from its extension.
+    ///
+    /// The `image` feature enables the BMP, GIF, ICO, JPEG, PNG, PNM, QOI, TGA, TIFF and `WebP`
+    /// formats of the `image` crate. Enable the other ones you need on your own `image`
+    /// dependency, like `image = { version = "0.25", features = ["avif-native"] }`.
+    pub fn decode_from_file(path: impl AsRef<Path>) -> Result<FBox<Self>, ImageCrateError> {
+        let image = ::image::ImageReader::open(path)?
+            .with_guessed_format()?
+            .decode()?;
+        Ok((&image).try_into()?)
+    }
+
+    /// Load an image from file data in memory with the `image` crate.
+    ///
+    /// See [`Image::decode_from_file`].
+    pub fn decode_from_memory(data: &[u8]) -> Result<FBox<Self>, ImageCrateError> {
+        Ok((&::image::load_from_memory(data)?).try_into()?)
+    }
+
+    /// Save the image to a file with the `image` crate.
+    ///
+    /// Unlike [`Image::save_to_file`], this supports all the formats enabled on the `image`
+    /// crate. The format is deduced from the extension of `path`.
+    pub fn encode_to_file(&self, path: impl AsRef<Path>) -> Result<(), ImageCrateError> {
+        RgbaImage::from(self).save(path)?;
+        Ok(())
+    }
+
+    /// Save the image to a buffer in memory with the `image` crate, in `format`.
+    ///
+    /// See [`Image::encode_to_file`].
+    pub fn encode_to_memory(&self, format: ImageFormat) -> Result<Vec<u8>, ImageCrateError> {
+        let mut data = Cursor::new(Vec::new());
+        RgbaImage::from(self).write_to(&mut data, format)?;
+        Ok(data.into_inner())
+    }
+}
+
+/// Loading through the `image` crate
+impl Texture {
+    /// Load a texture from an image file with the `image` crate.
+    ///
+    /// See [`Image::decode_from_file`].
+    pub fn decode_from_file(path: impl AsRef<Path>) -> Result<FBox<Self>, ImageCrateError> {
+        let image = Image::decode_from_file(path)?;
+        Ok(Texture::from_image(&image, false, IntRect::default())?)
+    }
+
+    /// Load a texture from image file data in memory with the `image` crate.
+    ///
+    /// See [`Image::decode_from_memory`].
+    pub fn decode_from_memory(data: &[u8]) -> Result<FBox<Self>, ImageCrateError> {
+        let image = Image::decode_from_memory(data)?;
+        Ok(Texture::from_image(&image, false, IntRect::default())?)
+    }
+}
+
+/// Error that can happen when loading or saving through the `image` crate
+#[derive(Debug)]
+pub enum ImageCrateError {
+    /// The `image` crate failed to read, decode, encode or write the image
+    Image(ImageError),
+    /// The SFML image or texture couldn't be created
+    Sf(SfError),
+}
+
+impl From<ImageError> for ImageCrateError {
+    fn from(error: ImageError) -> Self {
+        Self::Image(error)
+    }
+}
+
+impl From<std::io::Error> for ImageCrateError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Image(ImageError::IoError(error))
+    }
+}
+
+impl From<SfError> for ImageCrateError {
+    fn from(error: SfError) -> Self {
+        Self::Sf(error)
+    }
+}
+
+impl Error for ImageCrateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Image(error) => Some(error),
+            Self::Sf(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for ImageCrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Image(error) => write!(f, "image crate error: {error}"),
+            Self::Sf(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        ::image::{ImageBuffer, Rgb, Rgba},
+    };
+
+    fn gradient() -> RgbaImage {
+        ImageBuffer::from_fn(3, 2, |x, y| {
+            #[expect(clippy::cast_possible_truncation, reason = "the image is tiny")]
+            Rgba([x as u8 * 80, y as u8 * 200, 30, 255 - x as u8])
+        })
+    }
+
+    #[test]
+    fn rgba_image_round_trip() -> Result<(), Box<dyn Error>> {
+        let original = gradient();
+        let image = FBox::<Image>::try_from(&original)?;
+        assert_eq!(image.size(), Vector2u::new(3, 2));
+        assert_eq!(image.pixel_data(), original.as_raw().as_slice());
+        assert_eq!(RgbaImage::from(&*image), original);
+        Ok(())
+    }
+
+    #[test]
+    fn dynamic_image_is_converted_to_rgba() -> Result<(), Box<dyn Error>> {
+        let rgb = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(2, 2, Rgb([1, 2, 3])));
+        let image = FBox::<Image>::try_from(&rgb)?;
+        assert_eq!(image.pixel_data(), [1, 2, 3, 255].repeat(4));
+        assert_eq!(
+            DynamicImage::from(&*image),
+            DynamicImage::ImageRgba8(rgb.to_rgba8())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn encode_decode_round_trip() -> Result<(), Box<dyn Error>> {
+        let image = FBox::<Image>::try_from(&gradient())?;
+        for format in [ImageFormat::Png, ImageFormat::Tiff, ImageFormat::WebP] {
+            let data = image.encode_to_memory(format)?;
+            let decoded = Image::decode_from_memory(&data)?;
+            assert_eq!(decoded.pixel_data(), image.pixel_data(), "{format:?}");
+        }
+        Ok(())
+    }
+}
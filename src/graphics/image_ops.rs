@@ -0,0 +1,553 @@
+use {
+    crate::{
+        SfResult,
+        cpp::FBox,
+        graphics::{Image, IntRect},
+        system::{Angle, Vector2i, Vector2u},
+    },
+    std::collections::VecDeque,
+};
+
+/// Filter used to resample an image, see [`Image::rescale`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScaleFilter {
+    /// Each pixel takes the color of the closest source pixel. Keeps pixel art sharp.
+    Nearest,
+    /// Linear interpolation between the 2x2 closest source pixels
+    #[default]
+    Bilinear,
+    /// Catmull-Rom interpolation between the 4x4 closest source pixels. Sharper than
+    /// bilinear, with a slight ringing on hard edges.
+    Bicubic,
+}
+
+impl ScaleFilter {
+    fn support(self) -> f32 {
+        match self {
+            Self::Nearest => 0.5,
+            Self::Bilinear => 1.,
+            Self::Bicubic => 2.,
+        }
+    }
+
+    fn kernel(self, x: f32) -> f32 {
+        let x = x.abs();
+        match self {
+            Self::Nearest => f32::from(u8::from(x < 0.5)),
+            Self::Bilinear => (1. - x).max(0.),
+            Self::Bicubic => {
+                if x < 1. {
+                    (1.5 * x - 2.5) * x * x + 1.
+                } else if x < 2. {
+                    ((-0.5 * x + 2.5) * x - 4.) * x + 2.
+                } else {
+                    0.
+                }
+            }
+        }
+    }
+}
+
+/// Sub-images, rotation and scaling
+impl Image {
+    /// Create a new image from the pixels of `area`.
+    ///
+    /// `area` is clipped to the bounds of the image, so the new image can be smaller than it,
+    /// or empty.
+    pub fn sub_image(&self, area: IntRect) -> SfResult<FBox<Image>> {
+        let mut image = Image::new()?;
+        let (size, pixels) = self.area_pixels(area);
+        image.set_pixels(size, &pixels);
+        Ok(image)
+    }
+
+    /// Keep only the pixels of `area`.
+    ///
+    /// `area` is clipped to the bounds of the image, so the image is empty if `area` is
+    /// outside of it.
+    pub fn crop(&mut self, area: IntRect) {
+        let (size, pixels) = self.area_pixels(area);
+        self.set_pixels(size, &pixels);
+    }
+
+    /// Rotate the image by 90 degrees clockwise
+    pub fn rotate_90(&mut self) {
+        let size = self.size();
+        let pixels = rotate_quarter(self.pixel_data(), size, true);
+        self.set_pixels(Vector2u::new(size.y, size.x), &pixels);
+    }
+
+    /// Rotate the image by 180 degrees
+    pub fn rotate_180(&mut self) {
+        self.pixels_mut().reverse();
+    }
+
+    /// Rotate the image by 270 degrees clockwise (90 degrees counterclockwise)
+    pub fn rotate_270(&mut self) {
+        let size = self.size();
+        let pixels = rotate_quarter(self.pixel_data(), size, false);
+        self.set_pixels(Vector2u::new(size.y, size.x), &pixels);
+    }
+
+    /// Resample the image to `size` with `filter`.
+    ///
+    /// Unlike [`Image::resize_with_color`], this scales the content of the image. Colors are
+    /// interpolated with premultiplied alpha, so transparent pixels don't darken the edges.
+    /// When shrinking, the filter is widened so that all the source pixels contribute.
+    pub fn rescale(&mut self, size: Vector2u, filter: ScaleFilter) {
+        let pixels = resample(self.pixel_data(), self.size(), size, filter);
+        self.set_pixels(size, &pixels);
+    }
+
+    fn area_pixels(&self, area: IntRect) -> (Vector2u, Vec<u8>) {
+        let size = self.size();
+        let bounds = IntRect::new(
+            Vector2i::default(),
+            Vector2i::new(
+                i32::try_from(size.x).unwrap_or(i32::MAX),
+                i32::try_from(size.y).unwrap_or(i32::MAX),
+            ),
+        );
+        let Some(area) = area.intersection(&bounds) else {
+            return (Vector2u::default(), Vec::new());
+        };
+        let area_size: Vector2u = area.size.as_other();
+        let area_position: Vector2u = area.position.as_other();
+        let (width, x) = (size.x as usize, area_position.x as usize);
+        let row_length = area_size.x as usize * 4;
+        let mut pixels = Vec::with_capacity(row_length * area_size.y as usize);
+        for row in self
+            .pixel_data()
+            .chunks_exact(width * 4)
+            .skip(area_position.y as usize)
+            .take(area_size.y as usize)
+        {
+            pixels.extend_from_slice(&row[x * 4..x * 4 + row_length]);
+        }
+        (area_size, pixels)
+    }
+
+    /// Replace the pixels of the image with `pixels`, which were made for `size`.
+    fn set_pixels(&mut self, size: Vector2u, pixels: &[u8]) {
+        debug_assert_eq!(pixels.len(), size.x as usize * size.y as usize * 4);
+        // SAFETY: the operations of this module make 4 bytes for each pixel of `size`
+        unsafe { self.resize_with_pixels_unchecked(size, pixels) }
+    }
+}
+
+/// Filters and color adjustments
+impl Image {
+    /// Blur the image with a Gaussian filter of standard deviation `sigma`, in pixels.
+    ///
+    /// The pixels outside of the image are considered to repeat the edge pixels. Colors are
+    /// blurred with premultiplied alpha.
+    pub fn gaussian_blur(&mut self, sigma: f32) {
+        if sigma <= 0. {
+            return;
+        }
+        let pixels = blur(self.pixel_data(), self.size(), sigma);
+        self.set_pixels(self.size(), &pixels);
+    }
+
+    /// Add `amount` to the red, green and blue components of each pixel, where 1 is the full
+    /// range: -1 makes the image black, and 1 makes it white.
+    pub fn adjust_brightness(&mut self, amount: f32) {
+        self.map_colors(|rgb| rgb.map(|c| c + amount));
+    }
+
+    /// Scale the difference between the components of each pixel and mid-gray by `factor`:
+    /// 1 keeps the image unchanged, 0 makes it gray, and larger values increase the contrast.
+    pub fn adjust_contrast(&mut self, factor: f32) {
+        self.map_colors(|rgb| rgb.map(|c| (c - 0.5) * factor + 0.5));
+    }
+
+    /// Rotate the hue of each pixel by `angle`, keeping its saturation and value.
+    pub fn shift_hue(&mut self, angle: Angle) {
+        let shift = angle.wrap_unsigned().as_degrees() / 60.;
+        self.map_colors(|rgb| {
+            let (hue, saturation, value) = rgb_to_hsv(rgb);
+            hsv_to_rgb((hue + shift) % 6., saturation, value)
+        });
+    }
+
+    /// Multiply the color components of each pixel by its alpha.
+    ///
+    /// Premultiplied images are drawn with [`BlendMode`]s whose color source factor is `One`
+    /// instead of `SrcAlpha`, and filter without dark fringes.
+    ///
+    /// [`BlendMode`]: crate::graphics::BlendMode
+    pub fn premultiply_alpha(&mut self) {
+        self.map_pixels(|pixel| {
+            let alpha = u16::from(pixel[3]);
+            for c in &mut pixel[..3] {
+                *c = div_255(u16::from(*c) * alpha);
+            }
+        });
+    }
+
+    /// Divide the color components of each pixel by its alpha, undoing
+    /// [`premultiply_alpha`](Image::premultiply_alpha) up to rounding.
+    ///
+    /// Fully transparent pixels are left unchanged.
+    pub fn unpremultiply_alpha(&mut self) {
+        self.map_pixels(|pixel| {
+            let alpha = u32::from(pixel[3]);
+            if alpha == 0 {
+                return;
+            }
+            for c in &mut pixel[..3] {
+                let value = (u32::from(*c) * 255 + alpha / 2) / alpha;
+                *c = u8::try_from(value).unwrap_or(u8::MAX);
+            }
+        });
+    }
+
+    /// Give the fully transparent pixels the color of the closest visible pixels, keeping
+    /// them transparent.
+    ///
+    /// Texture filtering and mipmapping blend the edge pixels of a sprite with their
+    /// transparent neighbours. Their color is usually black or white, which shows as a dark
+    /// or light outline. Bleeding the colors of the visible pixels into them removes it.
+    pub fn alpha_bleed(&mut self) {
+        let size = self.size();
+        bleed(self.pixel_data_mut(), size);
+    }
+
+    fn map_pixels(&mut self, f: impl FnMut(&mut [u8])) {
+        self.pixel_data_mut().chunks_exact_mut(4).for_each(f);
+    }
+
+    fn map_colors(&mut self, mut f: impl FnMut([f32; 3]) -> [f32; 3]) {
+        self.map_pixels(|pixel| {
+            let rgb = f([pixel[0], pixel[1], pixel[2]].map(|c| f32::from(c) / 255.));
+            for (c, value) in pixel.iter_mut().zip(rgb) {
+                *c = to_u8(value * 255.);
+            }
+        });
+    }
+}
+
+type Rgba = [f32; 4];
+
+/// The source pixels that make an output pixel, starting at `first`, with their weights.
+///
+/// Indices outside of the source are clamped to its edges.
+struct Contribution {
+    first: isize,
+    weights: Vec<f32>,
+}
+
+fn to_u8(value: f32) -> u8 {
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let value = value.round().clamp(0., 255.) as u8;
+    value
+}
+
+fn div_255(value: u16) -> u8 {
+    u8::try_from((value + 128 + ((value + 128) >> 8)) >> 8).unwrap_or(u8::MAX)
+}
+
+fn rotate_quarter(pixels: &[u8], size: Vector2u, clockwise: bool) -> Vec<u8> {
+    let (width, height) = (size.x as usize, size.y as usize);
+    let mut rotated = vec![0; pixels.len()];
+    for (i, pixel) in pixels.chunks_exact(4).enumerate() {
+        let (x, y) = (i % width, i / width);
+        // The rotated image is `height` pixels wide
+        let (new_x, new_y) = if clockwise {
+            (height - 1 - y, x)
+        } else {
+            (y, width - 1 - x)
+        };
+        let start = (new_y * height + new_x) * 4;
+        rotated[start..start + 4].copy_from_slice(pixel);
+    }
+    rotated
+}
+
+fn to_premultiplied(pixels: &[u8]) -> Vec<Rgba> {
+    pixels
+        .chunks_exact(4)
+        .map(|pixel| {
+            let alpha = f32::from(pixel[3]) / 255.;
+            let [r, g, b] = [pixel[0], pixel[1], pixel[2]].map(|c| f32::from(c) / 255. * alpha);
+            [r, g, b, alpha]
+        })
+        .collect()
+}
+
+fn from_premultiplied(pixels: &[Rgba]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(pixels.len() * 4);
+    for &[r, g, b, alpha] in pixels {
+        let alpha = alpha.clamp(0., 1.);
+        if alpha > 0. {
+            bytes.extend([r, g, b].map(|c| to_u8(c.clamp(0., alpha) / alpha * 255.)));
+        } else {
+            bytes.extend([0; 3]);
+        }
+        bytes.push(to_u8(alpha * 255.));
+    }
+    bytes
+}
+
+fn transpose(pixels: &[Rgba], width: usize) -> Vec<Rgba> {
+    let height = pixels.len() / width;
+    let mut transposed = Vec::with_capacity(pixels.len());
+    for x in 0..width {
+        transposed.extend((0..height).map(|y| pixels[y * width + x]));
+    }
+    transposed
+}
+
+/// Apply `contributions` to each row, giving rows of `contributions.len()` pixels.
+#[expect(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+fn convolve_rows(pixels: &[Rgba], width: usize, contributions: &[Contribution]) -> Vec<Rgba> {
+    let mut result = Vec::with_capacity(pixels.len() / width * contributions.len());
+    for row in pixels.chunks_exact(width) {
+        for contribution in contributions {
+            let mut sum = [0.; 4];
+            for (i, &weight) in contribution.weights.iter().enumerate() {
+                let x = (contribution.first + i as isize).clamp(0, width as isize - 1);
+                for (sum, value) in sum.iter_mut().zip(row[x as usize]) {
+                    *sum += value * weight;
+                }
+            }
+            result.push(sum);
+        }
+    }
+    result
+}
+
+/// Convolve the rows, then the columns, of an image.
+fn convolve(
+    pixels: &[Rgba],
+    width: usize,
+    horizontal: &[Contribution],
+    vertical: &[Contribution],
+) -> Vec<Rgba> {
+    let rows = convolve_rows(pixels, width, horizontal);
+    let height = pixels.len() / width;
+    let columns = convolve_rows(&transpose(&rows, horizontal.len()), height, vertical);
+    transpose(&columns, vertical.len())
+}
+
+#[expect(clippy::cast_possible_truncation)]
+fn scale_contributions(from: usize, to: usize, filter: ScaleFilter) -> Vec<Contribution> {
+    let scale = from as f32 / to as f32;
+    // Widen the filter when shrinking, so that every source pixel contributes
+    let stretch = scale.max(1.);
+    let support = filter.support() * stretch;
+    (0..to)
+        .map(|i| {
+            let center = (i as f32 + 0.5) * scale;
+            let first = (center - support).floor() as isize;
+            let last = (center + support).ceil() as isize;
+            let mut weights: Vec<f32> = (first..last)
+                .map(|j| filter.kernel((j as f32 + 0.5 - center) / stretch))
+                .collect();
+            let total: f32 = weights.iter().sum();
+            if total != 0. {
+                weights.iter_mut().for_each(|weight| *weight /= total);
+            }
+            Contribution { first, weights }
+        })
+        .collect()
+}
+
+fn resample(pixels: &[u8], size: Vector2u, new_size: Vector2u, filter: ScaleFilter) -> Vec<u8> {
+    let (width, height) = (size.x as usize, size.y as usize);
+    let (new_width, new_height) = (new_size.x as usize, new_size.y as usize);
+    if new_width == 0 || new_height == 0 {
+        return Vec::new();
+    }
+    if width == 0 || height == 0 {
+        return vec![0; new_width * new_height * 4];
+    }
+    if filter == ScaleFilter::Nearest {
+        let mut resampled = Vec::with_capacity(new_width * new_height * 4);
+        for y in 0..new_height {
+            let src_y = (2 * y + 1) * height / (2 * new_height);
+            for x in 0..new_width {
+                let src = (src_y * width + (2 * x + 1) * width / (2 * new_width)) * 4;
+                resampled.extend_from_slice(&pixels[src..src + 4]);
+            }
+        }
+        return resampled;
+    }
+    from_premultiplied(&convolve(
+        &to_premultiplied(pixels),
+        width,
+        &scale_contributions(width, new_width, filter),
+        &scale_contributions(height, new_height, filter),
+    ))
+}
+
+#[expect(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn blur(pixels: &[u8], size: Vector2u, sigma: f32) -> Vec<u8> {
+    let (width, height) = (size.x as usize, size.y as usize);
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+    let radius = (sigma * 3.).ceil() as isize;
+    let mut weights: Vec<f32> = (-radius..=radius)
+        .map(|i| (-(i * i) as f32 / (2. * sigma * sigma)).exp())
+        .collect();
+    let total: f32 = weights.iter().sum();
+    weights.iter_mut().for_each(|weight| *weight /= total);
+    let contributions = |length: usize| -> Vec<Contribution> {
+        (0..length as isize)
+            .map(|i| Contribution {
+                first: i - radius,
+                weights: weights.clone(),
+            })
+            .collect()
+    };
+    from_premultiplied(&convolve(
+        &to_premultiplied(pixels),
+        width,
+        &contributions(width),
+        &contributions(height),
+    ))
+}
+
+/// Give each transparent pixel the average color of its neighbours that are visible, or
+/// closer to a visible pixel, in waves from the visible pixels.
+fn bleed(pixels: &mut [u8], size: Vector2u) {
+    let (width, height) = (size.x as usize, size.y as usize);
+    let mut colored: Vec<bool> = pixels.chunks_exact(4).map(|pixel| pixel[3] > 0).collect();
+    let neighbours = |i: usize| {
+        let (x, y) = (i % width, i / width);
+        (y.saturating_sub(1)..(y + 2).min(height)).flat_map(move |ny| {
+            (x.saturating_sub(1)..(x + 2).min(width))
+                .map(move |nx| ny * width + nx)
+                .filter(move |&n| n != i)
+        })
+    };
+    let mut queued = colored.clone();
+    let mut wave: VecDeque<usize> = VecDeque::new();
+    for i in 0..colored.len() {
+        if !colored[i] && neighbours(i).any(|n| colored[n]) {
+            queued[i] = true;
+            wave.push_back(i);
+        }
+    }
+    while !wave.is_empty() {
+        let mut next = VecDeque::new();
+        let mut filled = Vec::with_capacity(wave.len());
+        for &i in &wave {
+            let mut sum = [0u32; 3];
+            let mut count = 0;
+            for n in neighbours(i).filter(|&n| colored[n]) {
+                for (sum, &c) in sum.iter_mut().zip(&pixels[n * 4..n * 4 + 3]) {
+                    *sum += u32::from(c);
+                }
+                count += 1;
+            }
+            filled.push((i, sum.map(|c| u8::try_from(c / count).unwrap_or(u8::MAX))));
+            for n in neighbours(i) {
+                if !queued[n] {
+                    queued[n] = true;
+                    next.push_back(n);
+                }
+            }
+        }
+        // Colors are only taken from the previous waves, so that they don't depend on the
+        // order of the pixels within a wave
+        for (i, rgb) in filled {
+            pixels[i * 4..i * 4 + 3].copy_from_slice(&rgb);
+            colored[i] = true;
+        }
+        wave = next;
+    }
+}
+
+/// Convert a color to a hue in `0..6`, a saturation and a value
+fn rgb_to_hsv([r, g, b]: [f32; 3]) -> (f32, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta <= 0. {
+        0.
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6.)
+    } else if max == g {
+        (b - r) / delta + 2.
+    } else {
+        (r - g) / delta + 4.
+    };
+    let saturation = if max > 0. { delta / max } else { 0. };
+    (hue, saturation, max)
+}
+
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> [f32; 3] {
+    let channel = |n: f32| {
+        let k = (n + hue) % 6.;
+        value - value * saturation * k.min(4. - k).clamp(0., 1.)
+    };
+    [channel(5.), channel(3.), channel(1.)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate_quarters() {
+        // 2x1: red, green
+        let pixels = [255, 0, 0, 255, 0, 255, 0, 255];
+        let clockwise = rotate_quarter(&pixels, Vector2u::new(2, 1), true);
+        assert_eq!(clockwise, pixels);
+        let back = rotate_quarter(&clockwise, Vector2u::new(1, 2), true);
+        assert_eq!(back, [0, 255, 0, 255, 255, 0, 0, 255]);
+        let counterclockwise = rotate_quarter(&pixels, Vector2u::new(2, 1), false);
+        assert_eq!(counterclockwise, [0, 255, 0, 255, 255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn resample_keeps_uniform_colors() {
+        let pixels = [10, 200, 30, 255].repeat(6);
+        for filter in [
+            ScaleFilter::Nearest,
+            ScaleFilter::Bilinear,
+            ScaleFilter::Bicubic,
+        ] {
+            let resampled = resample(&pixels, Vector2u::new(3, 2), Vector2u::new(5, 1), filter);
+            assert_eq!(resampled, [10, 200, 30, 255].repeat(5), "{filter:?}");
+        }
+        let blurred = blur(&pixels, Vector2u::new(3, 2), 1.5);
+        assert_eq!(blurred, pixels);
+    }
+
+    #[test]
+    fn nearest_doubles_pixels() {
+        let pixels = [1, 1, 1, 1, 2, 2, 2, 2];
+        let resampled = resample(
+            &pixels,
+            Vector2u::new(2, 1),
+            Vector2u::new(4, 1),
+            ScaleFilter::Nearest,
+        );
+        assert_eq!(resampled, [1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn bleed_spreads_visible_colors() {
+        let mut pixels = [0, 0, 0, 0, 200, 100, 50, 255, 0, 0, 0, 0, 0, 0, 0, 0].to_vec();
+        bleed(&mut pixels, Vector2u::new(4, 1));
+        assert_eq!(
+            pixels,
+            [
+                200, 100, 50, 0, 200, 100, 50, 255, 200, 100, 50, 0, 200, 100, 50, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn hue_round_trip() {
+        let (hue, saturation, value) = rgb_to_hsv([1., 0.5, 0.]);
+        assert_eq!((hue, saturation, value), (0.5, 1., 1.));
+        assert_eq!(
+            hsv_to_rgb((hue + 2.) % 6., saturation, value),
+            [0., 1., 0.5]
+        );
+    }
+}
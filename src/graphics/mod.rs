@@ -1,19 +1,30 @@
 //! 2D graphics module: sprites, text, shapes..
 
-use crate::system::Vector2f;
-
 #[doc(inline)]
 pub use self::blend_mode::BlendMode;
+use crate::system::Vector2f;
+#[cfg(feature = "image")]
+pub use self::image_crate::ImageCrateError;
+#[cfg(feature = "derive")]
+pub use sfml_derive::Uniforms;
 pub use {
     self::{
+        bitmap_font::{BitmapFont, BitmapFontError, BitmapGlyph, BitmapText},
+        camera::Camera,
         circle_shape::CircleShape,
         color::Color,
         convex_shape::ConvexShape,
         custom_shape::{CustomShape, CustomShapePoints},
         drawable::Drawable,
+        ellipse_shape::EllipseShape,
         font::{Font, Info as FontInfo},
+        font_family::FontFamily,
         glyph::Glyph,
-        image::{Image, PixelAccessError},
+        image::{Image, PixelAccessError, PixelDataError},
+        image_ops::ScaleFilter,
+        path::{FillRule, Path, PathShape},
+        pie_shape::PieShape,
+        polyline::{LineCap, LineJoin, Polyline},
         primitive_type::PrimitiveType,
         rc_font::RcFont,
         rc_sprite::RcSprite,
@@ -21,18 +32,30 @@ pub use {
         rc_texture::RcTexture,
         rect::{FloatRect, IntRect, Rect},
         rectangle_shape::RectangleShape,
+        render_queue::RenderQueue,
         render_states::RenderStates,
         render_target::RenderTarget,
         render_texture::RenderTexture,
         render_window::RenderWindow,
+        rich_text::{MarkupError, RichText, TextSpan},
+        ring_shape::RingShape,
+        rounded_rectangle_shape::RoundedRectangleShape,
+        scaling_policy::ScalingPolicy,
+        scene_graph::{NodeId, SceneGraph, SceneNode},
         shader::Shader,
+        shader_preprocessor::{ShaderPreprocessor, ShaderSourceError, ShaderWatcher, SourceMap},
         shape::Shape,
         sprite::Sprite,
+        star_shape::StarShape,
         text::Text,
+        text_layout::{LineMetrics, TextAlignment, TextLayout},
         text_style::TextStyle,
         texture::Texture,
         transform::Transform,
         transformable::Transformable,
+        uniforms::{
+            CurrentTexture, UniformBinding, UniformError, UniformLocation, UniformValue, Uniforms,
+        },
         vertex::Vertex,
         vertex_buffer::{VertexBuffer, VertexBufferUsage},
         view::View,
@@ -40,35 +63,61 @@ pub use {
     crate::ffi::graphics::ShaderType,
 };
 
+mod bitmap_font;
 pub mod blend_mode;
+mod camera;
 mod circle_shape;
+pub mod collision;
 mod color;
 mod convex_shape;
 mod custom_shape;
 mod drawable;
+mod ellipse_shape;
 mod font;
+mod font_family;
 pub mod glsl;
 mod glyph;
 mod image;
+#[cfg(feature = "image")]
+mod image_crate;
+mod image_ops;
+pub mod particles;
+mod path;
+mod pie_shape;
+mod polyline;
+pub mod post_process;
 mod primitive_type;
 mod rc_font;
 mod rc_sprite;
 mod rc_text;
 mod rc_texture;
+pub mod recording;
 mod rect;
 mod rectangle_shape;
+mod render_queue;
 mod render_states;
 mod render_target;
 mod render_texture;
 mod render_window;
+mod rich_text;
+mod ring_shape;
+mod rounded_rectangle_shape;
+mod scaling_policy;
+mod scene_graph;
+pub mod sdf;
 mod shader;
+mod shader_preprocessor;
 mod shape;
 mod sprite;
+mod star_shape;
+pub mod testing;
 mod text;
+mod text_layout;
 mod text_style;
 mod texture;
 mod transform;
 mod transformable;
+mod uniforms;
 mod vertex;
 mod vertex_buffer;
 mod view;
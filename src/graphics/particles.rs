@@ -0,0 +1,662 @@
+//! 2D particle system.
+//!
+//! A [`ParticleSystem`] owns a set of [`Emitter`]s that spawn particles, and a set of
+//! [`Affector`]s that modify them every frame. All the particles of a system are rendered
+//! with a single call to [`RenderTarget::draw_primitives`], either as points or as
+//! textured quads.
+//!
+//! The simulation is deterministic: two systems created with the same seed, configured
+//! the same way and updated with the same sequence of time steps produce the same particles.
+//!
+//! # Usage example
+//!
+//! ```no_run
+//! use sfml::{
+//!     graphics::{
+//!         Color, RenderTarget, RenderWindow, Transformable,
+//!         particles::{ColorOverLifetime, EmissionShape, Emitter, Gravity, ParticleSystem},
+//!     },
+//!     system::{Time, Vector2f},
+//! };
+//! # let mut window: RenderWindow = unimplemented!();
+//!
+//! let mut system = ParticleSystem::new(42);
+//! let mut emitter = Emitter::new();
+//! emitter.rate = 200.;
+//! emitter.shape = EmissionShape::Circle { radius: 10. };
+//! emitter.lifetime = Time::seconds(1.)..=Time::seconds(2.);
+//! emitter.set_position((400., 300.));
+//! system.add_emitter(emitter);
+//! system.add_affector(Gravity(Vector2f::new(0., 100.)));
+//! system.add_affector(ColorOverLifetime {
+//!     start: Color::YELLOW,
+//!     end: Color::rgba(255, 0, 0, 0),
+//! });
+//!
+//! // In the main loop
+//! system.update(Time::seconds(1. / 60.));
+//! window.draw(&system);
+//! ```
+//!
+//! [`RenderTarget::draw_primitives`]: crate::graphics::RenderTarget::draw_primitives
+
+use {
+    crate::{
+        graphics::{
+            Color, Drawable, IntRect, PrimitiveType, RenderStates, RenderTarget, Texture,
+            Transformable, Vertex,
+            transformable::{Transformation, impl_transformable_via},
+        },
+        system::{Angle, Time, Vector2f},
+    },
+    std::{fmt, ops::RangeInclusive},
+};
+
+/// A single particle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    /// Current position, in world coordinates
+    pub position: Vector2f,
+    /// Current velocity, in units per second
+    pub velocity: Vector2f,
+    /// Current rotation
+    pub rotation: Angle,
+    /// Rotation applied per second
+    pub angular_velocity: Angle,
+    /// Width and height of the particle, when rendered as a quad
+    pub size: f32,
+    /// Current color
+    pub color: Color,
+    /// Time elapsed since the particle was spawned
+    pub age: Time,
+    /// Total time the particle lives for
+    pub lifetime: Time,
+}
+
+impl Particle {
+    /// Fraction of the lifetime that has elapsed, in range `[0, 1]`.
+    #[must_use]
+    pub fn life_ratio(&self) -> f32 {
+        if self.lifetime <= Time::ZERO {
+            1.
+        } else {
+            (self.age / self.lifetime).clamp(0., 1.)
+        }
+    }
+}
+
+/// Area in which an [`Emitter`] spawns its particles, in the emitter's local coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmissionShape {
+    /// All particles spawn at the emitter's origin
+    Point,
+    /// Particles spawn inside a disc centered on the origin
+    Circle {
+        /// Radius of the disc
+        radius: f32,
+    },
+    /// Particles spawn inside an annulus centered on the origin
+    Ring {
+        /// Radius of the hole
+        inner_radius: f32,
+        /// Radius of the outer edge
+        outer_radius: f32,
+    },
+    /// Particles spawn inside a rectangle centered on the origin
+    Rectangle {
+        /// Width and height of the rectangle
+        size: Vector2f,
+    },
+    /// Particles spawn on a horizontal segment centered on the origin
+    Line {
+        /// Length of the segment
+        length: f32,
+    },
+}
+
+/// Spawns particles into a [`ParticleSystem`].
+///
+/// The emission parameters are public fields. Ranges are sampled uniformly for every
+/// spawned particle.
+///
+/// An emitter is [`Transformable`]: its position, rotation and scale are applied to the
+/// emission shape, and its rotation is also applied to the emission direction.
+#[derive(Debug, Clone)]
+pub struct Emitter {
+    /// Number of particles emitted per second
+    pub rate: f32,
+    /// Area in which particles spawn
+    pub shape: EmissionShape,
+    /// Lifetime of the spawned particles
+    pub lifetime: RangeInclusive<Time>,
+    /// Initial speed of the spawned particles, in units per second
+    pub speed: RangeInclusive<f32>,
+    /// Direction in which the particles are emitted, relative to the emitter's rotation
+    pub direction: Angle,
+    /// Maximum deviation from `direction`, on either side
+    pub spread: Angle,
+    /// Initial size of the spawned particles
+    pub size: RangeInclusive<f32>,
+    /// Initial rotation of the spawned particles
+    pub rotation: RangeInclusive<Angle>,
+    /// Angular velocity of the spawned particles, per second
+    pub angular_velocity: RangeInclusive<Angle>,
+    /// Initial color of the spawned particles
+    pub color: Color,
+    pending_burst: u32,
+    accumulator: f32,
+    transformation: Transformation,
+}
+
+impl Default for Emitter {
+    fn default() -> Self {
+        Self {
+            rate: 10.,
+            shape: EmissionShape::Point,
+            lifetime: Time::seconds(1.)..=Time::seconds(1.),
+            speed: 50.0..=50.0,
+            direction: Angle::degrees(0.),
+            spread: Angle::degrees(180.),
+            size: 4.0..=4.0,
+            rotation: Angle::degrees(0.)..=Angle::degrees(0.),
+            angular_velocity: Angle::degrees(0.)..=Angle::degrees(0.),
+            color: Color::WHITE,
+            pending_burst: 0,
+            accumulator: 0.,
+            transformation: Transformation::default(),
+        }
+    }
+}
+
+impl Emitter {
+    /// Create a new emitter, emitting 10 white particles per second in every direction.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emit `count` particles at once, on the next update of the system.
+    pub fn burst(&mut self, count: u32) {
+        self.pending_burst = self.pending_burst.saturating_add(count);
+    }
+
+    fn spawn(&self, rng: &mut Rng) -> Particle {
+        let local = match self.shape {
+            EmissionShape::Point => Vector2f::new(0., 0.),
+            EmissionShape::Circle { radius } => {
+                let r = radius * rng.next_f32().sqrt();
+                Vector2f::from_polar(r, Angle::radians(rng.range(0., std::f32::consts::TAU)))
+            }
+            EmissionShape::Ring {
+                inner_radius,
+                outer_radius,
+            } => {
+                let (inner_sq, outer_sq) =
+                    (inner_radius * inner_radius, outer_radius * outer_radius);
+                let r = rng.range(inner_sq, outer_sq).sqrt();
+                Vector2f::new(r, 0.)
+                    .rotated_by(Angle::radians(rng.range(0., std::f32::consts::TAU)))
+            }
+            EmissionShape::Rectangle { size } => Vector2f::new(
+                rng.range(-size.x / 2., size.x / 2.),
+                rng.range(-size.y / 2., size.y / 2.),
+            ),
+            EmissionShape::Line { length } => {
+                Vector2f::new(rng.range(-length / 2., length / 2.), 0.)
+            }
+        };
+        let spread = self.spread.as_radians();
+        let direction = self.transformation.rotation()
+            + self.direction
+            + Angle::radians(rng.range(-spread, spread));
+        let speed = rng.range(*self.speed.start(), *self.speed.end());
+        let lifetime = Time::microseconds(rng.range_i64(
+            self.lifetime.start().as_microseconds(),
+            self.lifetime.end().as_microseconds(),
+        ));
+        Particle {
+            position: self.transformation.transform().transform_point(local),
+            velocity: Vector2f::new(speed, 0.).rotated_by(direction),
+            rotation: rng.range_angle(&self.rotation),
+            angular_velocity: rng.range_angle(&self.angular_velocity),
+            size: rng.range(*self.size.start(), *self.size.end()),
+            color: self.color,
+            age: Time::ZERO,
+            lifetime,
+        }
+    }
+}
+
+impl_transformable_via!(Emitter, transformation);
+
+/// Modifies particles of a [`ParticleSystem`] every update.
+///
+/// Closures taking a particle and the elapsed time also implement this trait.
+pub trait Affector {
+    /// Apply the affector to `particle`, for a time step of `dt`.
+    fn affect(&mut self, particle: &mut Particle, dt: Time);
+}
+
+impl<F: FnMut(&mut Particle, Time)> Affector for F {
+    fn affect(&mut self, particle: &mut Particle, dt: Time) {
+        self(particle, dt);
+    }
+}
+
+/// Applies a constant acceleration, in units per second squared.
+#[derive(Debug, Clone, Copy)]
+pub struct Gravity(pub Vector2f);
+
+impl Affector for Gravity {
+    fn affect(&mut self, particle: &mut Particle, dt: Time) {
+        particle.velocity += self.0 * dt.as_seconds();
+    }
+}
+
+/// Slows particles down exponentially.
+///
+/// Every second, the velocity is multiplied by `exp(-coefficient)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Drag {
+    /// Strength of the drag
+    pub coefficient: f32,
+}
+
+impl Affector for Drag {
+    fn affect(&mut self, particle: &mut Particle, dt: Time) {
+        particle.velocity *= (-self.coefficient * dt.as_seconds()).exp();
+    }
+}
+
+/// Interpolates the color of particles over their lifetime.
+#[derive(Debug, Clone, Copy)]
+pub struct ColorOverLifetime {
+    /// Color of newly spawned particles
+    pub start: Color,
+    /// Color of particles at the end of their life
+    pub end: Color,
+}
+
+impl Affector for ColorOverLifetime {
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn affect(&mut self, particle: &mut Particle, _dt: Time) {
+        let t = particle.life_ratio();
+        let lerp = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+        particle.color = Color::rgba(
+            lerp(self.start.r, self.end.r),
+            lerp(self.start.g, self.end.g),
+            lerp(self.start.b, self.end.b),
+            lerp(self.start.a, self.end.a),
+        );
+    }
+}
+
+/// Interpolates the size of particles over their lifetime.
+#[derive(Debug, Clone, Copy)]
+pub struct SizeOverLifetime {
+    /// Size of newly spawned particles
+    pub start: f32,
+    /// Size of particles at the end of their life
+    pub end: f32,
+}
+
+impl Affector for SizeOverLifetime {
+    fn affect(&mut self, particle: &mut Particle, _dt: Time) {
+        particle.size = self.start + (self.end - self.start) * particle.life_ratio();
+    }
+}
+
+/// Swirls particles around a center point.
+///
+/// The tangential acceleration is `strength / distance`, so particles close to the center
+/// spin faster. Positive strengths turn clockwise on screen.
+#[derive(Debug, Clone, Copy)]
+pub struct Vortex {
+    /// Center of the vortex, in world coordinates
+    pub center: Vector2f,
+    /// Strength of the vortex
+    pub strength: f32,
+}
+
+impl Affector for Vortex {
+    fn affect(&mut self, particle: &mut Particle, dt: Time) {
+        let offset = particle.position - self.center;
+        let distance = offset.length();
+        if distance > f32::EPSILON {
+            let tangent = offset.perpendicular() / distance;
+            particle.velocity += tangent * (self.strength / distance.max(1.) * dt.as_seconds());
+        }
+    }
+}
+
+/// How the particles of a [`ParticleSystem`] are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// One pixel-sized point per particle. Size, rotation and texture are ignored.
+    Points,
+    /// One quad per particle, textured with the texture rect of the system if any.
+    #[default]
+    Quads,
+}
+
+/// A collection of particles, spawned by [`Emitter`]s and animated by [`Affector`]s.
+///
+/// See the [module level documentation](self) for an example.
+pub struct ParticleSystem<'t> {
+    particles: Vec<Particle>,
+    emitters: Vec<Emitter>,
+    affectors: Vec<Box<dyn Affector>>,
+    texture: Option<&'t Texture>,
+    texture_rect: IntRect,
+    render_mode: RenderMode,
+    max_particles: usize,
+    rng: Rng,
+    vertices: Vec<Vertex>,
+}
+
+impl fmt::Debug for ParticleSystem<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParticleSystem")
+            .field("particles", &self.particles.len())
+            .field("emitters", &self.emitters)
+            .field("affectors", &self.affectors.len())
+            .field("texture", &self.texture)
+            .field("texture_rect", &self.texture_rect)
+            .field("render_mode", &self.render_mode)
+            .field("max_particles", &self.max_particles)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'t> ParticleSystem<'t> {
+    /// Create an empty particle system.
+    ///
+    /// `seed` initializes the random number generator used to sample emitter parameters.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            particles: Vec::new(),
+            emitters: Vec::new(),
+            affectors: Vec::new(),
+            texture: None,
+            texture_rect: IntRect::default(),
+            render_mode: RenderMode::default(),
+            max_particles: usize::MAX,
+            rng: Rng(seed),
+            vertices: Vec::new(),
+        }
+    }
+
+    /// Create an empty particle system that renders its particles with `texture`.
+    #[must_use]
+    pub fn with_texture(seed: u64, texture: &'t Texture) -> Self {
+        let mut system = Self::new(seed);
+        system.set_texture(texture, true);
+        system
+    }
+
+    /// Add an emitter, and return its index.
+    pub fn add_emitter(&mut self, emitter: Emitter) -> usize {
+        self.emitters.push(emitter);
+        self.emitters.len() - 1
+    }
+
+    /// Remove and return the emitter at `index`.
+    ///
+    /// The indices of the following emitters are shifted down by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_emitter(&mut self, index: usize) -> Emitter {
+        self.emitters.remove(index)
+    }
+
+    /// Get the emitters of the system.
+    #[must_use]
+    pub fn emitters(&self) -> &[Emitter] {
+        &self.emitters
+    }
+
+    /// Get mutable access to the emitters of the system, to move or reconfigure them.
+    pub fn emitters_mut(&mut self) -> &mut [Emitter] {
+        &mut self.emitters
+    }
+
+    /// Add an affector. Affectors are applied in the order they were added.
+    pub fn add_affector<A: Affector + 'static>(&mut self, affector: A) {
+        self.affectors.push(Box::new(affector));
+    }
+
+    /// Remove all the affectors.
+    pub fn clear_affectors(&mut self) {
+        self.affectors.clear();
+    }
+
+    /// Set the texture used to render the particles as quads.
+    ///
+    /// If `reset_rect` is `true`, the texture rect is adjusted to the size of the texture.
+    pub fn set_texture(&mut self, texture: &'t Texture, reset_rect: bool) {
+        self.texture = Some(texture);
+        if reset_rect {
+            self.texture_rect = IntRect::new(Default::default(), texture.size().as_other());
+        }
+    }
+
+    /// Render the particles without a texture.
+    pub fn disable_texture(&mut self) {
+        self.texture = None;
+    }
+
+    /// Get the texture used to render the particles, if any.
+    #[must_use]
+    pub fn texture(&self) -> Option<&'t Texture> {
+        self.texture
+    }
+
+    /// Set the sub-rectangle of the texture mapped onto every particle.
+    pub fn set_texture_rect(&mut self, rect: IntRect) {
+        self.texture_rect = rect;
+    }
+
+    /// Get the sub-rectangle of the texture mapped onto every particle.
+    #[must_use]
+    pub fn texture_rect(&self) -> IntRect {
+        self.texture_rect
+    }
+
+    /// Set how the particles are rendered. The default is [`RenderMode::Quads`].
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.render_mode = mode;
+    }
+
+    /// Get how the particles are rendered.
+    #[must_use]
+    pub fn render_mode(&self) -> RenderMode {
+        self.render_mode
+    }
+
+    /// Limit the number of live particles. Emitters stop spawning once it is reached.
+    ///
+    /// By default, the number of particles is unlimited.
+    pub fn set_max_particles(&mut self, max: usize) {
+        self.max_particles = max;
+        self.particles.truncate(max);
+    }
+
+    /// Get the live particles.
+    #[must_use]
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// Remove all the live particles.
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.vertices.clear();
+    }
+
+    /// Advance the simulation by `dt`.
+    ///
+    /// Particles age and die, affectors are applied, particles move,
+    /// then emitters spawn new particles. The vertices drawn by the system are rebuilt.
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn update(&mut self, dt: Time) {
+        let seconds = dt.as_seconds();
+        self.particles.retain_mut(|particle| {
+            particle.age += dt;
+            particle.age < particle.lifetime
+        });
+        for particle in &mut self.particles {
+            for affector in &mut self.affectors {
+                affector.affect(particle, dt);
+            }
+            particle.position += particle.velocity * seconds;
+            particle.rotation += particle.angular_velocity * seconds;
+        }
+        for emitter in &mut self.emitters {
+            emitter.accumulator += emitter.rate * seconds;
+            let whole = emitter.accumulator.floor();
+            emitter.accumulator -= whole;
+            let count = (whole as u32).saturating_add(std::mem::take(&mut emitter.pending_burst));
+            for _ in 0..count {
+                if self.particles.len() >= self.max_particles {
+                    break;
+                }
+                self.particles.push(emitter.spawn(&mut self.rng));
+            }
+        }
+        self.update_vertices();
+    }
+
+    fn update_vertices(&mut self) {
+        self.vertices.clear();
+        match self.render_mode {
+            RenderMode::Points => {
+                self.vertices.extend(
+                    self.particles
+                        .iter()
+                        .map(|p| Vertex::with_pos_color(p.position, p.color)),
+                );
+            }
+            RenderMode::Quads => {
+                let rect = self.texture_rect.as_other::<f32>();
+                let (left, top) = (rect.position.x, rect.position.y);
+                let (right, bottom) = (left + rect.size.x, top + rect.size.y);
+                let tex_coords = [
+                    Vector2f::new(left, top),
+                    Vector2f::new(right, top),
+                    Vector2f::new(right, bottom),
+                    Vector2f::new(left, bottom),
+                ];
+                for p in &self.particles {
+                    let half = p.size / 2.;
+                    let corners = [
+                        Vector2f::new(-half, -half),
+                        Vector2f::new(half, -half),
+                        Vector2f::new(half, half),
+                        Vector2f::new(-half, half),
+                    ]
+                    .map(|corner| p.position + corner.rotated_by(p.rotation));
+                    for i in [0, 1, 2, 0, 2, 3] {
+                        self.vertices
+                            .push(Vertex::new(corners[i], p.color, tex_coords[i]));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Drawable for ParticleSystem<'_> {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        let (primitive_type, texture) = match self.render_mode {
+            RenderMode::Points => (PrimitiveType::POINTS, None),
+            RenderMode::Quads => (PrimitiveType::TRIANGLES, self.texture),
+        };
+        let states = RenderStates { texture, ..*states };
+        target.draw_primitives(&self.vertices, primitive_type, &states);
+    }
+}
+
+/// Small deterministic random number generator (`SplitMix64`).
+#[derive(Debug, Clone, Copy)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    /// Uniform value in `[0, 1)`
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u32 << 24) as f32
+    }
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+    #[expect(clippy::cast_possible_truncation)]
+    fn range_i64(&mut self, min: i64, max: i64) -> i64 {
+        min + ((max - min) as f64 * f64::from(self.next_f32())) as i64
+    }
+    fn range_angle(&mut self, range: &RangeInclusive<Angle>) -> Angle {
+        Angle::radians(self.range(range.start().as_radians(), range.end().as_radians()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_is_splitmix64() {
+        let mut rng = Rng(1_234_567);
+        let values = [(); 5].map(|()| rng.next_u64());
+        assert_eq!(
+            values,
+            [
+                6_457_827_717_110_365_317,
+                3_203_168_211_198_807_973,
+                9_817_491_932_198_370_423,
+                4_593_380_528_125_082_431,
+                16_408_922_859_458_223_821,
+            ]
+        );
+    }
+
+    #[test]
+    fn same_seed_same_particles() {
+        let simulate = |seed| {
+            let mut system = ParticleSystem::new(seed);
+            let mut emitter = Emitter::new();
+            emitter.rate = 50.;
+            emitter.shape = EmissionShape::Ring {
+                inner_radius: 5.,
+                outer_radius: 10.,
+            };
+            emitter.speed = 10.0..=80.0;
+            emitter.lifetime = Time::seconds(0.5)..=Time::seconds(2.);
+            emitter.spread = Angle::degrees(45.);
+            emitter.size = 1.0..=6.0;
+            emitter.set_position((100., 50.));
+            emitter.burst(20);
+            let _ = system.add_emitter(emitter);
+            system.add_affector(Gravity(Vector2f::new(0., 98.)));
+            for step in 1..=30 {
+                system.update(Time::milliseconds(step * 3));
+            }
+            system.particles().to_vec()
+        };
+        let particles = simulate(42);
+        assert!(!particles.is_empty());
+        assert_eq!(particles, simulate(42));
+        assert_ne!(particles, simulate(43));
+    }
+}
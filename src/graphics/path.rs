@@ -0,0 +1,729 @@
+use crate::{
+    graphics::{
+        Color, Drawable, FloatRect, LineCap, LineJoin, PrimitiveType, RenderStates, RenderTarget,
+        Transformable, Vertex,
+        polyline::{self, Stroke},
+        transformable::{Transformation, impl_transformable_via},
+        vertex_array_bounds,
+    },
+    system::{Angle, Vector2f},
+};
+
+/// Rule deciding which areas enclosed by a [`Path`] are filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// A point is inside if a ray from it crosses the path an odd number of times.
+    ///
+    /// Nested contours make holes, whatever their direction.
+    #[default]
+    EvenOdd,
+    /// A point is inside if the path winds around it a non-zero number of times.
+    ///
+    /// Nested contours make holes only if they go in the opposite direction.
+    NonZero,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Contour {
+    points: Vec<Vector2f>,
+    closed: bool,
+}
+
+/// A vector path made of lines, Bézier curves and arcs.
+///
+/// A path is made of contours. [`move_to`](Self::move_to) starts a new contour, and the
+/// other methods extend the current one. Curves are flattened into line segments as they
+/// are added, with a precision set by [`set_tolerance`](Self::set_tolerance).
+///
+/// A path can be [filled](Self::fill), with any number of concave, self-intersecting or
+/// nested contours, and [outlined](Self::outline). Both produce triangles for
+/// [`RenderTarget::draw_primitives`]. To draw a path directly, use a [`PathShape`].
+///
+/// # Usage example
+///
+/// ```
+/// # use sfml::graphics::*;
+/// # use sfml::system::Vector2f;
+/// let mut path = Path::new();
+/// path.move_to((0., 0.))
+///     .line_to((100., 0.))
+///     .quad_to((150., 50.), (100., 100.))
+///     .line_to((0., 100.))
+///     .close();
+/// // A square hole
+/// path.move_to((25., 25.))
+///     .line_to((75., 25.))
+///     .line_to((75., 75.))
+///     .line_to((25., 75.))
+///     .close();
+/// let triangles = path.fill(FillRule::EvenOdd, Color::WHITE);
+/// assert!(!triangles.is_empty());
+/// assert_eq!(triangles.len() % 3, 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Path {
+    contours: Vec<Contour>,
+    tolerance: f32,
+}
+
+impl Default for Path {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creation
+impl Path {
+    /// Create an empty path, with a tolerance of a quarter of a pixel.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            contours: Vec::new(),
+            tolerance: 0.25,
+        }
+    }
+
+    /// Set the maximum distance between curves and the segments approximating them.
+    ///
+    /// This only affects curves added after the call.
+    pub fn set_tolerance(&mut self, tolerance: f32) {
+        self.tolerance = tolerance.max(1e-4);
+    }
+
+    /// Get the maximum distance between curves and the segments approximating them.
+    #[must_use]
+    pub fn tolerance(&self) -> f32 {
+        self.tolerance
+    }
+
+    /// Remove all the contours of the path.
+    pub fn clear(&mut self) {
+        self.contours.clear();
+    }
+
+    /// Tell whether the path has no contours.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.contours.is_empty()
+    }
+}
+
+/// Building
+impl Path {
+    /// Start a new contour at `point`.
+    pub fn move_to(&mut self, point: impl Into<Vector2f>) -> &mut Self {
+        self.contours.push(Contour {
+            points: vec![point.into()],
+            closed: false,
+        });
+        self
+    }
+
+    /// Add a straight line to `point`.
+    ///
+    /// Without a current contour, this starts one at `point`.
+    pub fn line_to(&mut self, point: impl Into<Vector2f>) -> &mut Self {
+        let point = point.into();
+        match self.open_contour() {
+            Some(contour) => contour.points.push(point),
+            None => {
+                let _ = self.move_to(point);
+            }
+        }
+        self
+    }
+
+    /// Add a quadratic Bézier curve to `point`, with a control point.
+    pub fn quad_to(
+        &mut self,
+        control: impl Into<Vector2f>,
+        point: impl Into<Vector2f>,
+    ) -> &mut Self {
+        let (control, end) = (control.into(), point.into());
+        let start = self.start_point(control);
+        let deviation = (start - control * 2. + end).length();
+        let steps = segment_count((deviation / (8. * self.tolerance)).sqrt());
+        for i in 1..=steps {
+            let t = i as f32 / steps as f32;
+            let u = 1. - t;
+            let _ = self.line_to(start * (u * u) + control * (2. * u * t) + end * (t * t));
+        }
+        self
+    }
+
+    /// Add a cubic Bézier curve to `point`, with two control points.
+    pub fn cubic_to(
+        &mut self,
+        control1: impl Into<Vector2f>,
+        control2: impl Into<Vector2f>,
+        point: impl Into<Vector2f>,
+    ) -> &mut Self {
+        let (c1, c2, end) = (control1.into(), control2.into(), point.into());
+        let start = self.start_point(c1);
+        let deviation = (start - c1 * 2. + c2)
+            .length()
+            .max((c1 - c2 * 2. + end).length());
+        let steps = segment_count((0.75 * deviation / self.tolerance).sqrt());
+        for i in 1..=steps {
+            let t = i as f32 / steps as f32;
+            let u = 1. - t;
+            let _ = self.line_to(
+                start * (u * u * u)
+                    + c1 * (3. * u * u * t)
+                    + c2 * (3. * u * t * t)
+                    + end * (t * t * t),
+            );
+        }
+        self
+    }
+
+    /// Add an arc of a circle.
+    ///
+    /// The arc starts at angle `start` and goes on for `sweep`, which is clockwise if positive.
+    /// It is connected to the current contour with a straight line, or starts a new contour
+    /// if there is none.
+    pub fn arc(
+        &mut self,
+        center: impl Into<Vector2f>,
+        radius: f32,
+        start: Angle,
+        sweep: Angle,
+    ) -> &mut Self {
+        let center = center.into();
+        let start = start.as_radians();
+        let sweep = sweep.as_radians();
+        let steps = self.arc_segment_count(radius, sweep);
+        for i in 0..=steps {
+            let angle = start + sweep * i as f32 / steps as f32;
+            let _ = self.line_to(center + Vector2f::new(angle.cos(), angle.sin()) * radius);
+        }
+        self
+    }
+
+    /// Add a line towards `corner`, rounded with an arc of `radius` tangent to both the line
+    /// from the current point to `corner`, and the line from `corner` to `end`.
+    ///
+    /// This works like `arcTo` of the HTML canvas: the contour ends at the second tangent
+    /// point, not at `end`.
+    pub fn arc_to(
+        &mut self,
+        corner: impl Into<Vector2f>,
+        end: impl Into<Vector2f>,
+        radius: f32,
+    ) -> &mut Self {
+        let (corner, end) = (corner.into(), end.into());
+        let start = self.start_point(corner);
+        let (to_start, to_end) = (start - corner, end - corner);
+        if radius <= 0. || to_start.length_sq() < 1e-10 || to_end.length_sq() < 1e-10 {
+            return self.line_to(corner);
+        }
+        let (to_start, to_end) = (to_start.normalized(), to_end.normalized());
+        let cos = to_start.dot(to_end).clamp(-1., 1.);
+        if cos.abs() > 1. - 1e-6 {
+            // Collinear, there is no arc
+            return self.line_to(corner);
+        }
+        let half = cos.acos() / 2.;
+        let tangent_distance = radius / half.tan();
+        let center = corner + (to_start + to_end).normalized() * (radius / half.sin());
+        let first = corner + to_start * tangent_distance - center;
+        let second = corner + to_end * tangent_distance - center;
+        let start_angle = first.y.atan2(first.x);
+        let sweep = first.cross(second).atan2(first.dot(second));
+        let _ = self.line_to(center + first);
+        self.arc(
+            center,
+            radius,
+            Angle::radians(start_angle),
+            Angle::radians(sweep),
+        )
+    }
+
+    /// Close the current contour, connecting its last point to its first one.
+    ///
+    /// Drawing after closing a contour starts a new one at its first point.
+    pub fn close(&mut self) -> &mut Self {
+        if let Some(contour) = self.contours.last_mut() {
+            contour.closed = true;
+        }
+        self
+    }
+
+    /// The contour that drawing commands extend, if any
+    fn open_contour(&mut self) -> Option<&mut Contour> {
+        let last = self.contours.last()?;
+        if last.closed {
+            let first = last.points[0];
+            self.contours.push(Contour {
+                points: vec![first],
+                closed: false,
+            });
+        }
+        self.contours.last_mut()
+    }
+
+    /// The point a curve starts from, starting a contour at `default` if there is none
+    fn start_point(&mut self, default: Vector2f) -> Vector2f {
+        match self.open_contour() {
+            Some(contour) => contour.points[contour.points.len() - 1],
+            None => {
+                let _ = self.move_to(default);
+                default
+            }
+        }
+    }
+
+    fn arc_segment_count(&self, radius: f32, sweep: f32) -> usize {
+        let max_step = 2.
+            * (1. - self.tolerance / radius.abs().max(1e-4))
+                .clamp(-1., 1.)
+                .acos();
+        segment_count(sweep.abs() / max_step.max(0.01))
+    }
+}
+
+fn segment_count(count: f32) -> usize {
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let count = count.ceil().clamp(1., 1024.) as usize;
+    count
+}
+
+/// Geometry
+impl Path {
+    /// Get the contours of the path, as flattened points and whether they are closed.
+    pub fn contours(&self) -> impl Iterator<Item = (&[Vector2f], bool)> {
+        self.contours
+            .iter()
+            .map(|contour| (&contour.points[..], contour.closed))
+    }
+
+    /// Get the bounding rectangle of the points of the path.
+    #[must_use]
+    pub fn bounds(&self) -> FloatRect {
+        let mut points = self.contours.iter().flat_map(|c| c.points.iter().copied());
+        let Some(first) = points.next() else {
+            return FloatRect::default();
+        };
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            (
+                Vector2f::new(min.x.min(p.x), min.y.min(p.y)),
+                Vector2f::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        });
+        FloatRect::new(min, max - min)
+    }
+
+    /// Triangulate the area enclosed by the path.
+    ///
+    /// All contours are treated as closed. The result is a list of triangles.
+    #[must_use]
+    pub fn fill(&self, rule: FillRule, color: Color) -> Vec<Vertex> {
+        let mut vertices = Vec::new();
+        tessellate(&self.contours, rule, color, &mut vertices);
+        vertices
+    }
+
+    /// Triangulate the outline of the path, centered on the contours.
+    ///
+    /// Open contours get caps at their ends. The result is a list of triangles.
+    #[must_use]
+    pub fn outline(&self, width: f32, join: LineJoin, cap: LineCap, color: Color) -> Vec<Vertex> {
+        let stroke = Stroke {
+            width,
+            join,
+            cap,
+            ..Stroke::default()
+        };
+        let mut vertices = Vec::new();
+        for contour in &self.contours {
+            polyline::stroke(
+                &contour.points,
+                contour.closed,
+                &stroke,
+                color,
+                &mut vertices,
+            );
+        }
+        vertices
+    }
+}
+
+/// A non-horizontal edge, going down from `top` to `bottom`
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    top: Vector2f,
+    bottom: Vector2f,
+    /// +1 if the contour goes down along the edge, -1 if it goes up
+    winding: i32,
+}
+
+impl Edge {
+    fn x_at(&self, y: f32) -> f32 {
+        let t = (y - self.top.y) / (self.bottom.y - self.top.y);
+        self.top.x + (self.bottom.x - self.top.x) * t
+    }
+}
+
+/// Triangulate the inside of `contours` by cutting it in horizontal trapezoids.
+///
+/// The plane is cut in bands at the y of every vertex and edge intersection. Within a
+/// band, edges don't cross, so sorting them by x and counting windings tells which spans
+/// between consecutive edges are inside.
+fn tessellate(contours: &[Contour], rule: FillRule, color: Color, out: &mut Vec<Vertex>) {
+    let mut edges = Vec::new();
+    for contour in contours {
+        let points = &contour.points;
+        for (i, &a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            if a.y < b.y {
+                edges.push(Edge {
+                    top: a,
+                    bottom: b,
+                    winding: 1,
+                });
+            } else if a.y > b.y {
+                edges.push(Edge {
+                    top: b,
+                    bottom: a,
+                    winding: -1,
+                });
+            }
+        }
+    }
+
+    let mut ys: Vec<f32> = edges.iter().flat_map(|e| [e.top.y, e.bottom.y]).collect();
+    for (i, a) in edges.iter().enumerate() {
+        for b in &edges[i + 1..] {
+            if let Some(y) = intersection_y(a, b) {
+                ys.push(y);
+            }
+        }
+    }
+    ys.sort_by(f32::total_cmp);
+    ys.dedup_by(|a, b| (*a - *b).abs() < 1e-5);
+
+    let mut active = Vec::new();
+    for band in ys.windows(2) {
+        let (y0, y1) = (band[0], band[1]);
+        let middle = (y0 + y1) / 2.;
+        active.clear();
+        active.extend(
+            edges
+                .iter()
+                .filter(|e| e.top.y <= middle && e.bottom.y >= middle)
+                .map(|e| (e.x_at(y0), e.x_at(y1), e.x_at(middle), e.winding)),
+        );
+        active.sort_by(|a, b| a.2.total_cmp(&b.2));
+        let mut winding = 0;
+        for pair in active.windows(2) {
+            let (left, right) = (pair[0], pair[1]);
+            winding += left.3;
+            let inside = match rule {
+                FillRule::EvenOdd => winding % 2 != 0,
+                FillRule::NonZero => winding != 0,
+            };
+            if inside {
+                let vertex = |x, y| Vertex::with_pos_color(Vector2f::new(x, y), color);
+                let (top_left, top_right) = (vertex(left.0, y0), vertex(right.0, y0));
+                let (bottom_left, bottom_right) = (vertex(left.1, y1), vertex(right.1, y1));
+                out.extend([
+                    top_left,
+                    top_right,
+                    bottom_left,
+                    bottom_left,
+                    top_right,
+                    bottom_right,
+                ]);
+            }
+        }
+    }
+}
+
+/// The y where two edges cross, excluding their end points
+fn intersection_y(a: &Edge, b: &Edge) -> Option<f32> {
+    let r = a.bottom - a.top;
+    let s = b.bottom - b.top;
+    let denominator = r.cross(s);
+    if denominator.abs() < 1e-12 {
+        return None;
+    }
+    let offset = b.top - a.top;
+    let t = offset.cross(s) / denominator;
+    let u = offset.cross(r) / denominator;
+    let inside = 1e-6..1. - 1e-6;
+    (inside.contains(&t) && inside.contains(&u)).then_some(a.top.y + r.y * t)
+}
+
+/// A [`Path`] that can be drawn, with a fill and an outline.
+///
+/// Like [`Shape`]s, the outline is drawn over the fill, with the given thickness.
+/// Unlike them, the outline is centered on the path.
+///
+/// [`Shape`]: crate::graphics::Shape
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut path = Path::new();
+/// path.move_to((50., 0.))
+///     .line_to((100., 100.))
+///     .line_to((0., 30.))
+///     .line_to((100., 30.))
+///     .line_to((0., 100.))
+///     .close();
+/// let mut star = PathShape::new(path);
+/// star.set_fill_rule(FillRule::NonZero);
+/// star.set_fill_color(Color::YELLOW);
+/// star.set_outline_thickness(3.);
+/// window.draw(&star);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathShape {
+    path: Path,
+    fill_rule: FillRule,
+    fill_color: Color,
+    outline_color: Color,
+    outline_thickness: f32,
+    join: LineJoin,
+    cap: LineCap,
+    transformation: Transformation,
+    fill_vertices: Vec<Vertex>,
+    outline_vertices: Vec<Vertex>,
+}
+
+/// Creation
+impl PathShape {
+    /// Create a white shape from a path, without an outline.
+    #[must_use]
+    pub fn new(path: Path) -> Self {
+        let mut shape = Self {
+            path,
+            fill_rule: FillRule::EvenOdd,
+            fill_color: Color::WHITE,
+            outline_color: Color::WHITE,
+            outline_thickness: 0.,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            transformation: Transformation::default(),
+            fill_vertices: Vec::new(),
+            outline_vertices: Vec::new(),
+        };
+        shape.update_fill();
+        shape.update_outline();
+        shape
+    }
+}
+
+/// Properties
+impl PathShape {
+    /// Replace the path of the shape.
+    pub fn set_path(&mut self, path: Path) {
+        self.path = path;
+        self.update_fill();
+        self.update_outline();
+    }
+
+    /// Get the path of the shape.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Set the rule deciding which areas are filled. The default is [`FillRule::EvenOdd`].
+    pub fn set_fill_rule(&mut self, rule: FillRule) {
+        self.fill_rule = rule;
+        self.update_fill();
+    }
+
+    /// Get the rule deciding which areas are filled.
+    #[must_use]
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    /// Set the fill color of the shape. The default is opaque white.
+    pub fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+        for vertex in &mut self.fill_vertices {
+            vertex.color = color;
+        }
+    }
+
+    /// Get the fill color of the shape.
+    #[must_use]
+    pub fn fill_color(&self) -> Color {
+        self.fill_color
+    }
+
+    /// Set the outline color of the shape. The default is opaque white.
+    pub fn set_outline_color(&mut self, color: Color) {
+        self.outline_color = color;
+        for vertex in &mut self.outline_vertices {
+            vertex.color = color;
+        }
+    }
+
+    /// Get the outline color of the shape.
+    #[must_use]
+    pub fn outline_color(&self) -> Color {
+        self.outline_color
+    }
+
+    /// Set the thickness of the outline. The default is 0, which means no outline.
+    pub fn set_outline_thickness(&mut self, thickness: f32) {
+        self.outline_thickness = thickness;
+        self.update_outline();
+    }
+
+    /// Get the thickness of the outline.
+    #[must_use]
+    pub fn outline_thickness(&self) -> f32 {
+        self.outline_thickness
+    }
+
+    /// Set how the segments of the outline are joined.
+    pub fn set_outline_join(&mut self, join: LineJoin) {
+        self.join = join;
+        self.update_outline();
+    }
+
+    /// Get how the segments of the outline are joined.
+    #[must_use]
+    pub fn outline_join(&self) -> LineJoin {
+        self.join
+    }
+
+    /// Set how the ends of open contours are drawn.
+    pub fn set_outline_cap(&mut self, cap: LineCap) {
+        self.cap = cap;
+        self.update_outline();
+    }
+
+    /// Get how the ends of open contours are drawn.
+    #[must_use]
+    pub fn outline_cap(&self) -> LineCap {
+        self.cap
+    }
+
+    /// Get the local bounding rectangle of the shape, including the outline.
+    #[must_use]
+    pub fn local_bounds(&self) -> FloatRect {
+        if self.outline_vertices.is_empty() {
+            vertex_array_bounds(&self.fill_vertices)
+        } else {
+            let fill = self.path.bounds();
+            let outline = vertex_array_bounds(&self.outline_vertices);
+            let min = Vector2f::new(
+                fill.position.x.min(outline.position.x),
+                fill.position.y.min(outline.position.y),
+            );
+            let max = Vector2f::new(
+                (fill.position.x + fill.size.x).max(outline.position.x + outline.size.x),
+                (fill.position.y + fill.size.y).max(outline.position.y + outline.size.y),
+            );
+            FloatRect::new(min, max - min)
+        }
+    }
+
+    /// Get the global bounding rectangle of the shape, including the outline.
+    #[must_use]
+    pub fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(self.local_bounds())
+    }
+
+    fn update_fill(&mut self) {
+        self.fill_vertices = self.path.fill(self.fill_rule, self.fill_color);
+    }
+
+    fn update_outline(&mut self) {
+        self.outline_vertices = if self.outline_thickness > 0. {
+            self.path.outline(
+                self.outline_thickness,
+                self.join,
+                self.cap,
+                self.outline_color,
+            )
+        } else {
+            Vec::new()
+        };
+    }
+}
+
+impl_transformable_via!(PathShape, transformation);
+
+impl Drawable for PathShape {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        let states = RenderStates {
+            transform: states.transform * *self.transform(),
+            ..*states
+        };
+        target.draw_primitives(&self.fill_vertices, PrimitiveType::TRIANGLES, &states);
+        if !self.outline_vertices.is_empty() {
+            target.draw_primitives(&self.outline_vertices, PrimitiveType::TRIANGLES, &states);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(triangles: &[Vertex]) -> f32 {
+        triangles
+            .chunks(3)
+            .map(|t| {
+                let (a, b, c) = (t[0].position, t[1].position, t[2].position);
+                (b - a).cross(c - a).abs() / 2.
+            })
+            .sum()
+    }
+
+    fn square(path: &mut Path, min: f32, max: f32, clockwise: bool) {
+        let _ = path.move_to((min, min));
+        if clockwise {
+            let _ = path
+                .line_to((max, min))
+                .line_to((max, max))
+                .line_to((min, max));
+        } else {
+            let _ = path
+                .line_to((min, max))
+                .line_to((max, max))
+                .line_to((max, min));
+        }
+        let _ = path.close();
+    }
+
+    #[test]
+    fn fill_rules_with_holes() {
+        let mut path = Path::new();
+        square(&mut path, 0., 10., true);
+        square(&mut path, 2., 8., true);
+        assert!((area(&path.fill(FillRule::EvenOdd, Color::WHITE)) - 64.).abs() < 1e-3);
+        assert!((area(&path.fill(FillRule::NonZero, Color::WHITE)) - 100.).abs() < 1e-3);
+
+        let mut path = Path::new();
+        square(&mut path, 0., 10., true);
+        square(&mut path, 2., 8., false);
+        assert!((area(&path.fill(FillRule::NonZero, Color::WHITE)) - 64.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn self_intersecting_bowtie() {
+        let mut path = Path::new();
+        let _ = path
+            .move_to((0., 0.))
+            .line_to((10., 10.))
+            .line_to((10., 0.))
+            .line_to((0., 10.))
+            .close();
+        // Two triangles of base 10 and height 5
+        assert!((area(&path.fill(FillRule::EvenOdd, Color::WHITE)) - 50.).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,167 @@
+use {
+    crate::{
+        graphics::{CustomShape, Shape, Texture, custom_shape::impl_shape_via},
+        system::{Angle, Vector2f},
+    },
+    std::f32::consts::TAU,
+};
+
+/// Specialized shape representing a pie slice: a sector of a circle.
+///
+/// The slice starts at the start angle and goes on for the sweep angle. Angles are measured
+/// from the positive X axis, and positive angles turn clockwise on screen. A sweep of 360°
+/// or more gives a full circle.
+///
+/// The local origin of the slice is the top-left corner of the bounding box of its circle,
+/// like for [`CircleShape`], so the tip of the slice is at `(radius, radius)`.
+/// For an arc with a hole in the middle, use [`RingShape`].
+///
+/// [`CircleShape`]: crate::graphics::CircleShape
+/// [`RingShape`]: crate::graphics::RingShape
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # use sfml::system::Angle;
+/// # let mut window: RenderWindow = unimplemented!();
+/// // A pie chart slice for 30%
+/// let mut slice = PieShape::new(80., Angle::degrees(-90.), Angle::degrees(0.3 * 360.));
+/// slice.set_fill_color(Color::CYAN);
+/// window.draw(&slice);
+/// ```
+#[derive(Debug)]
+pub struct PieShape<'s> {
+    shape: CustomShape<'s>,
+    radius: f32,
+    start: Angle,
+    sweep: Angle,
+    circle_point_count: usize,
+}
+
+/// Creation
+impl<'s> PieShape<'s> {
+    /// Create a pie slice of a circle of the given radius.
+    ///
+    /// The arc of a full circle would be approximated with 30 points.
+    #[must_use]
+    pub fn new(radius: f32, start: Angle, sweep: Angle) -> Self {
+        let mut shape = Self {
+            shape: CustomShape::new(Box::new(Vec::new())),
+            radius,
+            start,
+            sweep,
+            circle_point_count: 30,
+        };
+        shape.update();
+        shape
+    }
+
+    /// Create a pie slice with a texture.
+    #[must_use]
+    pub fn with_texture(radius: f32, start: Angle, sweep: Angle, texture: &'s Texture) -> Self {
+        let mut shape = Self::new(radius, start, sweep);
+        shape.set_texture(texture, true);
+        shape
+    }
+}
+
+/// Properties
+impl PieShape<'_> {
+    /// Set the radius of the circle.
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius;
+        self.update();
+    }
+
+    /// Get the radius of the circle.
+    #[must_use]
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Set the angle at which the slice starts.
+    pub fn set_start_angle(&mut self, start: Angle) {
+        self.start = start;
+        self.update();
+    }
+
+    /// Get the angle at which the slice starts.
+    #[must_use]
+    pub fn start_angle(&self) -> Angle {
+        self.start
+    }
+
+    /// Set the angle covered by the slice.
+    pub fn set_sweep_angle(&mut self, sweep: Angle) {
+        self.sweep = sweep;
+        self.update();
+    }
+
+    /// Get the angle covered by the slice.
+    #[must_use]
+    pub fn sweep_angle(&self) -> Angle {
+        self.sweep
+    }
+
+    /// Set the number of points that would approximate the arc of a full circle.
+    ///
+    /// The arc of the slice uses a proportional number of points, and at least 2.
+    pub fn set_circle_point_count(&mut self, count: usize) {
+        self.circle_point_count = count;
+        self.update();
+    }
+
+    /// Get the number of points that would approximate the arc of a full circle.
+    #[must_use]
+    pub fn circle_point_count(&self) -> usize {
+        self.circle_point_count
+    }
+
+    fn update(&mut self) {
+        let points = points(self.radius, self.start, self.sweep, self.circle_point_count);
+        self.shape.set_points(Box::new(points));
+    }
+}
+
+impl_shape_via!(PieShape, shape);
+
+fn points(radius: f32, start: Angle, sweep: Angle, circle_point_count: usize) -> Vec<Vector2f> {
+    let center = Vector2f::new(radius, radius);
+    let start = start.as_radians();
+    let sweep = sweep.as_radians().clamp(-TAU, TAU);
+    let full = sweep.abs() >= TAU;
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let steps = ((circle_point_count as f32 * sweep.abs() / TAU).ceil() as usize).max(1);
+    // A full circle doesn't repeat its first point, and has no tip
+    let arc_points = if full { steps } else { steps + 1 };
+    let mut points = Vec::with_capacity(arc_points + 1);
+    if !full {
+        points.push(center);
+    }
+    points.extend((0..arc_points).map(|i| {
+        let (sin, cos) = (start + sweep * i as f32 / steps as f32).sin_cos();
+        center + Vector2f::new(cos, sin) * radius
+    }));
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_counts() {
+        let full = points(10., Angle::default(), Angle::degrees(360.), 30);
+        assert_eq!(full.len(), 30);
+        // A quarter of 30 points is rounded up to 8 steps, plus the tip
+        let quarter = points(10., Angle::default(), Angle::degrees(90.), 30);
+        assert_eq!(quarter.len(), 10);
+        let center = Vector2f::new(10., 10.);
+        assert_eq!(quarter[0], center);
+        assert!((quarter[1] - Vector2f::new(20., 10.)).length() < 1e-4);
+        assert!((quarter[9] - Vector2f::new(10., 20.)).length() < 1e-4);
+        let backwards = points(10., Angle::default(), Angle::degrees(-90.), 30);
+        assert!((backwards[9] - Vector2f::new(10., 0.)).length() < 1e-4);
+    }
+}
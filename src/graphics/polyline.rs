@@ -0,0 +1,512 @@
+use {
+    crate::{
+        graphics::{
+            Color, Drawable, FloatRect, PrimitiveType, RenderStates, RenderTarget, Transformable,
+            Vertex,
+            transformable::{Transformation, impl_transformable_via},
+            vertex_array_bounds,
+        },
+        system::Vector2f,
+    },
+    std::f32::consts::PI,
+};
+
+/// How two segments of a [`Polyline`] are joined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet.
+    ///
+    /// If they meet too far away, see [`Polyline::set_miter_limit`], a bevel join is used.
+    #[default]
+    Miter,
+    /// The corner is rounded
+    Round,
+    /// The corner is cut off
+    Bevel,
+}
+
+/// How the ends of an open [`Polyline`] are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    /// The line stops at its end points
+    #[default]
+    Butt,
+    /// The line ends with half circles
+    Round,
+    /// The line is extended by half its width past its end points
+    Square,
+}
+
+/// Parameters of a stroke, shared by [`Polyline`] and path outlines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Stroke {
+    pub(super) width: f32,
+    pub(super) join: LineJoin,
+    pub(super) cap: LineCap,
+    pub(super) miter_limit: f32,
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        Self {
+            width: 1.,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            miter_limit: 4.,
+        }
+    }
+}
+
+/// A line with a width, going through a list of points.
+///
+/// The line is triangulated into a vertex array and drawn with a single call to
+/// [`RenderTarget::draw_primitives`]. Segments and joins overlap slightly, which is not
+/// visible with opaque colors, but is with translucent ones. To draw a translucent line,
+/// draw an opaque one into a [`RenderTexture`], and draw that texture translucently.
+///
+/// [`RenderTexture`]: crate::graphics::RenderTexture
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut line = Polyline::from_points([(10., 10.), (100., 50.), (20., 120.)]);
+/// line.set_width(8.);
+/// line.set_join(LineJoin::Round);
+/// line.set_cap(LineCap::Square);
+/// line.set_color(Color::GREEN);
+/// window.draw(&line);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Polyline {
+    points: Vec<Vector2f>,
+    stroke: Stroke,
+    closed: bool,
+    color: Color,
+    transformation: Transformation,
+    vertices: Vec<Vertex>,
+}
+
+impl Default for Polyline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creation
+impl Polyline {
+    /// Create an empty polyline, 1 pixel wide and white.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            points: Vec::new(),
+            stroke: Stroke::default(),
+            closed: false,
+            color: Color::WHITE,
+            transformation: Transformation::default(),
+            vertices: Vec::new(),
+        }
+    }
+
+    /// Create a polyline going through `points`, 1 pixel wide and white.
+    #[must_use]
+    pub fn from_points<P: Into<Vector2f>>(points: impl IntoIterator<Item = P>) -> Self {
+        let mut line = Self::new();
+        line.set_points(points);
+        line
+    }
+}
+
+/// Points
+impl Polyline {
+    /// Replace the points of the line.
+    pub fn set_points<P: Into<Vector2f>>(&mut self, points: impl IntoIterator<Item = P>) {
+        self.points.clear();
+        self.points.extend(points.into_iter().map(Into::into));
+        self.update();
+    }
+
+    /// Add a point at the end of the line.
+    pub fn push_point<P: Into<Vector2f>>(&mut self, point: P) {
+        self.points.push(point.into());
+        self.update();
+    }
+
+    /// Get the points of the line.
+    #[must_use]
+    pub fn points(&self) -> &[Vector2f] {
+        &self.points
+    }
+
+    /// Remove all the points of the line.
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.update();
+    }
+}
+
+/// Style
+impl Polyline {
+    /// Set the width of the line. The default width is 1.
+    pub fn set_width(&mut self, width: f32) {
+        self.stroke.width = width;
+        self.update();
+    }
+
+    /// Get the width of the line.
+    #[must_use]
+    pub fn width(&self) -> f32 {
+        self.stroke.width
+    }
+
+    /// Set how segments are joined. The default is [`LineJoin::Miter`].
+    pub fn set_join(&mut self, join: LineJoin) {
+        self.stroke.join = join;
+        self.update();
+    }
+
+    /// Get how segments are joined.
+    #[must_use]
+    pub fn join(&self) -> LineJoin {
+        self.stroke.join
+    }
+
+    /// Set how the ends of the line are drawn. The default is [`LineCap::Butt`].
+    ///
+    /// Closed lines have no caps.
+    pub fn set_cap(&mut self, cap: LineCap) {
+        self.stroke.cap = cap;
+        self.update();
+    }
+
+    /// Get how the ends of the line are drawn.
+    #[must_use]
+    pub fn cap(&self) -> LineCap {
+        self.stroke.cap
+    }
+
+    /// Set the maximum ratio between the length of a miter join and the half width of
+    /// the line. Sharper corners use a bevel join. The default limit is 4.
+    pub fn set_miter_limit(&mut self, limit: f32) {
+        self.stroke.miter_limit = limit;
+        self.update();
+    }
+
+    /// Get the miter limit.
+    #[must_use]
+    pub fn miter_limit(&self) -> f32 {
+        self.stroke.miter_limit
+    }
+
+    /// Set whether the last point is connected back to the first one.
+    pub fn set_closed(&mut self, closed: bool) {
+        self.closed = closed;
+        self.update();
+    }
+
+    /// Tell whether the last point is connected back to the first one.
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Set the color of the line. The default color is opaque white.
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+        for vertex in &mut self.vertices {
+            vertex.color = color;
+        }
+    }
+
+    /// Get the color of the line.
+    #[must_use]
+    pub fn color(&self) -> Color {
+        self.color
+    }
+}
+
+/// Geometry
+impl Polyline {
+    /// Get the triangles of the line, in local coordinates.
+    #[must_use]
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    /// Get the local bounding rectangle of the line.
+    #[must_use]
+    pub fn local_bounds(&self) -> FloatRect {
+        vertex_array_bounds(&self.vertices)
+    }
+
+    /// Get the global bounding rectangle of the line.
+    #[must_use]
+    pub fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(self.local_bounds())
+    }
+
+    fn update(&mut self) {
+        self.vertices.clear();
+        stroke(
+            &self.points,
+            self.closed,
+            &self.stroke,
+            self.color,
+            &mut self.vertices,
+        );
+    }
+}
+
+impl_transformable_via!(Polyline, transformation);
+
+impl Drawable for Polyline {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        let states = RenderStates {
+            transform: states.transform * *self.transform(),
+            ..*states
+        };
+        target.draw_primitives(&self.vertices, PrimitiveType::TRIANGLES, &states);
+    }
+}
+
+/// Triangulate the stroke of `points` into `out`.
+pub(super) fn stroke(
+    points: &[Vector2f],
+    closed: bool,
+    stroke: &Stroke,
+    color: Color,
+    out: &mut Vec<Vertex>,
+) {
+    let half_width = stroke.width / 2.;
+    if half_width <= 0. {
+        return;
+    }
+    let mut points = points.to_vec();
+    points.dedup_by(|a, b| (*a - *b).length_sq() < 1e-10);
+    if closed && points.len() > 1 && (points[0] - points[points.len() - 1]).length_sq() < 1e-10 {
+        let _ = points.pop();
+    }
+    let mut triangle = |a: Vector2f, b: Vector2f, c: Vector2f| {
+        out.extend([a, b, c].map(|p| Vertex::with_pos_color(p, color)));
+    };
+
+    match points[..] {
+        [] => return,
+        [point] => {
+            let offset = Vector2f::new(half_width, 0.);
+            match stroke.cap {
+                LineCap::Butt => {}
+                LineCap::Round => fan(&mut triangle, point, offset, 2. * PI),
+                LineCap::Square => {
+                    let (x, y) = (Vector2f::new(half_width, 0.), Vector2f::new(0., half_width));
+                    triangle(point - x - y, point + x - y, point - x + y);
+                    triangle(point - x + y, point + x - y, point + x + y);
+                }
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let count = points.len();
+    let segments = if closed { count } else { count - 1 };
+    let directions: Vec<Vector2f> = (0..segments)
+        .map(|i| (points[(i + 1) % count] - points[i]).normalized())
+        .collect();
+
+    // Segment bodies
+    for (i, &direction) in directions.iter().enumerate() {
+        let mut start = points[i];
+        let mut end = points[(i + 1) % count];
+        if !closed && stroke.cap == LineCap::Square {
+            if i == 0 {
+                start -= direction * half_width;
+            }
+            if i == segments - 1 {
+                end += direction * half_width;
+            }
+        }
+        let normal = direction.perpendicular() * half_width;
+        triangle(start + normal, end + normal, start - normal);
+        triangle(start - normal, end + normal, end - normal);
+    }
+
+    // Joins
+    let joints = if closed { 0..count } else { 1..count - 1 };
+    for i in joints {
+        let before = directions[(i + segments - 1) % segments];
+        let after = directions[i];
+        let point = points[i];
+        let cross = before.cross(after);
+        if cross.abs() < 1e-6 && before.dot(after) > 0. {
+            continue;
+        }
+        // The gap between the segments opens on the outer side of the turn
+        let side = if cross > 0. { -half_width } else { half_width };
+        let outer_before = before.perpendicular() * side;
+        let outer_after = after.perpendicular() * side;
+        match stroke.join {
+            LineJoin::Bevel => triangle(point, point + outer_before, point + outer_after),
+            LineJoin::Miter => {
+                let cos_half = ((1. + before.dot(after)) / 2.).max(0.).sqrt();
+                if cos_half * stroke.miter_limit < 1. {
+                    triangle(point, point + outer_before, point + outer_after);
+                } else {
+                    let miter =
+                        point + (outer_before + outer_after).normalized() * (half_width / cos_half);
+                    triangle(point, point + outer_before, miter);
+                    triangle(point, miter, point + outer_after);
+                }
+            }
+            LineJoin::Round => {
+                let angle = outer_before
+                    .cross(outer_after)
+                    .atan2(outer_before.dot(outer_after));
+                fan(&mut triangle, point, outer_before, angle);
+            }
+        }
+    }
+
+    // Caps
+    if !closed && stroke.cap == LineCap::Round {
+        let first = directions[0].perpendicular() * half_width;
+        fan(&mut triangle, points[0], first, PI);
+        let last = directions[segments - 1].perpendicular() * half_width;
+        fan(&mut triangle, points[count - 1], last, -PI);
+    }
+}
+
+/// Add a circular fan around `center`, sweeping `start` (relative to the center) by `angle`
+/// radians.
+fn fan(
+    triangle: &mut impl FnMut(Vector2f, Vector2f, Vector2f),
+    center: Vector2f,
+    start: Vector2f,
+    angle: f32,
+) {
+    // The chord must not deviate from the arc by more than a quarter of a pixel
+    let radius = start.length();
+    let max_step = 2. * (1. - 0.25 / radius).clamp(-1., 1.).acos();
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let steps = (angle.abs() / max_step.max(0.01)).ceil().max(1.) as u32;
+    let (sin, cos) = (angle / steps as f32).sin_cos();
+    let mut current = start;
+    for _ in 0..steps {
+        let next = Vector2f::new(
+            current.x * cos - current.y * sin,
+            current.x * sin + current.y * cos,
+        );
+        triangle(center, center + current, center + next);
+        current = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A right-angle corner at `(10, 0)`, 2 pixels wide, turning down
+    fn corner(join: LineJoin) -> Polyline {
+        let mut line = Polyline::from_points([(0., 0.), (10., 0.), (10., 10.)]);
+        line.set_width(2.);
+        line.set_join(join);
+        line
+    }
+
+    /// The vertices after the two segment bodies
+    fn join_vertices(line: &Polyline) -> Vec<Vector2f> {
+        line.vertices()[12..].iter().map(|v| v.position).collect()
+    }
+
+    fn close(a: Vector2f, b: Vector2f) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    #[test]
+    fn bevel_join() {
+        let line = corner(LineJoin::Bevel);
+        let join = join_vertices(&line);
+        let expected = [(10., 0.), (10., -1.), (11., 0.)].map(Vector2f::from);
+        assert_eq!(join.len(), 3);
+        assert!(join.iter().zip(expected).all(|(&a, b)| close(a, b)));
+    }
+
+    #[test]
+    fn miter_join_and_limit() {
+        let mut line = corner(LineJoin::Miter);
+        let join = join_vertices(&line);
+        assert_eq!(join.len(), 6);
+        assert!(join.iter().any(|&p| close(p, Vector2f::new(11., -1.))));
+        // A right angle miter is sqrt(2) half widths long
+        line.set_miter_limit(1.4);
+        let join = join_vertices(&line);
+        assert_eq!(join.len(), 3);
+        assert!(!join.iter().any(|&p| close(p, Vector2f::new(11., -1.))));
+        line.set_miter_limit(1.5);
+        assert_eq!(join_vertices(&line).len(), 6);
+    }
+
+    #[test]
+    fn round_join() {
+        let join = join_vertices(&corner(LineJoin::Round));
+        assert!(!join.is_empty() && join.len() % 3 == 0);
+        let center = Vector2f::new(10., 0.);
+        for triangle in join.chunks(3) {
+            assert!(close(triangle[0], center));
+            assert!((triangle[1] - center).length() - 1. < 1e-4);
+            assert!((triangle[2] - center).length() - 1. < 1e-4);
+        }
+        // The fan sweeps the outer side, from the top to the right of the corner
+        assert!(close(join[1], Vector2f::new(10., -1.)));
+        assert!(close(join[join.len() - 1], Vector2f::new(11., 0.)));
+    }
+
+    #[test]
+    fn caps() {
+        let mut line = Polyline::from_points([(0., 0.), (10., 0.)]);
+        line.set_width(2.);
+        let bounds = |line: &Polyline| {
+            let bounds = line.local_bounds();
+            (
+                bounds.position.x,
+                bounds.size.x,
+                bounds.position.y,
+                bounds.size.y,
+            )
+        };
+        assert_eq!(line.vertices().len(), 6);
+        assert_eq!(bounds(&line), (0., 10., -1., 2.));
+        line.set_cap(LineCap::Square);
+        assert_eq!(line.vertices().len(), 6);
+        assert_eq!(bounds(&line), (-1., 12., -1., 2.));
+        line.set_cap(LineCap::Round);
+        assert!(line.vertices().len() > 6);
+        // The arcs stay within a quarter of a pixel of the circle
+        let (x, width, ..) = bounds(&line);
+        assert!((-1.0..=-0.75).contains(&x), "{x}");
+        assert!((11.5..=12.).contains(&width), "{width}");
+    }
+
+    #[test]
+    fn closed_loop() {
+        let mut line = Polyline::from_points([(0., 0.), (10., 0.), (10., 10.), (0., 10.)]);
+        line.set_join(LineJoin::Bevel);
+        line.set_cap(LineCap::Square);
+        line.set_closed(true);
+        // Four segments and four joins, without caps
+        assert_eq!(line.vertices().len(), 4 * 6 + 4 * 3);
+        let positions = |line: &Polyline| -> Vec<Vector2f> {
+            line.vertices().iter().map(|v| v.position).collect()
+        };
+        let without_repeated_start = positions(&line);
+        line.push_point((0., 0.));
+        assert_eq!(positions(&line), without_repeated_start);
+        line.set_closed(false);
+        assert_eq!(line.vertices().len(), 4 * 6 + 3 * 3);
+    }
+}
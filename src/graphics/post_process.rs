@@ -0,0 +1,562 @@
+//! Full-screen post-processing effects.
+//!
+//! A [`PostProcessChain`] renders the scene into a [`RenderTexture`], then applies a list of
+//! [`PostEffect`]s to it, ping-ponging between two render textures: each effect reads the
+//! result of the previous one, and writes into the other texture. The result is finally
+//! drawn over a whole render target.
+//!
+//! The module ships a few effects: [`Blur`], [`Bloom`], [`Crt`], [`ColorGrading`] and
+//! [`Vignette`]. Other single-pass effects can be written as a fragment shader and wrapped
+//! in a [`ShaderPass`], and multi-pass ones by implementing [`PostEffect`].
+//!
+//! The effects are owned by the caller and passed to [`PostProcessChain::present`], so
+//! their settings can be changed between frames, and effects can be toggled by leaving them
+//! out of the list.
+//!
+//! # Usage example
+//!
+//! ```no_run
+//! use sfml::graphics::{
+//!     Color, RenderTarget, RenderWindow,
+//!     post_process::{Bloom, PostProcessChain, Vignette},
+//! };
+//! # let mut window: RenderWindow = unimplemented!();
+//! # let sprite: sfml::graphics::Sprite = unimplemented!();
+//!
+//! let mut chain = PostProcessChain::new(window.size()).unwrap();
+//! let mut bloom = Bloom::new().unwrap();
+//! bloom.threshold = 0.7;
+//! let mut vignette = Vignette::new().unwrap();
+//!
+//! // In the main loop
+//! chain.scene().clear(Color::BLACK);
+//! chain.scene().draw(&sprite);
+//! chain
+//!     .present(&mut [&mut bloom, &mut vignette], &mut window)
+//!     .unwrap();
+//! window.display();
+//! ```
+
+use crate::{
+    SfResult,
+    cpp::FBox,
+    graphics::{
+        BlendMode, Color, FloatRect, RenderStates, RenderTarget, RenderTexture, Shader, ShaderType,
+        Sprite, Texture, Transformable, View, glsl,
+    },
+    system::{Vector2f, Vector2u},
+    window::ContextSettings,
+};
+
+/// Source of the separable Gaussian blur fragment shader.
+///
+/// It blurs along one axis. The uniforms are:
+///
+/// * `texture`: the source texture
+/// * `direction`: the offset between two samples, in texture coordinates
+pub const BLUR_FRAGMENT_SHADER: &str = r"uniform sampler2D texture;
+uniform vec2 direction;
+
+void main()
+{
+    vec2 uv = gl_TexCoord[0].xy;
+    vec4 color = texture2D(texture, uv) * 0.2270270270;
+    color += texture2D(texture, uv + direction * 1.3846153846) * 0.3162162162;
+    color += texture2D(texture, uv - direction * 1.3846153846) * 0.3162162162;
+    color += texture2D(texture, uv + direction * 3.2307692308) * 0.0702702703;
+    color += texture2D(texture, uv - direction * 3.2307692308) * 0.0702702703;
+    gl_FragColor = color;
+}
+";
+
+/// Source of the fragment shader that keeps the bright parts of an image, for [`Bloom`].
+///
+/// The uniforms are:
+///
+/// * `texture`: the source texture
+/// * `threshold`: the luminance under which pixels become black
+pub const BRIGHT_PASS_FRAGMENT_SHADER: &str = r"uniform sampler2D texture;
+uniform float threshold;
+
+void main()
+{
+    vec4 color = texture2D(texture, gl_TexCoord[0].xy);
+    float luminance = dot(color.rgb, vec3(0.2126, 0.7152, 0.0722));
+    float factor = max(luminance - threshold, 0.0) / max(luminance, 0.0001);
+    gl_FragColor = vec4(color.rgb * factor, 1.0);
+}
+";
+
+/// Source of the fragment shader that scales the colors of an image, for [`Bloom`].
+///
+/// The uniforms are:
+///
+/// * `texture`: the source texture
+/// * `intensity`: the factor applied to the colors
+pub const INTENSITY_FRAGMENT_SHADER: &str = r"uniform sampler2D texture;
+uniform float intensity;
+
+void main()
+{
+    gl_FragColor = vec4(texture2D(texture, gl_TexCoord[0].xy).rgb * intensity, 1.0);
+}
+";
+
+/// Source of the CRT monitor fragment shader.
+///
+/// The uniforms are:
+///
+/// * `texture`: the source texture
+/// * `resolution`: the size of the source texture, in pixels
+/// * `curvature`: how much the screen bulges
+/// * `scanline_intensity`: how dark the scanlines are, between 0 and 1
+/// * `aberration`: the distance between the red and blue channels, in pixels
+pub const CRT_FRAGMENT_SHADER: &str = r"uniform sampler2D texture;
+uniform vec2 resolution;
+uniform float curvature;
+uniform float scanline_intensity;
+uniform float aberration;
+
+void main()
+{
+    vec2 centered = gl_TexCoord[0].xy * 2.0 - 1.0;
+    centered *= 1.0 + curvature * dot(centered, centered);
+    vec2 uv = centered * 0.5 + 0.5;
+    if (uv.x < 0.0 || uv.x > 1.0 || uv.y < 0.0 || uv.y > 1.0)
+    {
+        gl_FragColor = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+    vec2 offset = vec2(aberration / resolution.x, 0.0);
+    vec4 color = texture2D(texture, uv);
+    color.r = texture2D(texture, uv + offset).r;
+    color.b = texture2D(texture, uv - offset).b;
+    float scanline = sin(uv.y * resolution.y * 3.14159265) * 0.5 + 0.5;
+    color.rgb *= 1.0 - scanline_intensity * scanline;
+    gl_FragColor = color;
+}
+";
+
+/// Source of the color grading fragment shader.
+///
+/// The uniforms are:
+///
+/// * `texture`: the source texture
+/// * `brightness`: added to the colors
+/// * `contrast`: factor applied to the distance of the colors from middle gray
+/// * `saturation`: 0 for grayscale, 1 for the original colors, more for vivid colors
+/// * `tint`: multiplied with the colors
+pub const COLOR_GRADING_FRAGMENT_SHADER: &str = r"uniform sampler2D texture;
+uniform float brightness;
+uniform float contrast;
+uniform float saturation;
+uniform vec4 tint;
+
+void main()
+{
+    vec4 color = texture2D(texture, gl_TexCoord[0].xy);
+    vec3 rgb = (color.rgb + brightness - 0.5) * contrast + 0.5;
+    float luminance = dot(rgb, vec3(0.2126, 0.7152, 0.0722));
+    rgb = mix(vec3(luminance), rgb, saturation);
+    gl_FragColor = vec4(clamp(rgb * tint.rgb, 0.0, 1.0), color.a);
+}
+";
+
+/// Source of the vignette fragment shader.
+///
+/// The uniforms are:
+///
+/// * `texture`: the source texture
+/// * `radius`: the distance from the center where the vignette starts, 1 being a corner
+/// * `softness`: the distance over which the vignette fades in
+/// * `color`: the color of the vignette, whose alpha is the strength of the effect
+pub const VIGNETTE_FRAGMENT_SHADER: &str = r"uniform sampler2D texture;
+uniform float radius;
+uniform float softness;
+uniform vec4 color;
+
+void main()
+{
+    vec4 pixel = texture2D(texture, gl_TexCoord[0].xy);
+    float distance = length(gl_TexCoord[0].xy - 0.5) * 1.41421356;
+    float amount = smoothstep(radius, radius + softness, distance) * color.a;
+    gl_FragColor = vec4(mix(pixel.rgb, color.rgb, amount), pixel.a);
+}
+";
+
+/// An effect of a [`PostProcessChain`].
+pub trait PostEffect {
+    /// Apply the effect to `source`, and draw the result into `target`.
+    ///
+    /// `target` is the same size as `source`, and its content must be entirely replaced.
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) -> SfResult<()>;
+}
+
+/// Renders a scene into a texture, and applies [`PostEffect`]s to it.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug)]
+pub struct PostProcessChain {
+    buffers: [FBox<RenderTexture>; 2],
+}
+
+impl PostProcessChain {
+    /// Create a chain for a target of `size` pixels.
+    pub fn new(size: Vector2u) -> SfResult<Self> {
+        Ok(Self {
+            buffers: [RenderTexture::new(size)?, RenderTexture::new(size)?],
+        })
+    }
+
+    /// Resize the textures of the chain, when the target is resized.
+    ///
+    /// The content of the scene is lost.
+    pub fn resize(&mut self, size: Vector2u) -> SfResult<()> {
+        for buffer in &mut self.buffers {
+            buffer.resize(size, &ContextSettings::default())?;
+        }
+        Ok(())
+    }
+
+    /// Get the size of the textures of the chain.
+    #[must_use]
+    pub fn size(&self) -> Vector2u {
+        self.buffers[0].size()
+    }
+
+    /// Enable or disable the smooth filter of the textures of the chain.
+    ///
+    /// This matters when the result is presented on a target of a different size.
+    pub fn set_smooth(&mut self, smooth: bool) {
+        for buffer in &mut self.buffers {
+            buffer.set_smooth(smooth);
+        }
+    }
+
+    /// Get the render texture to draw the scene into.
+    ///
+    /// Clear it at the beginning of each frame, like a window.
+    pub fn scene(&mut self) -> &mut RenderTexture {
+        &mut self.buffers[0]
+    }
+
+    /// Apply `effects` in order to the scene, and draw the result over the whole `target`.
+    ///
+    /// The view of `target` is restored afterwards. With no effects, the scene is drawn as is.
+    pub fn present(
+        &mut self,
+        effects: &mut [&mut dyn PostEffect],
+        target: &mut dyn RenderTarget,
+    ) -> SfResult<()> {
+        self.buffers[0].display();
+        let mut current = 0;
+        for effect in effects {
+            let [first, second] = &mut self.buffers;
+            let (source, destination) = if current == 0 {
+                (first, second)
+            } else {
+                (second, first)
+            };
+            effect.apply(source.texture(), destination)?;
+            destination.display();
+            current = 1 - current;
+        }
+
+        let result = self.buffers[current].texture();
+        let size = result.size().as_other::<f32>();
+        let previous_view = target.view().to_owned();
+        target.set_view(&View::from_rect(FloatRect::new(Vector2f::default(), size)));
+        target.draw(&Sprite::with_texture(result));
+        target.set_view(&previous_view);
+        Ok(())
+    }
+}
+
+/// A single-pass effect made of a fragment shader.
+///
+/// The shader reads the source from its `texture` uniform.
+#[derive(Debug)]
+pub struct ShaderPass {
+    shader: FBox<Shader<'static>>,
+}
+
+impl ShaderPass {
+    /// Wrap `shader`, which must have a `uniform sampler2D texture`.
+    pub fn new(mut shader: FBox<Shader<'static>>) -> SfResult<Self> {
+        shader.set_uniform_current_texture("texture")?;
+        Ok(Self { shader })
+    }
+
+    /// Compile a fragment shader, which must have a `uniform sampler2D texture`.
+    pub fn from_fragment_source(source: &str) -> SfResult<Self> {
+        Self::new(Shader::from_memory(source, ShaderType::Fragment)?)
+    }
+
+    /// Get the shader, to set its uniforms.
+    pub fn shader_mut(&mut self) -> &mut Shader<'static> {
+        &mut self.shader
+    }
+
+    fn draw(&self, source: &Texture, target: &mut RenderTexture, blend_mode: BlendMode) {
+        draw_fullscreen(source, target, Some(&*self.shader), blend_mode);
+    }
+}
+
+impl PostEffect for ShaderPass {
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) -> SfResult<()> {
+        self.draw(source, target, BlendMode::NONE);
+        Ok(())
+    }
+}
+
+/// A Gaussian blur.
+#[derive(Debug)]
+pub struct Blur {
+    /// Radius of the blur, in pixels. The default radius is 8.
+    pub radius: f32,
+    pass: ShaderPass,
+    scratch: Scratch,
+}
+
+impl Blur {
+    /// Compile the blur shader.
+    pub fn new() -> SfResult<Self> {
+        Ok(Self {
+            radius: 8.,
+            pass: ShaderPass::from_fragment_source(BLUR_FRAGMENT_SHADER)?,
+            scratch: Scratch::default(),
+        })
+    }
+}
+
+impl PostEffect for Blur {
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) -> SfResult<()> {
+        // The samples of the kernel go up to about 4 steps away from the center
+        let step = self.radius / 4.;
+        let size = source.size();
+        let texel = Vector2f::new(1., 1.).cwise_div(size.as_other());
+        let horizontal = self.scratch.get(size)?;
+        self.pass
+            .shader_mut()
+            .set_uniform_vec2("direction", Vector2f::new(texel.x * step, 0.))?;
+        self.pass.draw(source, horizontal, BlendMode::NONE);
+        self.pass
+            .shader_mut()
+            .set_uniform_vec2("direction", Vector2f::new(0., texel.y * step))?;
+        self.pass
+            .draw(horizontal.texture(), target, BlendMode::NONE);
+        Ok(())
+    }
+}
+
+/// A glow around the bright parts of the image.
+///
+/// The bright parts are extracted, blurred at half resolution, and added to the image.
+#[derive(Debug)]
+pub struct Bloom {
+    /// Luminance, between 0 and 1, above which pixels glow. The default threshold is 0.8.
+    pub threshold: f32,
+    /// Factor applied to the glow. The default intensity is 1.
+    pub intensity: f32,
+    /// The blur applied to the bright parts, at half resolution.
+    pub blur: Blur,
+    bright_pass: ShaderPass,
+    intensity_pass: ShaderPass,
+    bright: Scratch,
+    blurred: Scratch,
+}
+
+impl Bloom {
+    /// Compile the bloom shaders.
+    pub fn new() -> SfResult<Self> {
+        Ok(Self {
+            threshold: 0.8,
+            intensity: 1.,
+            blur: Blur::new()?,
+            bright_pass: ShaderPass::from_fragment_source(BRIGHT_PASS_FRAGMENT_SHADER)?,
+            intensity_pass: ShaderPass::from_fragment_source(INTENSITY_FRAGMENT_SHADER)?,
+            bright: Scratch::default(),
+            blurred: Scratch::default(),
+        })
+    }
+}
+
+impl PostEffect for Bloom {
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) -> SfResult<()> {
+        let size = source.size();
+        let half_size = Vector2u::new((size.x / 2).max(1), (size.y / 2).max(1));
+        let bright = self.bright.get(half_size)?;
+        bright.set_smooth(true);
+        self.bright_pass
+            .shader_mut()
+            .set_uniform_float("threshold", self.threshold)?;
+        self.bright_pass.draw(source, bright, BlendMode::NONE);
+
+        let blurred = self.blurred.get(half_size)?;
+        blurred.set_smooth(true);
+        self.blur.apply(bright.texture(), blurred)?;
+
+        draw_fullscreen(source, target, None, BlendMode::NONE);
+        self.intensity_pass
+            .shader_mut()
+            .set_uniform_float("intensity", self.intensity)?;
+        self.intensity_pass
+            .draw(blurred.texture(), target, BlendMode::ADD);
+        Ok(())
+    }
+}
+
+/// An old CRT monitor: curved screen, scanlines and chromatic aberration.
+#[derive(Debug)]
+pub struct Crt {
+    /// How much the screen bulges. The default curvature is 0.05.
+    pub curvature: f32,
+    /// How dark the scanlines are, between 0 and 1. The default intensity is 0.25.
+    pub scanline_intensity: f32,
+    /// Distance between the red and blue channels, in pixels. The default distance is 1.
+    pub aberration: f32,
+    pass: ShaderPass,
+}
+
+impl Crt {
+    /// Compile the CRT shader.
+    pub fn new() -> SfResult<Self> {
+        Ok(Self {
+            curvature: 0.05,
+            scanline_intensity: 0.25,
+            aberration: 1.,
+            pass: ShaderPass::from_fragment_source(CRT_FRAGMENT_SHADER)?,
+        })
+    }
+}
+
+impl PostEffect for Crt {
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) -> SfResult<()> {
+        let shader = self.pass.shader_mut();
+        shader.set_uniform_vec2("resolution", source.size().as_other())?;
+        shader.set_uniform_float("curvature", self.curvature)?;
+        shader.set_uniform_float("scanline_intensity", self.scanline_intensity)?;
+        shader.set_uniform_float("aberration", self.aberration)?;
+        self.pass.apply(source, target)
+    }
+}
+
+/// Brightness, contrast, saturation and tint adjustments.
+#[derive(Debug)]
+pub struct ColorGrading {
+    /// Added to the colors, between -1 and 1. The default brightness is 0.
+    pub brightness: f32,
+    /// Factor applied to the distance of the colors from middle gray. The default contrast
+    /// is 1.
+    pub contrast: f32,
+    /// 0 for grayscale, 1 for the original colors, more for vivid colors. The default
+    /// saturation is 1.
+    pub saturation: f32,
+    /// Multiplied with the colors. The default tint is white.
+    pub tint: Color,
+    pass: ShaderPass,
+}
+
+impl ColorGrading {
+    /// Compile the color grading shader.
+    pub fn new() -> SfResult<Self> {
+        Ok(Self {
+            brightness: 0.,
+            contrast: 1.,
+            saturation: 1.,
+            tint: Color::WHITE,
+            pass: ShaderPass::from_fragment_source(COLOR_GRADING_FRAGMENT_SHADER)?,
+        })
+    }
+}
+
+impl PostEffect for ColorGrading {
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) -> SfResult<()> {
+        let shader = self.pass.shader_mut();
+        shader.set_uniform_float("brightness", self.brightness)?;
+        shader.set_uniform_float("contrast", self.contrast)?;
+        shader.set_uniform_float("saturation", self.saturation)?;
+        shader.set_uniform_vec4("tint", glsl::Vec4::from(self.tint))?;
+        self.pass.apply(source, target)
+    }
+}
+
+/// Darkened edges.
+#[derive(Debug)]
+pub struct Vignette {
+    /// Distance from the center where the vignette starts, 1 being a corner.
+    /// The default radius is 0.6.
+    pub radius: f32,
+    /// Distance over which the vignette fades in. The default softness is 0.5.
+    pub softness: f32,
+    /// Color of the vignette, whose alpha is the strength of the effect.
+    /// The default color is opaque black.
+    pub color: Color,
+    pass: ShaderPass,
+}
+
+impl Vignette {
+    /// Compile the vignette shader.
+    pub fn new() -> SfResult<Self> {
+        Ok(Self {
+            radius: 0.6,
+            softness: 0.5,
+            color: Color::BLACK,
+            pass: ShaderPass::from_fragment_source(VIGNETTE_FRAGMENT_SHADER)?,
+        })
+    }
+}
+
+impl PostEffect for Vignette {
+    fn apply(&mut self, source: &Texture, target: &mut RenderTexture) -> SfResult<()> {
+        let shader = self.pass.shader_mut();
+        shader.set_uniform_float("radius", self.radius)?;
+        shader.set_uniform_float("softness", self.softness)?;
+        shader.set_uniform_vec4("color", glsl::Vec4::from(self.color))?;
+        self.pass.apply(source, target)
+    }
+}
+
+/// A render texture created on first use, and resized when needed.
+#[derive(Debug, Default)]
+struct Scratch(Option<FBox<RenderTexture>>);
+
+impl Scratch {
+    fn get(&mut self, size: Vector2u) -> SfResult<&mut RenderTexture> {
+        match &mut self.0 {
+            Some(texture) if texture.size() == size => {}
+            Some(texture) => texture.resize(size, &ContextSettings::default())?,
+            None => self.0 = Some(RenderTexture::new(size)?),
+        }
+        Ok(self.0.as_mut().expect("Scratch texture was just created"))
+    }
+}
+
+/// Draw `source` stretched over the whole `target`.
+fn draw_fullscreen(
+    source: &Texture,
+    target: &mut RenderTexture,
+    shader: Option<&Shader>,
+    blend_mode: BlendMode,
+) {
+    let mut sprite = Sprite::with_texture(source);
+    sprite.set_scale(
+        target
+            .size()
+            .as_other::<f32>()
+            .cwise_div(source.size().as_other()),
+    );
+    let states = RenderStates {
+        blend_mode,
+        shader,
+        ..RenderStates::DEFAULT
+    };
+    // The view of the scene may have been changed by the user, and must be kept for the
+    // next frame
+    let previous_view = target.view().to_owned();
+    let view = target.default_view().to_owned();
+    target.set_view(&view);
+    target.draw_with_renderstates(&sprite, &states);
+    target.set_view(&previous_view);
+    target.display();
+}
@@ -0,0 +1,724 @@
+//! Screenshots and frame recording.
+//!
+//! [`RenderWindow::capture`] and [`RenderTexture::capture`] copy what was drawn into an
+//! [`Image`], and [`RenderWindow::save_screenshot`] saves it to a file in one call.
+//!
+//! A [`FrameRecorder`] captures every Nth frame, and hands the frames to a [`FrameSink`] on a
+//! background thread, so that encoding them doesn't slow the game down. The module ships a
+//! few sinks:
+//!
+//! - [`ImageSequence`] saves each frame to a numbered PNG file
+//! - `GifWriter` encodes an animated GIF, with the `gif` feature
+//! - `ApngWriter` encodes an animated PNG, with the `apng` feature
+//!
+//! # Usage example
+//!
+//! ```no_run
+//! use sfml::graphics::{
+//!     Color, RenderTarget, RenderWindow,
+//!     recording::{FrameRecorder, ImageSequence},
+//! };
+//! # let mut window: RenderWindow = unimplemented!();
+//!
+//! // Record every other frame
+//! let mut recorder = FrameRecorder::new(ImageSequence::new("capture", "frame"), 2);
+//! while window.is_open() {
+//!     window.clear(Color::BLACK);
+//!     // Draw the scene, then capture it before displaying it
+//!     recorder.frame(&window).unwrap();
+//!     window.display();
+//! }
+//! // Wait for the last frames to be saved
+//! recorder.finish().unwrap();
+//! ```
+
+#[cfg(any(feature = "gif", feature = "apng"))]
+use {
+    crate::system::Time,
+    std::{fs::File, io, io::BufWriter, io::Write, path::Path},
+};
+use {
+    crate::{
+        SfError, SfResult,
+        cpp::FBox,
+        graphics::{Image, RenderTarget, RenderTexture, RenderWindow, Texture},
+        system::Vector2u,
+    },
+    std::{
+        error::Error,
+        fmt,
+        path::PathBuf,
+        sync::mpsc::{self, Sender},
+        thread::{self, JoinHandle},
+    },
+};
+
+/// Screenshots
+impl RenderWindow {
+    /// Copy the content of the window into a new image.
+    ///
+    /// This copies what was drawn since the last [`display`](RenderWindow::display), so call
+    /// it after drawing the frame, and before displaying it.
+    pub fn capture(&self) -> SfResult<FBox<Image>> {
+        let mut texture = Texture::new()?;
+        if !texture.resize(RenderTarget::size(self), false) {
+            return Err(SfError::CallFailed);
+        }
+        // The texture has the size of the window
+        unsafe { texture.update_from_render_window(self, Vector2u::default()) };
+        texture.copy_to_image()
+    }
+
+    /// Save the content of the window to an image file.
+    ///
+    /// See [`RenderWindow::capture`] and [`Image::save_to_file`].
+    pub fn save_screenshot(&self, filename: &str) -> SfResult<()> {
+        self.capture()?.save_to_file(filename)
+    }
+}
+
+/// Screenshots
+impl RenderTexture {
+    /// Copy the content of the render texture into a new image.
+    ///
+    /// This copies what was drawn until the last [`display`](RenderTexture::display).
+    pub fn capture(&self) -> SfResult<FBox<Image>> {
+        self.texture().copy_to_image()
+    }
+
+    /// Save the content of the render texture to an image file.
+    ///
+    /// See [`RenderTexture::capture`] and [`Image::save_to_file`].
+    pub fn save_screenshot(&self, filename: &str) -> SfResult<()> {
+        self.capture()?.save_to_file(filename)
+    }
+}
+
+/// A render target that can be captured by a [`FrameRecorder`].
+pub trait CaptureSource {
+    /// Copy the content of the render target into a new image.
+    fn capture(&self) -> SfResult<FBox<Image>>;
+}
+
+impl CaptureSource for RenderWindow {
+    fn capture(&self) -> SfResult<FBox<Image>> {
+        RenderWindow::capture(self)
+    }
+}
+
+impl CaptureSource for RenderTexture {
+    fn capture(&self) -> SfResult<FBox<Image>> {
+        RenderTexture::capture(self)
+    }
+}
+
+/// A frame captured by a [`FrameRecorder`].
+#[derive(Debug, Clone)]
+pub struct RecordedFrame {
+    /// Index of the frame among the recorded ones, starting at 0
+    pub index: u64,
+    /// Size of the frame, in pixels
+    pub size: Vector2u,
+    /// The 4 RGBA bytes of each pixel, row by row, like [`Image::pixel_data`]
+    pub pixels: Vec<u8>,
+}
+
+/// Boxed error returned by a [`FrameSink`]
+pub type SinkError = Box<dyn Error + Send + Sync>;
+
+/// Where a [`FrameRecorder`] writes the frames it captured.
+///
+/// The methods are called on the background thread of the recorder.
+pub trait FrameSink: Send + 'static {
+    /// Write a frame.
+    ///
+    /// If this fails, the recording stops, and the error is returned by the next call to
+    /// [`FrameRecorder::frame`], or by [`FrameRecorder::finish`].
+    fn write_frame(&mut self, frame: &RecordedFrame) -> Result<(), SinkError>;
+
+    /// Finish writing, after the last frame.
+    fn finish(&mut self) -> Result<(), SinkError> {
+        Ok(())
+    }
+}
+
+/// Captures every Nth frame of a render target, and writes them to a [`FrameSink`] on a
+/// background thread.
+///
+/// The captured frames are queued for the background thread. If it can't keep up, the queue
+/// grows, so prefer recording short sequences, or capturing fewer frames.
+///
+/// Dropping the recorder waits for the queued frames to be written, like
+/// [`FrameRecorder::finish`], but ignores the errors.
+#[derive(Debug)]
+pub struct FrameRecorder {
+    interval: u32,
+    frames: u64,
+    recorded: u64,
+    sender: Option<Sender<RecordedFrame>>,
+    worker: Option<JoinHandle<Result<(), RecordError>>>,
+}
+
+impl FrameRecorder {
+    /// Create a recorder that captures one frame every `interval` frames, and writes them to
+    /// `sink`.
+    ///
+    /// An `interval` of 0 or 1 captures all the frames.
+    #[must_use]
+    pub fn new(mut sink: impl FrameSink, interval: u32) -> Self {
+        let (sender, receiver) = mpsc::channel::<RecordedFrame>();
+        let worker = thread::spawn(move || {
+            for frame in receiver {
+                sink.write_frame(&frame).map_err(RecordError::Sink)?;
+            }
+            sink.finish().map_err(RecordError::Sink)
+        });
+        Self {
+            interval: interval.max(1),
+            frames: 0,
+            recorded: 0,
+            sender: Some(sender),
+            worker: Some(worker),
+        }
+    }
+
+    /// Count a frame, and capture `source` if this frame is recorded.
+    ///
+    /// Call it once per frame, after drawing it. For a [`RenderWindow`], this must be before
+    /// displaying it.
+    ///
+    /// Fails with the error of the sink if it failed to write a previous frame, and with
+    /// [`RecordError::Stopped`] after that.
+    pub fn frame(&mut self, source: &impl CaptureSource) -> Result<(), RecordError> {
+        self.record(|| {
+            let image = source.capture()?;
+            Ok((image.size(), image.pixel_data().to_vec()))
+        })
+    }
+
+    /// Count a frame, and send the pixels returned by `capture` if this frame is recorded.
+    fn record(
+        &mut self,
+        capture: impl FnOnce() -> SfResult<(Vector2u, Vec<u8>)>,
+    ) -> Result<(), RecordError> {
+        let recorded = self.frames % u64::from(self.interval) == 0;
+        self.frames += 1;
+        if !recorded {
+            return Ok(());
+        }
+        let Some(sender) = &self.sender else {
+            return Err(RecordError::Stopped);
+        };
+        let (size, pixels) = capture().map_err(RecordError::Capture)?;
+        let frame = RecordedFrame {
+            index: self.recorded,
+            size,
+            pixels,
+        };
+        self.recorded += 1;
+        if sender.send(frame).is_err() {
+            // The background thread stopped on an error
+            self.join()?;
+            return Err(RecordError::Stopped);
+        }
+        Ok(())
+    }
+
+    /// Get the number of frames captured so far.
+    #[must_use]
+    pub fn recorded_frames(&self) -> u64 {
+        self.recorded
+    }
+
+    /// Wait for the captured frames to be written, and finish the sink.
+    pub fn finish(mut self) -> Result<(), RecordError> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Result<(), RecordError> {
+        drop(self.sender.take());
+        match self.worker.take() {
+            Some(worker) => worker.join().unwrap_or(Err(RecordError::Stopped)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for FrameRecorder {
+    fn drop(&mut self) {
+        let _ = self.join();
+    }
+}
+
+/// Saves each frame to a numbered PNG file, like `frame00042.png`.
+#[derive(Debug, Clone)]
+pub struct ImageSequence {
+    /// Directory where the files are saved. It must exist.
+    pub directory: PathBuf,
+    /// Start of the file names, before the frame index
+    pub prefix: String,
+}
+
+impl ImageSequence {
+    /// Save the frames to `directory`, in files whose names start with `prefix`.
+    #[must_use]
+    pub fn new(directory: impl Into<PathBuf>, prefix: impl Into<String>) -> Self {
+        Self {
+            directory: directory.into(),
+            prefix: prefix.into(),
+        }
+    }
+}
+
+impl FrameSink for ImageSequence {
+    fn write_frame(&mut self, frame: &RecordedFrame) -> Result<(), SinkError> {
+        let path = self
+            .directory
+            .join(format!("{}{:05}.png", self.prefix, frame.index));
+        let path = path
+            .to_str()
+            .ok_or("the path of the frame is not valid UTF-8")?;
+        Image::from_pixels(frame.size, &frame.pixels)?.save_to_file(path)?;
+        Ok(())
+    }
+}
+
+/// Check that all the frames of an animation have the size of the first one.
+#[cfg(any(feature = "gif", feature = "apng"))]
+fn check_size(expected: Vector2u, frame: &RecordedFrame) -> Result<(), SinkError> {
+    if frame.size == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "frame {} is {}x{}, but the animation is {}x{}",
+            frame.index, frame.size.x, frame.size.y, expected.x, expected.y
+        )
+        .into())
+    }
+}
+
+/// Encodes the frames into an animated GIF, that loops forever.
+///
+/// The colors of each frame are reduced to a palette of 256 colors.
+/// All the frames must have the same size, of at most 65535x65535 pixels.
+///
+/// Requires the `gif` feature.
+#[cfg(feature = "gif")]
+pub struct GifWriter<W: Write + Send + 'static> {
+    output: Option<W>,
+    encoder: Option<(Vector2u, gif::Encoder<W>)>,
+    delay: u16,
+    /// Quality of the palette, from 1 (best and slowest) to 30 (fastest)
+    pub speed: i32,
+}
+
+#[cfg(feature = "gif")]
+impl<W: Write + Send + 'static> fmt::Debug for GifWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GifWriter")
+            .field("delay", &self.delay)
+            .field("speed", &self.speed)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "gif")]
+impl GifWriter<BufWriter<File>> {
+    /// Create the GIF file `path`, whose frames last `frame_duration`.
+    pub fn create(path: impl AsRef<Path>, frame_duration: Time) -> io::Result<Self> {
+        Ok(Self::new(
+            BufWriter::new(File::create(path)?),
+            frame_duration,
+        ))
+    }
+}
+
+#[cfg(feature = "gif")]
+impl<W: Write + Send + 'static> GifWriter<W> {
+    /// Write a GIF to `output`, whose frames last `frame_duration`.
+    ///
+    /// GIF frame durations are in hundredths of a second, so `frame_duration` is rounded.
+    #[must_use]
+    pub fn new(output: W, frame_duration: Time) -> Self {
+        let centiseconds = (frame_duration.as_milliseconds() + 5) / 10;
+        Self {
+            output: Some(output),
+            encoder: None,
+            delay: u16::try_from(centiseconds.max(1)).unwrap_or(u16::MAX),
+            speed: 10,
+        }
+    }
+}
+
+#[cfg(feature = "gif")]
+impl<W: Write + Send + 'static> FrameSink for GifWriter<W> {
+    fn write_frame(&mut self, frame: &RecordedFrame) -> Result<(), SinkError> {
+        if let Some(output) = self.output.take() {
+            let width = u16::try_from(frame.size.x)?;
+            let height = u16::try_from(frame.size.y)?;
+            let mut encoder = gif::Encoder::new(output, width, height, &[])?;
+            encoder.set_repeat(gif::Repeat::Infinite)?;
+            self.encoder = Some((frame.size, encoder));
+        }
+        let Some((size, encoder)) = &mut self.encoder else {
+            return Err("the GIF is already finished".into());
+        };
+        check_size(*size, frame)?;
+        let mut pixels = frame.pixels.clone();
+        let mut gif_frame = gif::Frame::from_rgba_speed(
+            u16::try_from(size.x)?,
+            u16::try_from(size.y)?,
+            &mut pixels,
+            self.speed.clamp(1, 30),
+        );
+        gif_frame.delay = self.delay;
+        encoder.write_frame(&gif_frame)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), SinkError> {
+        if let Some((_, encoder)) = self.encoder.take() {
+            encoder.into_inner()?.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Encodes the frames into an animated PNG, that loops forever.
+///
+/// APNG files store the number of frames before the frames, so the frames are kept in memory
+/// until [`FrameRecorder::finish`], and encoded then. All the frames must have the same size.
+///
+/// Requires the `apng` feature.
+#[cfg(feature = "apng")]
+pub struct ApngWriter<W: Write + Send + 'static> {
+    output: Option<W>,
+    frames: Vec<RecordedFrame>,
+    delay: u16,
+}
+
+#[cfg(feature = "apng")]
+impl<W: Write + Send + 'static> fmt::Debug for ApngWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApngWriter")
+            .field("frames", &self.frames.len())
+            .field("delay", &self.delay)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "apng")]
+impl ApngWriter<BufWriter<File>> {
+    /// Create the APNG file `path`, whose frames last `frame_duration`.
+    pub fn create(path: impl AsRef<Path>, frame_duration: Time) -> io::Result<Self> {
+        Ok(Self::new(
+            BufWriter::new(File::create(path)?),
+            frame_duration,
+        ))
+    }
+}
+
+#[cfg(feature = "apng")]
+impl<W: Write + Send + 'static> ApngWriter<W> {
+    /// Write an APNG to `output`, whose frames last `frame_duration`, rounded to the
+    /// millisecond.
+    #[must_use]
+    pub fn new(output: W, frame_duration: Time) -> Self {
+        Self {
+            output: Some(output),
+            frames: Vec::new(),
+            delay: u16::try_from(frame_duration.as_milliseconds().max(1)).unwrap_or(u16::MAX),
+        }
+    }
+}
+
+#[cfg(feature = "apng")]
+impl<W: Write + Send + 'static> FrameSink for ApngWriter<W> {
+    fn write_frame(&mut self, frame: &RecordedFrame) -> Result<(), SinkError> {
+        if let Some(first) = self.frames.first() {
+            check_size(first.size, frame)?;
+        }
+        self.frames.push(frame.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), SinkError> {
+        let (Some(output), Some(first)) = (self.output.take(), self.frames.first()) else {
+            return Ok(());
+        };
+        let mut encoder = png::Encoder::new(output, first.size.x, first.size.y);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(u32::try_from(self.frames.len())?, 0)?;
+        encoder.set_frame_delay(self.delay, 1000)?;
+        let mut writer = encoder.write_header()?;
+        for frame in self.frames.drain(..) {
+            writer.write_image_data(&frame.pixels)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// Error that can happen when recording frames
+#[derive(Debug)]
+pub enum RecordError {
+    /// A frame couldn't be captured
+    Capture(SfError),
+    /// The sink failed to write a frame, or to finish
+    Sink(SinkError),
+    /// The recording stopped, after an error or a panic of the sink
+    Stopped,
+}
+
+impl Error for RecordError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Capture(error) => Some(error),
+            Self::Sink(error) => Some(&**error),
+            Self::Stopped => None,
+        }
+    }
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Capture(error) => write!(f, "failed to capture a frame: {error}"),
+            Self::Sink(error) => write!(f, "failed to write the recording: {error}"),
+            Self::Stopped => write!(f, "the recording stopped"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "gif", feature = "apng"))]
+    use std::sync::{Arc, Mutex};
+    use {
+        super::*,
+        std::{sync::mpsc::Receiver, time::Duration},
+    };
+
+    /// Sends the frames it gets, and fails when told to.
+    struct FakeSink {
+        frames: Sender<RecordedFrame>,
+        fail_write: bool,
+        fail_finish: bool,
+    }
+
+    impl FrameSink for FakeSink {
+        fn write_frame(&mut self, frame: &RecordedFrame) -> Result<(), SinkError> {
+            if self.fail_write {
+                return Err("write failed".into());
+            }
+            self.frames.send(frame.clone())?;
+            Ok(())
+        }
+
+        fn finish(&mut self) -> Result<(), SinkError> {
+            if self.fail_finish {
+                Err("finish failed".into())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn fake_recorder(
+        interval: u32,
+        fail_write: bool,
+        fail_finish: bool,
+    ) -> (FrameRecorder, Receiver<RecordedFrame>) {
+        let (frames, receiver) = mpsc::channel();
+        let sink = FakeSink {
+            frames,
+            fail_write,
+            fail_finish,
+        };
+        (FrameRecorder::new(sink, interval), receiver)
+    }
+
+    /// A 1x1 frame whose red component is `value`.
+    fn pixel(value: u8) -> SfResult<(Vector2u, Vec<u8>)> {
+        Ok((Vector2u::new(1, 1), vec![value, 0, 0, 255]))
+    }
+
+    fn sink_message(error: &RecordError) -> Option<String> {
+        match error {
+            RecordError::Sink(error) => Some(error.to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn every_nth_frame() -> Result<(), RecordError> {
+        let (mut recorder, receiver) = fake_recorder(3, false, false);
+        let mut captured = Vec::new();
+        for i in 0..7 {
+            recorder.record(|| {
+                captured.push(i);
+                pixel(i)
+            })?;
+        }
+        assert_eq!(captured, [0, 3, 6]);
+        assert_eq!(recorder.recorded_frames(), 3);
+        recorder.finish()?;
+        let frames: Vec<_> = receiver
+            .iter()
+            .map(|frame| (frame.index, frame.pixels[0]))
+            .collect();
+        assert_eq!(frames, [(0, 0), (1, 3), (2, 6)]);
+        Ok(())
+    }
+
+    #[test]
+    fn write_error() {
+        let (mut recorder, _receiver) = fake_recorder(1, true, false);
+        // The error is reported once the background thread stopped
+        let error = (0..1000).find_map(|_| {
+            let error = recorder.record(|| pixel(0)).err();
+            thread::sleep(Duration::from_millis(1));
+            error
+        });
+        assert_eq!(
+            error.as_ref().and_then(sink_message).as_deref(),
+            Some("write failed")
+        );
+        let error = recorder.record(|| pixel(0)).err();
+        assert!(matches!(error, Some(RecordError::Stopped)));
+        assert!(recorder.finish().is_ok());
+    }
+
+    #[test]
+    fn finish_error() {
+        let (mut recorder, _receiver) = fake_recorder(1, true, false);
+        assert!(recorder.record(|| pixel(0)).is_ok());
+        let error = recorder.finish().err();
+        assert_eq!(
+            error.as_ref().and_then(sink_message).as_deref(),
+            Some("write failed")
+        );
+        let (mut recorder, _receiver) = fake_recorder(1, false, true);
+        assert!(recorder.record(|| pixel(0)).is_ok());
+        let error = recorder.finish().err();
+        assert_eq!(
+            error.as_ref().and_then(sink_message).as_deref(),
+            Some("finish failed")
+        );
+    }
+
+    #[test]
+    fn capture_error() {
+        let (mut recorder, receiver) = fake_recorder(1, false, false);
+        let error = recorder.record(|| Err(SfError::CallFailed)).err();
+        assert!(matches!(
+            error,
+            Some(RecordError::Capture(SfError::CallFailed))
+        ));
+        assert_eq!(recorder.recorded_frames(), 0);
+        assert!(recorder.finish().is_ok());
+        assert_eq!(receiver.iter().count(), 0);
+    }
+
+    /// An output that can still be read after the sink that owns it is finished.
+    #[cfg(any(feature = "gif", feature = "apng"))]
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    #[cfg(any(feature = "gif", feature = "apng"))]
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0
+                .lock()
+                .map_err(|_| io::Error::other("poisoned"))?
+                .write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg(any(feature = "gif", feature = "apng"))]
+    impl SharedBuffer {
+        fn take(&self) -> Vec<u8> {
+            self.0
+                .lock()
+                .map(|mut data| data.split_off(0))
+                .unwrap_or_default()
+        }
+    }
+
+    /// Two 2x1 frames, with opaque colors that fit in a GIF palette.
+    #[cfg(any(feature = "gif", feature = "apng"))]
+    fn two_frames() -> [RecordedFrame; 2] {
+        let size = Vector2u::new(2, 1);
+        [
+            RecordedFrame {
+                index: 0,
+                size,
+                pixels: vec![255, 0, 0, 255, 0, 255, 0, 255],
+            },
+            RecordedFrame {
+                index: 1,
+                size,
+                pixels: vec![0, 0, 255, 255, 255, 255, 255, 255],
+            },
+        ]
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn gif_round_trip() -> Result<(), SinkError> {
+        let output = SharedBuffer::default();
+        let mut sink = GifWriter::new(output.clone(), Time::milliseconds(50));
+        let frames = two_frames();
+        for frame in &frames {
+            sink.write_frame(frame)?;
+        }
+        sink.finish()?;
+
+        let data = output.take();
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(data.as_slice())?;
+        assert_eq!((decoder.width(), decoder.height()), (2, 1));
+        let mut decoded = Vec::new();
+        while let Some(frame) = decoder.read_next_frame()? {
+            decoded.push((frame.buffer.to_vec(), frame.delay));
+        }
+        let expected: Vec<_> = frames
+            .iter()
+            .map(|frame| (frame.pixels.clone(), 5))
+            .collect();
+        assert_eq!(decoded, expected);
+        Ok(())
+    }
+
+    #[cfg(feature = "apng")]
+    #[test]
+    fn apng_round_trip() -> Result<(), SinkError> {
+        let output = SharedBuffer::default();
+        let mut sink = ApngWriter::new(output.clone(), Time::milliseconds(40));
+        let frames = two_frames();
+        for frame in &frames {
+            sink.write_frame(frame)?;
+        }
+        sink.finish()?;
+
+        let data = output.take();
+        let mut reader = png::Decoder::new(data.as_slice()).read_info()?;
+        let animation = reader.info().animation_control;
+        assert_eq!(animation.map(|control| control.num_frames), Some(2));
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        for frame in &frames {
+            let info = reader.next_frame(&mut buffer)?;
+            assert_eq!((info.width, info.height), (2, 1));
+            assert_eq!(&buffer[..info.buffer_size()], frame.pixels.as_slice());
+            let delay = reader.info().frame_control.map(|control| control.delay_num);
+            assert_eq!(delay, Some(40));
+        }
+        Ok(())
+    }
+}
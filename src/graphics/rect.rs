@@ -180,6 +180,90 @@ impl<T: PartialOrd + Add<Output = T> + Sub<Output = T> + Copy> Rect<T> {
             None
         }
     }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    ///
+    /// # Usage Example
+    /// ```
+    /// # use sfml::graphics::Rect;
+    /// # use sfml::system::Vector2;
+    /// let a = Rect::new(Vector2::new(0, 0), Vector2::new(2, 2));
+    /// let b = Rect::new(Vector2::new(3, 1), Vector2::new(2, 2));
+    /// assert_eq!(a.union(&b), Rect::new(Vector2::new(0, 0), Vector2::new(5, 3)));
+    /// ```
+    pub fn union(self, other: &Rect<T>) -> Rect<T> {
+        let (r1_min_x, r1_max_x) = min_max(self.position.x, self.position.x + self.size.x);
+        let (r1_min_y, r1_max_y) = min_max(self.position.y, self.position.y + self.size.y);
+        let (r2_min_x, r2_max_x) = min_max(other.position.x, other.position.x + other.size.x);
+        let (r2_min_y, r2_max_y) = min_max(other.position.y, other.position.y + other.size.y);
+        let left = min(r1_min_x, r2_min_x);
+        let top = min(r1_min_y, r2_min_y);
+        let right = max(r1_max_x, r2_max_x);
+        let bottom = max(r1_max_y, r2_max_y);
+        Rect::new(
+            Vector2::new(left, top),
+            Vector2::new(right - left, bottom - top),
+        )
+    }
+
+    /// Check if another rectangle is entirely inside the rectangle's area.
+    ///
+    /// # Usage Example
+    /// ```
+    /// # use sfml::graphics::Rect;
+    /// # use sfml::system::Vector2;
+    /// let a = Rect::new(Vector2::new(0, 0), Vector2::new(4, 4));
+    /// assert!(a.contains_rect(&Rect::new(Vector2::new(1, 1), Vector2::new(3, 3))));
+    /// assert!(!a.contains_rect(&Rect::new(Vector2::new(1, 1), Vector2::new(4, 4))));
+    /// ```
+    pub fn contains_rect(self, other: &Rect<T>) -> bool {
+        let (r1_min_x, r1_max_x) = min_max(self.position.x, self.position.x + self.size.x);
+        let (r1_min_y, r1_max_y) = min_max(self.position.y, self.position.y + self.size.y);
+        let (r2_min_x, r2_max_x) = min_max(other.position.x, other.position.x + other.size.x);
+        let (r2_min_y, r2_max_y) = min_max(other.position.y, other.position.y + other.size.y);
+        r2_min_x >= r1_min_x && r2_max_x <= r1_max_x && r2_min_y >= r1_min_y && r2_max_y <= r1_max_y
+    }
+
+    /// Returns the rectangle grown by `amount` on each side.
+    ///
+    /// A negative `amount` shrinks the rectangle.
+    ///
+    /// # Usage Example
+    /// ```
+    /// # use sfml::graphics::Rect;
+    /// # use sfml::system::Vector2;
+    /// let a = Rect::new(Vector2::new(2, 2), Vector2::new(2, 2)).inflate(1);
+    /// assert_eq!(a, Rect::new(Vector2::new(1, 1), Vector2::new(4, 4)));
+    /// ```
+    pub fn inflate(self, amount: T) -> Rect<T> {
+        Rect::new(
+            Vector2::new(self.position.x - amount, self.position.y - amount),
+            Vector2::new(self.size.x + amount + amount, self.size.y + amount + amount),
+        )
+    }
+
+    /// Returns the smallest rectangle containing all the points, or `None` if there are none.
+    ///
+    /// # Usage Example
+    /// ```
+    /// # use sfml::graphics::Rect;
+    /// # use sfml::system::Vector2;
+    /// let points = [Vector2::new(1, 3), Vector2::new(4, 0), Vector2::new(2, 2)];
+    /// let a = Rect::from_points(points);
+    /// assert_eq!(a, Some(Rect::new(Vector2::new(1, 0), Vector2::new(3, 3))));
+    /// assert_eq!(Rect::<i32>::from_points([]), None);
+    /// ```
+    pub fn from_points(points: impl IntoIterator<Item = Vector2<T>>) -> Option<Rect<T>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min_point, max_point) = points.fold((first, first), |(lo, hi), point| {
+            (
+                Vector2::new(min(lo.x, point.x), min(lo.y, point.y)),
+                Vector2::new(max(hi.x, point.x), max(hi.y, point.y)),
+            )
+        });
+        Some(Rect::new(min_point, max_point - min_point))
+    }
 }
 
 impl<T> Rect<T>
@@ -0,0 +1,198 @@
+use {
+    crate::graphics::{BlendMode, Drawable, RenderStates, RenderTarget, Shader, Texture},
+    std::{cmp::Ordering, fmt, ptr},
+};
+
+/// Collects drawables during a frame, and draws them sorted by layer and z when flushed.
+///
+/// This decouples the order in which the game logic submits its drawables from the order in
+/// which they are drawn.
+///
+/// At [flush](RenderQueue::flush), the submissions are sorted by increasing layer, then by
+/// increasing z within a layer, so the ones with the highest layer and z are drawn on top.
+/// Submissions with the same layer and z are grouped by texture, shader and blend mode of their
+/// render states, to minimize the state changes, and otherwise drawn in submission order.
+/// Give different z values to submissions that overlap and must be drawn in a specific order.
+///
+/// Only the texture of the render states is known to the queue. The texture that a drawable
+/// sets itself, like the one of a [`Sprite`], is not taken into account.
+///
+/// [`Sprite`]: crate::graphics::Sprite
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let (background, player, hud): (Sprite, Sprite, Text) = unimplemented!();
+/// # let enemies: Vec<Sprite> = unimplemented!();
+/// const WORLD: i32 = 0;
+/// const UI: i32 = 1;
+///
+/// let mut queue = RenderQueue::new();
+/// queue.submit(UI, 0., &hud);
+/// // Sort the characters by their vertical position, so the lower ones are in front
+/// for enemy in &enemies {
+///     queue.submit(WORLD, enemy.position().y, enemy);
+/// }
+/// queue.submit(WORLD, player.position().y, &player);
+/// queue.submit(WORLD, f32::NEG_INFINITY, &background);
+/// queue.flush(&mut window);
+/// ```
+#[derive(Default)]
+pub struct RenderQueue<'a> {
+    submissions: Vec<Submission<'a>>,
+}
+
+struct Submission<'a> {
+    layer: i32,
+    z: f32,
+    drawable: &'a dyn Drawable,
+    states: RenderStates<'a, 'a, 'a>,
+}
+
+impl fmt::Debug for RenderQueue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderQueue")
+            .field("len", &self.submissions.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> RenderQueue<'a> {
+    /// Create an empty queue.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Submit `drawable` to be drawn with the default render states.
+    pub fn submit(&mut self, layer: i32, z: f32, drawable: &'a dyn Drawable) {
+        self.submit_with_renderstates(layer, z, drawable, &RenderStates::DEFAULT);
+    }
+
+    /// Submit `drawable` to be drawn with `states`.
+    pub fn submit_with_renderstates(
+        &mut self,
+        layer: i32,
+        z: f32,
+        drawable: &'a dyn Drawable,
+        states: &RenderStates<'a, 'a, 'a>,
+    ) {
+        self.submissions.push(Submission {
+            layer,
+            z,
+            drawable,
+            states: *states,
+        });
+    }
+
+    /// Get the number of submissions waiting to be drawn.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.submissions.len()
+    }
+
+    /// Tell whether no submission is waiting to be drawn.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.submissions.is_empty()
+    }
+
+    /// Discard the submissions without drawing them.
+    pub fn clear(&mut self) {
+        self.submissions.clear();
+    }
+
+    /// Draw the submissions to `target` in sorted order, and empty the queue.
+    ///
+    /// The queue keeps its memory, so it can be reused for the next frame.
+    pub fn flush(&mut self, target: &mut dyn RenderTarget) {
+        self.sort();
+        for submission in self.submissions.drain(..) {
+            target.draw_with_renderstates(submission.drawable, &submission.states);
+        }
+    }
+
+    fn sort(&mut self) {
+        // Stable, so equal submissions stay in submission order
+        self.submissions.sort_by(|a, b| {
+            a.layer
+                .cmp(&b.layer)
+                .then_with(|| a.z.total_cmp(&b.z))
+                .then_with(|| compare_states(&a.states, &b.states))
+        });
+    }
+}
+
+/// Order render states to group the ones with the same texture, shader and blend mode.
+fn compare_states(a: &RenderStates<'_, '_, '_>, b: &RenderStates<'_, '_, '_>) -> Ordering {
+    let texture = |states: &RenderStates<'_, '_, '_>| states.texture.map(ptr::from_ref::<Texture>);
+    let shader = |states: &RenderStates<'_, '_, '_>| {
+        states
+            .shader
+            .map(|shader| ptr::from_ref::<Shader<'_>>(shader).cast::<()>())
+    };
+    texture(a)
+        .cmp(&texture(b))
+        .then_with(|| shader(a).cmp(&shader(b)))
+        .then_with(|| blend_key(&a.blend_mode).cmp(&blend_key(&b.blend_mode)))
+}
+
+fn blend_key(mode: &BlendMode) -> [u8; 6] {
+    [
+        mode.color_src_factor as u8,
+        mode.color_dst_factor as u8,
+        mode.color_equation as u8,
+        mode.alpha_src_factor as u8,
+        mode.alpha_dst_factor as u8,
+        mode.alpha_equation as u8,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Nothing;
+
+    impl Drawable for Nothing {
+        fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+            &'a self,
+            _target: &mut dyn RenderTarget,
+            _states: &RenderStates<'texture, 'shader, 'shader_texture>,
+        ) {
+        }
+    }
+
+    #[test]
+    fn sorted_by_layer_z_then_states() {
+        let nothing = Nothing;
+        let mut queue = RenderQueue::new();
+        let add = RenderStates {
+            blend_mode: BlendMode::ADD,
+            ..RenderStates::DEFAULT
+        };
+        queue.submit(1, 0., &nothing);
+        queue.submit_with_renderstates(0, 2., &nothing, &add);
+        queue.submit(0, 2., &nothing);
+        queue.submit_with_renderstates(0, 2., &nothing, &add);
+        queue.submit(0, -1., &nothing);
+        queue.sort();
+        let order: Vec<_> = queue
+            .submissions
+            .iter()
+            .map(|s| (s.layer, s.z, s.states.blend_mode == BlendMode::ADD))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                (0, -1., false),
+                (0, 2., true),
+                (0, 2., true),
+                (0, 2., false),
+                (1, 0., false),
+            ]
+        );
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
         RcText, RectangleShape, RenderStates, RenderTarget, Sprite, Text, Texture, Vertex,
         VertexBuffer, View,
     },
-    system::{Vector2f, Vector2i, Vector2u},
+    system::{Vector2f, Vector2i, Vector2u, err::diagnose},
     window::ContextSettings,
 };
 
@@ -54,7 +54,7 @@ impl RenderTexture {
     /// * size - width and height of the render-texture
     /// * settings - Additional settings for the underlying OpenGL texture and context
     pub fn resize(&mut self, size: Vector2u, settings: &ContextSettings) -> SfResult<()> {
-        unsafe { ffi::sfRenderTexture_resize(self, size, settings) }.into_sf_result()
+        diagnose(|| unsafe { ffi::sfRenderTexture_resize(self, size, settings) }.into_sf_result())
     }
     /// Update the contents of the target texture
     pub fn display(&mut self) {
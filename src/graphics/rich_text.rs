@@ -0,0 +1,604 @@
+use {
+    crate::{
+        graphics::{
+            Color, Drawable, FloatRect, FontFamily, RenderStates, RenderTarget, TextStyle,
+            Transformable,
+            text_layout::{GlyphBatch, GlyphRenderer, Metrics, batches_bounds, draw_batches},
+            transformable::{Transformation, impl_transformable_via},
+        },
+        system::Vector2f,
+    },
+    std::{error::Error, fmt},
+};
+
+/// A run of text sharing the same font and style, part of a [`RichText`].
+#[derive(Debug, Clone)]
+pub struct TextSpan<'s> {
+    /// The text of the span. It can contain newlines.
+    pub text: String,
+    /// Font of the span, with its fallback fonts
+    pub font: FontFamily<'s>,
+    /// Character size, in pixels
+    pub character_size: u32,
+    /// Style of the span
+    pub style: TextStyle,
+    /// Fill color of the span
+    pub fill_color: Color,
+    /// Outline color of the span
+    pub outline_color: Color,
+    /// Outline thickness of the span
+    pub outline_thickness: f32,
+    /// Letter spacing factor. See [`Text::set_letter_spacing`].
+    ///
+    /// [`Text::set_letter_spacing`]: crate::graphics::Text::set_letter_spacing
+    pub letter_spacing: f32,
+}
+
+impl<'s> TextSpan<'s> {
+    /// Create a span with a regular white style.
+    ///
+    /// `font` is either a [`Font`] or a [`FontFamily`].
+    ///
+    /// [`Font`]: crate::graphics::Font
+    #[must_use]
+    pub fn new(
+        text: impl Into<String>,
+        font: impl Into<FontFamily<'s>>,
+        character_size: u32,
+    ) -> Self {
+        Self {
+            text: text.into(),
+            font: font.into(),
+            character_size,
+            style: TextStyle::REGULAR,
+            fill_color: Color::WHITE,
+            outline_color: Color::BLACK,
+            outline_thickness: 0.,
+            letter_spacing: 1.,
+        }
+    }
+
+    /// Create a span with the same style as this one, but a different text.
+    #[must_use]
+    pub fn with_text(&self, text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Text made of several [`TextSpan`]s, each with its own font and style.
+///
+/// The spans follow each other, and spans on the same line share a baseline.
+/// Each line is as high as its largest span.
+///
+/// A `RichText` can be built from a list of spans, or from markup with
+/// [`RichText::from_markup`].
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # let font = Font::from_file("examples/resources/sansation.ttf").unwrap();
+/// # let mut window: RenderWindow = unimplemented!();
+/// let base = TextSpan::new("", &font, 20);
+/// let mut text = RichText::new();
+/// text.push_span(base.with_text("Hello, "));
+/// text.push_span(TextSpan {
+///     style: TextStyle::BOLD,
+///     fill_color: Color::YELLOW,
+///     ..base.with_text("world")
+/// });
+/// window.draw(&text);
+///
+/// // The same, with markup
+/// let text = RichText::from_markup("Hello, [b][color=yellow]world[/color][/b]", &base).unwrap();
+/// window.draw(&text);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RichText<'s> {
+    spans: Vec<TextSpan<'s>>,
+    line_spacing: f32,
+    transformation: Transformation,
+    batches: Vec<GlyphBatch<'s>>,
+    bounds: FloatRect,
+}
+
+/// Creation
+impl<'s> RichText<'s> {
+    /// Create an empty rich text.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            spans: Vec::new(),
+            line_spacing: 1.,
+            transformation: Transformation::default(),
+            batches: Vec::new(),
+            bounds: FloatRect::default(),
+        }
+    }
+
+    /// Create a rich text from a list of spans.
+    #[must_use]
+    pub fn from_spans(spans: Vec<TextSpan<'s>>) -> Self {
+        let mut text = Self::new();
+        text.set_spans(spans);
+        text
+    }
+
+    /// Create a rich text by parsing BBCode-like markup.
+    ///
+    /// Text outside of any tag uses the style of `base`, and tags change it:
+    ///
+    /// | Tag             | Effect                                        |
+    /// |-----------------|-----------------------------------------------|
+    /// | `[b]`           | Bold                                          |
+    /// | `[i]`           | Italic                                        |
+    /// | `[u]`           | Underlined                                    |
+    /// | `[s]`           | Strikethrough                                 |
+    /// | `[color=...]`   | Fill color                                    |
+    /// | `[outline=...]` | Outline color, with a thickness of at least 1 |
+    /// | `[size=20]`     | Character size                                |
+    /// | `[spacing=1.5]` | Letter spacing factor                         |
+    ///
+    /// Colors are either `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, or the name of one
+    /// of the [`Color`] constants, like `yellow`.
+    ///
+    /// Tags are closed with `[/tag]`, in the reverse order they were opened.
+    /// Tags still open at the end of the markup are closed implicitly.
+    /// Use `[[` to write a literal `[`.
+    pub fn from_markup(markup: &str, base: &TextSpan<'s>) -> Result<Self, MarkupError> {
+        Ok(Self::from_spans(parse_markup(markup, base)?))
+    }
+}
+
+impl Default for RichText<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Spans
+impl<'s> RichText<'s> {
+    /// Add a span at the end of the text.
+    pub fn push_span(&mut self, span: TextSpan<'s>) {
+        self.spans.push(span);
+        self.update();
+    }
+
+    /// Replace all the spans of the text.
+    pub fn set_spans(&mut self, spans: Vec<TextSpan<'s>>) {
+        self.spans = spans;
+        self.update();
+    }
+
+    /// Get the spans of the text.
+    #[must_use]
+    pub fn spans(&self) -> &[TextSpan<'s>] {
+        &self.spans
+    }
+
+    /// Remove all the spans.
+    pub fn clear(&mut self) {
+        self.spans.clear();
+        self.update();
+    }
+}
+
+/// Layout
+impl RichText<'_> {
+    /// Set the line spacing factor. See [`Text::set_line_spacing`].
+    ///
+    /// [`Text::set_line_spacing`]: crate::graphics::Text::set_line_spacing
+    pub fn set_line_spacing(&mut self, factor: f32) {
+        self.line_spacing = factor;
+        self.update();
+    }
+
+    /// Get the line spacing factor.
+    #[must_use]
+    pub fn line_spacing(&self) -> f32 {
+        self.line_spacing
+    }
+
+    /// Get the local bounding rectangle of the text.
+    #[must_use]
+    pub fn local_bounds(&self) -> FloatRect {
+        self.bounds
+    }
+
+    /// Get the global bounding rectangle of the text.
+    #[must_use]
+    pub fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(self.bounds)
+    }
+
+    fn update(&mut self) {
+        let metrics: Vec<Metrics> = self
+            .spans
+            .iter()
+            .map(|span| {
+                Metrics::new(
+                    span.font.clone(),
+                    span.character_size,
+                    span.style,
+                    span.letter_spacing,
+                    self.line_spacing,
+                )
+                .with_outline(span.outline_thickness)
+            })
+            .collect();
+
+        // Split the spans into lines of (span index, text) segments
+        let mut lines: Vec<Vec<(usize, &str)>> = vec![Vec::new()];
+        for (index, span) in self.spans.iter().enumerate() {
+            for (i, piece) in span.text.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(Vec::new());
+                }
+                if let Some(line) = lines.last_mut() {
+                    line.push((index, piece));
+                }
+            }
+        }
+
+        let mut batches = Vec::new();
+        let mut top = 0.;
+        for line in &lines {
+            let ascent = line
+                .iter()
+                .map(|&(index, _)| metrics[index].character_size)
+                .fold(0., f32::max);
+            let height = line
+                .iter()
+                .map(|&(index, _)| metrics[index].line_spacing)
+                .fold(0., f32::max);
+            let baseline = top + ascent;
+            let mut x = 0.;
+            for &(index, piece) in line {
+                let span = &self.spans[index];
+                let metrics = &metrics[index];
+                let mut renderer = GlyphRenderer::new(metrics, span.fill_color, span.outline_color);
+                let start = x;
+                let mut prev = None;
+                for c in piece.chars() {
+                    x += metrics.kerning(prev, c);
+                    renderer.add_char(c, Vector2f::new(x, baseline));
+                    x += metrics.advance(c);
+                    prev = Some(c);
+                }
+                renderer.add_decorations(start, x, baseline);
+                for mut batch in renderer.batches {
+                    let merged =
+                        GlyphBatch::find_or_insert(&mut batches, batch.font, batch.character_size);
+                    merged.vertices.append(&mut batch.vertices);
+                    merged.outline_vertices.append(&mut batch.outline_vertices);
+                }
+            }
+            top += height;
+        }
+        self.bounds = batches_bounds(&batches);
+        self.batches = batches;
+    }
+}
+
+impl_transformable_via!(RichText<'_>, transformation);
+
+impl Drawable for RichText<'_> {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        let states = RenderStates {
+            transform: states.transform * *self.transform(),
+            ..*states
+        };
+        draw_batches(&self.batches, target, &states);
+    }
+}
+
+/// Error that can happen when parsing markup with [`RichText::from_markup`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupError {
+    /// A `[` without a matching `]`
+    UnterminatedTag {
+        /// Byte offset of the `[`
+        position: usize,
+    },
+    /// A tag that is not supported
+    UnknownTag {
+        /// Byte offset of the tag
+        position: usize,
+        /// Name of the tag
+        tag: String,
+    },
+    /// A tag value that could not be parsed
+    InvalidValue {
+        /// Byte offset of the tag
+        position: usize,
+        /// The invalid value
+        value: String,
+    },
+    /// A closing tag that doesn't match the last opened tag
+    MismatchedClose {
+        /// Byte offset of the closing tag
+        position: usize,
+        /// Name of the closing tag
+        tag: String,
+    },
+}
+
+impl Error for MarkupError {}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedTag { position } => {
+                write!(f, "unterminated tag at {position}")
+            }
+            Self::UnknownTag { position, tag } => {
+                write!(f, "unknown tag `{tag}` at {position}")
+            }
+            Self::InvalidValue { position, value } => {
+                write!(f, "invalid tag value `{value}` at {position}")
+            }
+            Self::MismatchedClose { position, tag } => {
+                write!(
+                    f,
+                    "closing tag `{tag}` at {position} doesn't match any open tag"
+                )
+            }
+        }
+    }
+}
+
+fn parse_markup<'s>(markup: &str, base: &TextSpan<'s>) -> Result<Vec<TextSpan<'s>>, MarkupError> {
+    let mut spans = Vec::new();
+    let mut current = base.with_text("");
+    // Open tags, with the style to restore when they are closed
+    let mut stack: Vec<(&str, TextSpan<'s>)> = Vec::new();
+    let mut rest = markup;
+    while !rest.is_empty() {
+        let position = markup.len() - rest.len();
+        let Some(open) = rest.find('[') else {
+            current.text.push_str(rest);
+            break;
+        };
+        current.text.push_str(&rest[..open]);
+        rest = &rest[open..];
+        if let Some(after) = rest.strip_prefix("[[") {
+            current.text.push('[');
+            rest = after;
+            continue;
+        }
+        let position = position + open;
+        let close = rest
+            .find(']')
+            .ok_or(MarkupError::UnterminatedTag { position })?;
+        let tag = &rest[1..close];
+        rest = &rest[close + 1..];
+
+        let previous = current.with_text("");
+        if !current.text.is_empty() {
+            spans.push(std::mem::replace(&mut current, previous.with_text("")));
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            match stack.pop() {
+                Some((open_name, style)) if open_name == name => current = style,
+                _ => {
+                    return Err(MarkupError::MismatchedClose {
+                        position,
+                        tag: name.to_owned(),
+                    });
+                }
+            }
+            continue;
+        }
+        let (name, value) = match tag.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (tag, None),
+        };
+        let invalid = |value: &str| MarkupError::InvalidValue {
+            position,
+            value: value.to_owned(),
+        };
+        match (name, value) {
+            ("b", None) => current.style |= TextStyle::BOLD,
+            ("i", None) => current.style |= TextStyle::ITALIC,
+            ("u", None) => current.style |= TextStyle::UNDERLINED,
+            ("s", None) => current.style |= TextStyle::STRIKETHROUGH,
+            ("color", Some(value)) => {
+                current.fill_color = parse_color(value).ok_or_else(|| invalid(value))?;
+            }
+            ("outline", Some(value)) => {
+                current.outline_color = parse_color(value).ok_or_else(|| invalid(value))?;
+                current.outline_thickness = current.outline_thickness.max(1.);
+            }
+            ("size", Some(value)) => {
+                current.character_size = value.parse().map_err(|_| invalid(value))?;
+            }
+            ("spacing", Some(value)) => {
+                current.letter_spacing = value.parse().map_err(|_| invalid(value))?;
+            }
+            _ => {
+                return Err(MarkupError::UnknownTag {
+                    position,
+                    tag: tag.to_owned(),
+                });
+            }
+        }
+        stack.push((name, previous));
+    }
+    if !current.text.is_empty() {
+        spans.push(current);
+    }
+    Ok(spans)
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    let Some(hex) = value.strip_prefix('#') else {
+        return match value.to_ascii_lowercase().as_str() {
+            "black" => Some(Color::BLACK),
+            "white" => Some(Color::WHITE),
+            "red" => Some(Color::RED),
+            "green" => Some(Color::GREEN),
+            "blue" => Some(Color::BLUE),
+            "yellow" => Some(Color::YELLOW),
+            "magenta" => Some(Color::MAGENTA),
+            "cyan" => Some(Color::CYAN),
+            "transparent" => Some(Color::TRANSPARENT),
+            _ => None,
+        };
+    };
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..=i], 16).ok();
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        3 | 4 => {
+            let alpha = if hex.len() == 4 { digit(3)? * 17 } else { 255 };
+            Some(Color::rgba(
+                digit(0)? * 17,
+                digit(1)? * 17,
+                digit(2)? * 17,
+                alpha,
+            ))
+        }
+        6 | 8 => {
+            let alpha = if hex.len() == 8 { byte(6)? } else { 255 };
+            Some(Color::rgba(byte(0)?, byte(2)?, byte(4)?, alpha))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::graphics::Font, std::ptr::NonNull};
+
+    fn base() -> TextSpan<'static> {
+        // SAFETY: `Font` is a zero-sized opaque type, and parsing never calls into the font
+        let font: &Font = unsafe { NonNull::dangling().as_ref() };
+        TextSpan::new("", font, 20)
+    }
+
+    fn summary<'a>(spans: &'a [TextSpan]) -> Vec<(&'a str, TextStyle, Color)> {
+        spans
+            .iter()
+            .map(|span| (span.text.as_str(), span.style, span.fill_color))
+            .collect()
+    }
+
+    #[test]
+    fn default_line_spacing() {
+        assert_eq!(RichText::default().line_spacing(), 1.);
+    }
+
+    #[test]
+    fn nested_tags() {
+        let spans = parse_markup("a[b]b[i]c[/i]d[/b]e", &base());
+        let bold_italic = TextStyle::BOLD | TextStyle::ITALIC;
+        assert_eq!(
+            spans.as_deref().map(summary),
+            Ok(vec![
+                ("a", TextStyle::REGULAR, Color::WHITE),
+                ("b", TextStyle::BOLD, Color::WHITE),
+                ("c", bold_italic, Color::WHITE),
+                ("d", TextStyle::BOLD, Color::WHITE),
+                ("e", TextStyle::REGULAR, Color::WHITE),
+            ])
+        );
+        let spans = parse_markup("[size=30][spacing=2]x[/spacing]y", &base());
+        let sizes = spans.map(|spans| {
+            spans
+                .iter()
+                .map(|span| (span.character_size, span.letter_spacing))
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(sizes, Ok(vec![(30, 2.), (30, 1.)]));
+    }
+
+    #[test]
+    fn unclosed_tags() {
+        let spans = parse_markup("[color=red][u]x", &base());
+        assert_eq!(
+            spans.as_deref().map(summary),
+            Ok(vec![("x", TextStyle::UNDERLINED, Color::RED)])
+        );
+        assert_eq!(
+            parse_markup("ab[b", &base()).err(),
+            Some(MarkupError::UnterminatedTag { position: 2 })
+        );
+        assert_eq!(
+            parse_markup("[b]x[/i]", &base()).err(),
+            Some(MarkupError::MismatchedClose {
+                position: 4,
+                tag: "i".into()
+            })
+        );
+        assert_eq!(
+            parse_markup("x[/b]", &base()).err(),
+            Some(MarkupError::MismatchedClose {
+                position: 1,
+                tag: "b".into()
+            })
+        );
+        assert_eq!(
+            parse_markup("[big]x", &base()).err(),
+            Some(MarkupError::UnknownTag {
+                position: 0,
+                tag: "big".into()
+            })
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        let spans = parse_markup("a[[b] [[[b]c[/b]", &base());
+        assert_eq!(
+            spans.as_deref().map(summary),
+            Ok(vec![
+                ("a[b] [", TextStyle::REGULAR, Color::WHITE),
+                ("c", TextStyle::BOLD, Color::WHITE),
+            ])
+        );
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(parse_color("#f80"), Some(Color::rgb(255, 136, 0)));
+        assert_eq!(parse_color("#f808"), Some(Color::rgba(255, 136, 0, 136)));
+        assert_eq!(parse_color("#102030"), Some(Color::rgb(16, 32, 48)));
+        assert_eq!(parse_color("#10203040"), Some(Color::rgba(16, 32, 48, 64)));
+        assert_eq!(parse_color("Yellow"), Some(Color::YELLOW));
+        for bad in [
+            "",
+            "#",
+            "#12",
+            "#12345",
+            "#ggg",
+            "#+1+",
+            "purple",
+            "#1020304050",
+        ] {
+            assert_eq!(parse_color(bad), None, "{bad}");
+        }
+        assert_eq!(
+            parse_markup("[outline=#12]x", &base()).err(),
+            Some(MarkupError::InvalidValue {
+                position: 0,
+                value: "#12".into()
+            })
+        );
+        assert_eq!(
+            parse_markup("[size=big]x", &base()).err(),
+            Some(MarkupError::InvalidValue {
+                position: 0,
+                value: "big".into()
+            })
+        );
+    }
+}
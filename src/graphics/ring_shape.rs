@@ -0,0 +1,458 @@
+use {
+    crate::{
+        graphics::{
+            Color, Drawable, FloatRect, IntRect, PrimitiveType, RenderStates, RenderTarget, Shape,
+            Texture, Transformable, Vertex,
+            transformable::{Transformation, impl_transformable_via},
+            vertex_array_bounds,
+        },
+        system::{Angle, Vector2f},
+    },
+    std::f32::consts::TAU,
+};
+
+/// Specialized shape representing a ring (annulus), or an arc of a ring.
+///
+/// The ring is the area between two concentric circles. It can be limited to an arc, which
+/// starts at the start angle and goes on for the sweep angle. Angles are measured from the
+/// positive X axis, and positive angles turn clockwise on screen. The default sweep is 360°,
+/// a full ring.
+///
+/// The local origin of the ring is the top-left corner of the bounding box of its outer
+/// circle, like for [`CircleShape`].
+///
+/// A ring has a hole, which `sf::Shape` can't fill, so unlike the other shapes it is
+/// triangulated on the Rust side. Its points are those of the outer circle, followed by
+/// those of the inner circle in reverse order.
+///
+/// [`CircleShape`]: crate::graphics::CircleShape
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # use sfml::system::Angle;
+/// # let mut window: RenderWindow = unimplemented!();
+/// // A circular progress bar at 75%
+/// let mut progress = RingShape::new(40., 32.);
+/// progress.set_start_angle(Angle::degrees(-90.));
+/// progress.set_sweep_angle(Angle::degrees(0.75 * 360.));
+/// progress.set_fill_color(Color::GREEN);
+/// window.draw(&progress);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RingShape<'s> {
+    outer_radius: f32,
+    inner_radius: f32,
+    start: Angle,
+    sweep: Angle,
+    circle_point_count: usize,
+    texture: Option<&'s Texture>,
+    texture_rect: IntRect,
+    fill_color: Color,
+    outline_color: Color,
+    outline_thickness: f32,
+    transformation: Transformation,
+    points: Vec<Vector2f>,
+    /// The points are a single closed contour, rather than two circles
+    is_arc: bool,
+    inside_bounds: FloatRect,
+    vertices: Vec<Vertex>,
+    outline_vertices: Vec<Vertex>,
+}
+
+/// Creation
+impl<'s> RingShape<'s> {
+    /// Create a full ring between two circles of the given radii.
+    ///
+    /// The circles are approximated with 30 points.
+    #[must_use]
+    pub fn new(outer_radius: f32, inner_radius: f32) -> Self {
+        let mut shape = Self {
+            outer_radius,
+            inner_radius,
+            start: Angle::default(),
+            sweep: Angle::degrees(360.),
+            circle_point_count: 30,
+            texture: None,
+            texture_rect: IntRect::default(),
+            fill_color: Color::WHITE,
+            outline_color: Color::WHITE,
+            outline_thickness: 0.,
+            transformation: Transformation::default(),
+            points: Vec::new(),
+            is_arc: false,
+            inside_bounds: FloatRect::default(),
+            vertices: Vec::new(),
+            outline_vertices: Vec::new(),
+        };
+        shape.update();
+        shape
+    }
+
+    /// Create a full ring with a texture.
+    #[must_use]
+    pub fn with_texture(outer_radius: f32, inner_radius: f32, texture: &'s Texture) -> Self {
+        let mut shape = Self::new(outer_radius, inner_radius);
+        shape.set_texture(texture, true);
+        shape
+    }
+}
+
+/// Properties
+impl RingShape<'_> {
+    /// Set the radius of the outer circle.
+    pub fn set_outer_radius(&mut self, radius: f32) {
+        self.outer_radius = radius;
+        self.update();
+    }
+
+    /// Get the radius of the outer circle.
+    #[must_use]
+    pub fn outer_radius(&self) -> f32 {
+        self.outer_radius
+    }
+
+    /// Set the radius of the inner circle, the hole of the ring.
+    pub fn set_inner_radius(&mut self, radius: f32) {
+        self.inner_radius = radius;
+        self.update();
+    }
+
+    /// Get the radius of the inner circle.
+    #[must_use]
+    pub fn inner_radius(&self) -> f32 {
+        self.inner_radius
+    }
+
+    /// Set the angle at which the arc starts.
+    pub fn set_start_angle(&mut self, start: Angle) {
+        self.start = start;
+        self.update();
+    }
+
+    /// Get the angle at which the arc starts.
+    #[must_use]
+    pub fn start_angle(&self) -> Angle {
+        self.start
+    }
+
+    /// Set the angle covered by the arc. 360° or more gives a full ring.
+    pub fn set_sweep_angle(&mut self, sweep: Angle) {
+        self.sweep = sweep;
+        self.update();
+    }
+
+    /// Get the angle covered by the arc.
+    #[must_use]
+    pub fn sweep_angle(&self) -> Angle {
+        self.sweep
+    }
+
+    /// Set the number of points that approximate each full circle.
+    ///
+    /// An arc uses a proportional number of points, and at least 2 per circle.
+    pub fn set_circle_point_count(&mut self, count: usize) {
+        self.circle_point_count = count;
+        self.update();
+    }
+
+    /// Get the number of points that approximate each full circle.
+    #[must_use]
+    pub fn circle_point_count(&self) -> usize {
+        self.circle_point_count
+    }
+
+    fn update(&mut self) {
+        let mut start = self.start.as_radians();
+        let mut sweep = self.sweep.as_radians().clamp(-TAU, TAU);
+        // Go clockwise, so that the inside of the contour is on its left
+        if sweep < 0. {
+            start += sweep;
+            sweep = -sweep;
+        }
+        self.is_arc = sweep < TAU;
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let steps = ((self.circle_point_count as f32 * sweep / TAU).ceil() as usize).max(1);
+        let count = if self.is_arc { steps + 1 } else { steps };
+        let center = Vector2f::new(self.outer_radius, self.outer_radius);
+        let directions: Vec<Vector2f> = (0..count)
+            .map(|i| {
+                let (sin, cos) = (start + sweep * i as f32 / steps as f32).sin_cos();
+                Vector2f::new(cos, sin)
+            })
+            .collect();
+
+        self.points.clear();
+        self.points.extend(
+            directions
+                .iter()
+                .map(|&direction| center + direction * self.outer_radius),
+        );
+        self.points.extend(
+            directions
+                .iter()
+                .rev()
+                .map(|&direction| center + direction * self.inner_radius),
+        );
+        self.inside_bounds = bounds(&self.points);
+
+        // Fill with a quad per step, between the outer and inner points of the same angle
+        self.vertices.clear();
+        let quads = if self.is_arc { count - 1 } else { count };
+        let inner = |i: usize| self.points[2 * count - 1 - i % count];
+        for i in 0..quads {
+            let (outer0, outer1) = (self.points[i], self.points[(i + 1) % count]);
+            let (inner0, inner1) = (inner(i), inner(i + 1));
+            for position in [outer0, outer1, inner0, inner0, outer1, inner1] {
+                self.vertices
+                    .push(Vertex::with_pos_color(position, self.fill_color));
+            }
+        }
+        self.update_tex_coords();
+        self.update_outline();
+    }
+
+    fn update_tex_coords(&mut self) {
+        let bounds = self.inside_bounds;
+        let rect_position = self.texture_rect.position.as_other::<f32>();
+        let rect_size = self.texture_rect.size.as_other::<f32>();
+        for vertex in &mut self.vertices {
+            let ratio = Vector2f::new(
+                if bounds.size.x > 0. {
+                    (vertex.position.x - bounds.position.x) / bounds.size.x
+                } else {
+                    0.
+                },
+                if bounds.size.y > 0. {
+                    (vertex.position.y - bounds.position.y) / bounds.size.y
+                } else {
+                    0.
+                },
+            );
+            vertex.tex_coords = rect_position + rect_size.cwise_mul(ratio);
+        }
+    }
+
+    fn update_outline(&mut self) {
+        self.outline_vertices.clear();
+        if self.outline_thickness == 0. {
+            return;
+        }
+        let count = self.points.len() / 2;
+        if self.is_arc {
+            offset_contour(
+                &self.points,
+                self.outline_thickness,
+                self.outline_color,
+                &mut self.outline_vertices,
+            );
+        } else {
+            for contour in self.points.chunks(count) {
+                offset_contour(
+                    contour,
+                    self.outline_thickness,
+                    self.outline_color,
+                    &mut self.outline_vertices,
+                );
+            }
+        }
+    }
+}
+
+/// Geometry
+impl RingShape<'_> {
+    /// Get the triangles of the inside of the ring, in local coordinates.
+    #[must_use]
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    /// Get the triangles of the outline of the ring, in local coordinates.
+    #[must_use]
+    pub fn outline_vertices(&self) -> &[Vertex] {
+        &self.outline_vertices
+    }
+}
+
+impl<'s> Shape<'s> for RingShape<'s> {
+    fn set_texture(&mut self, texture: &'s Texture, reset_rect: bool) {
+        // Like sf::Shape, use the whole texture if no texture rect was set before
+        if reset_rect || (self.texture.is_none() && self.texture_rect == IntRect::default()) {
+            self.texture_rect = IntRect::new(Default::default(), texture.size().as_other());
+            self.update_tex_coords();
+        }
+        self.texture = Some(texture);
+    }
+    fn disable_texture(&mut self) {
+        self.texture = None;
+    }
+    fn set_texture_rect(&mut self, rect: IntRect) {
+        self.texture_rect = rect;
+        self.update_tex_coords();
+    }
+    fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+        for vertex in &mut self.vertices {
+            vertex.color = color;
+        }
+    }
+    fn set_outline_color(&mut self, color: Color) {
+        self.outline_color = color;
+        for vertex in &mut self.outline_vertices {
+            vertex.color = color;
+        }
+    }
+    fn set_outline_thickness(&mut self, thickness: f32) {
+        self.outline_thickness = thickness;
+        self.update_outline();
+    }
+    fn texture(&self) -> Option<&'s Texture> {
+        self.texture
+    }
+    fn texture_rect(&self) -> IntRect {
+        self.texture_rect
+    }
+    fn fill_color(&self) -> Color {
+        self.fill_color
+    }
+    fn outline_color(&self) -> Color {
+        self.outline_color
+    }
+    fn outline_thickness(&self) -> f32 {
+        self.outline_thickness
+    }
+    fn point_count(&self) -> usize {
+        self.points.len()
+    }
+    fn geometric_center(&self) -> Vector2f {
+        Vector2f::new(self.outer_radius, self.outer_radius)
+    }
+    fn point(&self, index: usize) -> Vector2f {
+        self.points[index]
+    }
+    fn local_bounds(&self) -> FloatRect {
+        if self.outline_vertices.is_empty() {
+            self.inside_bounds
+        } else {
+            vertex_array_bounds(&self.outline_vertices)
+        }
+    }
+    fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(self.local_bounds())
+    }
+}
+
+impl_transformable_via!(RingShape<'_>, transformation);
+
+impl Drawable for RingShape<'_> {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        let transform = states.transform * *self.transform();
+        let fill_states = RenderStates {
+            transform,
+            texture: self.texture,
+            ..*states
+        };
+        target.draw_primitives(&self.vertices, PrimitiveType::TRIANGLES, &fill_states);
+        let outline_states = RenderStates {
+            transform,
+            texture: None,
+            ..*states
+        };
+        target.draw_primitives(
+            &self.outline_vertices,
+            PrimitiveType::TRIANGLES,
+            &outline_states,
+        );
+    }
+}
+
+fn bounds(points: &[Vector2f]) -> FloatRect {
+    let Some(&first) = points.first() else {
+        return FloatRect::default();
+    };
+    let (min, max) = points.iter().fold((first, first), |(min, max), p| {
+        (
+            Vector2f::new(min.x.min(p.x), min.y.min(p.y)),
+            Vector2f::new(max.x.max(p.x), max.y.max(p.y)),
+        )
+    });
+    FloatRect::new(min, max - min)
+}
+
+/// Triangulate an outline of `thickness` on the outer side of a closed contour whose inside is
+/// on its left, the way `sf::Shape` does.
+fn offset_contour(contour: &[Vector2f], thickness: f32, color: Color, out: &mut Vec<Vertex>) {
+    let count = contour.len();
+    if count < 2 {
+        return;
+    }
+    let outward = |from: Vector2f, to: Vector2f| {
+        let edge = to - from;
+        let length = edge.length();
+        if length == 0. {
+            return Vector2f::default();
+        }
+        Vector2f::new(edge.y, -edge.x) / length
+    };
+    let offsets: Vec<Vector2f> = (0..count)
+        .map(|i| {
+            let previous = contour[(i + count - 1) % count];
+            let point = contour[i];
+            let next = contour[(i + 1) % count];
+            let (n1, n2) = (outward(previous, point), outward(point, next));
+            let factor = 1. + n1.dot(n2);
+            point + (n1 + n2) / factor * thickness
+        })
+        .collect();
+    for i in 0..count {
+        let j = (i + 1) % count;
+        for position in [
+            contour[i], offsets[i], contour[j], contour[j], offsets[i], offsets[j],
+        ] {
+            out.push(Vertex::with_pos_color(position, color));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn radii(ring: &RingShape, range: std::ops::Range<usize>) -> Vec<f32> {
+        let center = ring.geometric_center();
+        range.map(|i| (ring.point(i) - center).length()).collect()
+    }
+
+    #[test]
+    fn full_ring() {
+        let ring = RingShape::new(20., 12.);
+        assert_eq!(ring.point_count(), 60);
+        assert!(radii(&ring, 0..30).iter().all(|r| (r - 20.).abs() < 1e-4));
+        assert!(radii(&ring, 30..60).iter().all(|r| (r - 12.).abs() < 1e-4));
+        // Two triangles per step, all around
+        assert_eq!(ring.vertices().len(), 30 * 6);
+        assert!(ring.outline_vertices().is_empty());
+    }
+
+    #[test]
+    fn arc() {
+        let mut ring = RingShape::new(20., 12.);
+        ring.set_sweep_angle(Angle::degrees(90.));
+        // A quarter of 30 points is rounded up to 8 steps
+        assert_eq!(ring.point_count(), 18);
+        assert!(radii(&ring, 0..9).iter().all(|r| (r - 20.).abs() < 1e-4));
+        assert!(radii(&ring, 9..18).iter().all(|r| (r - 12.).abs() < 1e-4));
+        assert_eq!(ring.vertices().len(), 8 * 6);
+        ring.set_outline_thickness(1.);
+        // An arc is a single contour
+        assert_eq!(ring.outline_vertices().len(), 18 * 6);
+        ring.set_sweep_angle(Angle::degrees(360.));
+        // A full ring has two contours
+        assert_eq!(ring.outline_vertices().len(), 60 * 6);
+    }
+}
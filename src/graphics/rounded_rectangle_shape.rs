@@ -0,0 +1,190 @@
+use {
+    crate::{
+        graphics::{CustomShape, Shape, Texture, custom_shape::impl_shape_via},
+        system::Vector2f,
+    },
+    std::f32::consts::FRAC_PI_2,
+};
+
+/// Specialized shape representing a rectangle with rounded corners.
+///
+/// Each corner has its own radius. When the radii of two adjacent corners don't fit on a side,
+/// all the radii are scaled down so that they do.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut button = RoundedRectangleShape::new((200., 60.), 12.);
+/// button.set_corner_radii([12., 12., 0., 0.]);
+/// button.set_fill_color(Color::rgb(40, 40, 60));
+/// button.set_outline_thickness(2.);
+/// window.draw(&button);
+/// ```
+#[derive(Debug)]
+pub struct RoundedRectangleShape<'s> {
+    shape: CustomShape<'s>,
+    size: Vector2f,
+    radii: [f32; 4],
+    corner_point_count: usize,
+}
+
+/// Creation
+impl<'s> RoundedRectangleShape<'s> {
+    /// Create a rounded rectangle of the given size, with the same radius for all corners.
+    ///
+    /// Each corner is approximated with 8 points.
+    #[must_use]
+    pub fn new<S: Into<Vector2f>>(size: S, radius: f32) -> Self {
+        let mut shape = Self {
+            shape: CustomShape::new(Box::new(Vec::new())),
+            size: size.into(),
+            radii: [radius; 4],
+            corner_point_count: 8,
+        };
+        shape.update();
+        shape
+    }
+
+    /// Create a rounded rectangle with a texture.
+    #[must_use]
+    pub fn with_texture<S: Into<Vector2f>>(size: S, radius: f32, texture: &'s Texture) -> Self {
+        let mut shape = Self::new(size, radius);
+        shape.set_texture(texture, true);
+        shape
+    }
+}
+
+/// Properties
+impl RoundedRectangleShape<'_> {
+    /// Set the size of the rectangle.
+    pub fn set_size<S: Into<Vector2f>>(&mut self, size: S) {
+        self.size = size.into();
+        self.update();
+    }
+
+    /// Get the size of the rectangle.
+    #[must_use]
+    pub fn size(&self) -> Vector2f {
+        self.size
+    }
+
+    /// Set the same radius for all corners.
+    pub fn set_corner_radius(&mut self, radius: f32) {
+        self.set_corner_radii([radius; 4]);
+    }
+
+    /// Set the radius of each corner, in the order top-left, top-right, bottom-right,
+    /// bottom-left.
+    ///
+    /// A radius of 0 gives a sharp corner.
+    pub fn set_corner_radii(&mut self, radii: [f32; 4]) {
+        self.radii = radii;
+        self.update();
+    }
+
+    /// Get the radius of each corner, in the order top-left, top-right, bottom-right,
+    /// bottom-left.
+    ///
+    /// These are the radii that were set, before they are scaled down to fit.
+    #[must_use]
+    pub fn corner_radii(&self) -> [f32; 4] {
+        self.radii
+    }
+
+    /// Set the number of points used to approximate each rounded corner.
+    pub fn set_corner_point_count(&mut self, count: usize) {
+        self.corner_point_count = count;
+        self.update();
+    }
+
+    /// Get the number of points used to approximate each rounded corner.
+    #[must_use]
+    pub fn corner_point_count(&self) -> usize {
+        self.corner_point_count
+    }
+
+    fn update(&mut self) {
+        let points = points(self.size, self.radii, self.corner_point_count);
+        self.shape.set_points(Box::new(points));
+    }
+}
+
+impl_shape_via!(RoundedRectangleShape, shape);
+
+fn points(size: Vector2f, radii: [f32; 4], corner_point_count: usize) -> Vec<Vector2f> {
+    let Vector2f {
+        x: width,
+        y: height,
+    } = size;
+    let [top_left, top_right, bottom_right, bottom_left] = radii.map(|r| r.max(0.));
+    // Scale the radii down so that adjacent corners don't overlap, like CSS does
+    let fit = |side: f32, a: f32, b: f32| if a + b > side { side / (a + b) } else { 1. };
+    let factor = fit(width, top_left, top_right)
+        .min(fit(height, top_right, bottom_right))
+        .min(fit(width, bottom_right, bottom_left))
+        .min(fit(height, bottom_left, top_left));
+    let corners = [
+        (Vector2f::new(0., 0.), Vector2f::new(1., 1.), top_left),
+        (Vector2f::new(width, 0.), Vector2f::new(-1., 1.), top_right),
+        (
+            Vector2f::new(width, height),
+            Vector2f::new(-1., -1.),
+            bottom_right,
+        ),
+        (
+            Vector2f::new(0., height),
+            Vector2f::new(1., -1.),
+            bottom_left,
+        ),
+    ];
+
+    let mut points = Vec::with_capacity(4 * corner_point_count.max(1));
+    for (i, (corner, inward, radius)) in corners.into_iter().enumerate() {
+        let radius = radius * factor;
+        if radius <= 0. || corner_point_count < 2 {
+            points.push(corner);
+            continue;
+        }
+        let center = corner + inward * radius;
+        // Top-left goes from 180° to 270°, the next corners follow clockwise
+        let start = (i + 2) as f32 * FRAC_PI_2;
+        let steps = corner_point_count - 1;
+        points.extend((0..=steps).map(|step| {
+            let (sin, cos) = (start + FRAC_PI_2 * step as f32 / steps as f32).sin_cos();
+            center + Vector2f::new(cos, sin) * radius
+        }));
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Vector2f, b: (f32, f32)) -> bool {
+        (a - Vector2f::from(b)).length() < 1e-4
+    }
+
+    #[test]
+    fn point_counts() {
+        let size = Vector2f::new(100., 40.);
+        assert_eq!(points(size, [10.; 4], 8).len(), 32);
+        assert_eq!(points(size, [10., 0., 0., 0.], 8).len(), 11);
+        assert_eq!(points(size, [0.; 4], 8).len(), 4);
+        assert_eq!(points(size, [10.; 4], 1).len(), 4);
+    }
+
+    #[test]
+    fn radius_clamped_to_half_the_size() {
+        let points = points(Vector2f::new(100., 40.), [50.; 4], 8);
+        // The top-left corner is a quarter circle of radius 20, centered on (20, 20)
+        assert!(close(points[0], (0., 20.)));
+        assert!(close(points[7], (20., 0.)));
+        assert!(close(points[8], (80., 0.)));
+        assert!(close(points[15], (100., 20.)));
+        assert!(close(points[24], (20., 40.)));
+        assert!(close(points[31], (0., 20.)));
+    }
+}
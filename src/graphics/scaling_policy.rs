@@ -0,0 +1,156 @@
+use crate::{
+    graphics::{FloatRect, View},
+    system::{Vector2f, Vector2u},
+};
+
+/// How a view designed for a fixed resolution is scaled to a render target of any size.
+///
+/// Apply the policy when the target is created, and again on every [`Event::Resized`].
+/// The policy sets the size, viewport and scissor rectangle of the view, but keeps its center.
+/// [`RenderTarget::map_pixel_to_coords`] takes the viewport into account, so the mouse
+/// keeps mapping to the right world coordinates. Pixels in the black bars of
+/// [`Letterbox`] and [`PixelPerfect`] map to points outside the designed area.
+///
+/// [`Event::Resized`]: crate::window::Event::Resized
+/// [`RenderTarget::map_pixel_to_coords`]: crate::graphics::RenderTarget::map_pixel_to_coords
+/// [`Letterbox`]: ScalingPolicy::Letterbox
+/// [`PixelPerfect`]: ScalingPolicy::PixelPerfect
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::{graphics::*, system::*, window::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// const DESIGN_SIZE: Vector2f = Vector2f::new(320., 180.);
+/// let mut view = View::with_center_and_size(DESIGN_SIZE / 2., DESIGN_SIZE);
+/// ScalingPolicy::PixelPerfect.apply(&mut view, DESIGN_SIZE, window.size());
+/// while let Some(event) = window.poll_event() {
+///     if let Event::Resized { size } = event {
+///         ScalingPolicy::PixelPerfect.apply(&mut view, DESIGN_SIZE, size);
+///     }
+/// }
+/// window.set_view(&view);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScalingPolicy {
+    /// Scale uniformly to fit the target, and add black bars on the sides (pillarbox) or at
+    /// the top and bottom (letterbox) where the aspect ratios differ.
+    #[default]
+    Letterbox,
+    /// Scale each axis to fill the target, distorting the image if the aspect ratios differ.
+    Stretch,
+    /// Scale uniformly to fit the target, and show more of the world on the axis with room
+    /// left, instead of black bars.
+    Expand,
+    /// Scale uniformly to fill the target, and crop what goes past it on one axis.
+    Crop,
+    /// Scale by the largest whole factor that fits the target, and center the result with
+    /// black bars around it, so that each world pixel is a square of target pixels.
+    PixelPerfect,
+}
+
+impl ScalingPolicy {
+    /// Get the size of the view for a view designed for `design_size` shown on a target of
+    /// `target_size`.
+    ///
+    /// ```
+    /// # use sfml::{graphics::ScalingPolicy, system::{Vector2f, Vector2u}};
+    /// let design = Vector2f::new(320., 180.);
+    /// let target = Vector2u::new(1280, 1024);
+    /// assert_eq!(ScalingPolicy::Letterbox.view_size(design, target), design);
+    /// assert_eq!(ScalingPolicy::Expand.view_size(design, target), Vector2f::new(320., 256.));
+    /// ```
+    #[must_use]
+    pub fn view_size(self, design_size: Vector2f, target_size: Vector2u) -> Vector2f {
+        let target = target_size.as_other::<f32>();
+        match self {
+            Self::Letterbox | Self::Stretch | Self::PixelPerfect => design_size,
+            Self::Expand => target / fit_scale(design_size, target),
+            Self::Crop => target / fill_scale(design_size, target),
+        }
+    }
+
+    /// Get the viewport of the view, as a factor of the target size, for a view designed for
+    /// `design_size` shown on a target of `target_size`.
+    ///
+    /// ```
+    /// # use sfml::{graphics::{FloatRect, ScalingPolicy}, system::{Vector2f, Vector2u}};
+    /// let design = Vector2f::new(320., 180.);
+    /// let target = Vector2u::new(1000, 600);
+    /// // Scaled 3 times to 960x540, with 20 pixels on the sides and 30 at the top and bottom
+    /// assert_eq!(
+    ///     ScalingPolicy::PixelPerfect.viewport(design, target),
+    ///     FloatRect::new((0.02, 0.05).into(), (0.96, 0.9).into()),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn viewport(self, design_size: Vector2f, target_size: Vector2u) -> FloatRect {
+        let target = target_size.as_other::<f32>();
+        let scale = match self {
+            Self::Stretch | Self::Expand | Self::Crop => {
+                return FloatRect::new(Vector2f::new(0., 0.), Vector2f::new(1., 1.));
+            }
+            Self::Letterbox => fit_scale(design_size, target),
+            Self::PixelPerfect => fit_scale(design_size, target).floor().max(1.),
+        };
+        let size = design_size * scale;
+        let mut position = (target - size) / 2.;
+        if self == Self::PixelPerfect {
+            // Keep world pixels aligned with target pixels
+            position = Vector2f::new(position.x.floor(), position.y.floor());
+        }
+        FloatRect::new(position.cwise_div(target), size.cwise_div(target))
+    }
+
+    /// Set the size, viewport and scissor rectangle of `view`, designed for `design_size`,
+    /// to show it on a target of `target_size`.
+    pub fn apply(self, view: &mut View, design_size: Vector2f, target_size: Vector2u) {
+        if target_size.x == 0 || target_size.y == 0 {
+            return;
+        }
+        view.set_size(self.view_size(design_size, target_size));
+        view.set_viewport(self.viewport(design_size, target_size));
+        // The full rectangle disables the scissor test
+        view.set_scissor(self.scissor(design_size, target_size));
+    }
+
+    /// Get the scissor rectangle of the view, as a factor of the target size, for a view
+    /// designed for `design_size` shown on a target of `target_size`.
+    ///
+    /// This is the part of the viewport inside the target: with [`PixelPerfect`], the
+    /// viewport of a target smaller than the design size extends past the target.
+    ///
+    /// [`PixelPerfect`]: ScalingPolicy::PixelPerfect
+    ///
+    /// ```
+    /// # use sfml::{graphics::{FloatRect, ScalingPolicy}, system::{Vector2f, Vector2u}};
+    /// let design = Vector2f::new(320., 180.);
+    /// let target = Vector2u::new(200, 100);
+    /// // Not scaled down, so the view overflows the target on all sides
+    /// assert_eq!(
+    ///     ScalingPolicy::PixelPerfect.viewport(design, target),
+    ///     FloatRect::new((-0.3, -0.4).into(), (1.6, 1.8).into()),
+    /// );
+    /// assert_eq!(
+    ///     ScalingPolicy::PixelPerfect.scissor(design, target),
+    ///     FloatRect::new((0., 0.).into(), (1., 1.).into()),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn scissor(self, design_size: Vector2f, target_size: Vector2u) -> FloatRect {
+        let full = FloatRect::new(Vector2f::new(0., 0.), Vector2f::new(1., 1.));
+        self.viewport(design_size, target_size)
+            .intersection(&full)
+            .unwrap_or_default()
+    }
+}
+
+/// The largest uniform scale at which `design` fits in `target`
+fn fit_scale(design: Vector2f, target: Vector2f) -> f32 {
+    (target.x / design.x).min(target.y / design.y)
+}
+
+/// The smallest uniform scale at which `design` covers `target`
+fn fill_scale(design: Vector2f, target: Vector2f) -> f32 {
+    (target.x / design.x).max(target.y / design.y)
+}
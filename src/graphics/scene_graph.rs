@@ -0,0 +1,553 @@
+use {
+    crate::{
+        graphics::{
+            Drawable, FloatRect, RenderStates, RenderTarget, Transform, Transformable,
+            transformable::{Transformation, impl_transformable_via},
+        },
+        system::Vector2f,
+    },
+    std::{cell::Cell, fmt},
+};
+
+/// Identifier of a node in a [`SceneGraph`].
+///
+/// An identifier stays valid until its node is removed. It is not reused for the nodes added
+/// afterwards, so the identifier of a removed node can't refer to another node by accident.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    index: usize,
+    generation: u32,
+}
+
+/// A node of a [`SceneGraph`]: a local transform, an optional drawable, and the properties that
+/// apply to its children too.
+///
+/// The transform of the node is relative to its parent, through [`Transformable`].
+pub struct SceneNode<'a> {
+    transformation: Transformation,
+    drawable: Option<Box<dyn Drawable + 'a>>,
+    local_bounds: Option<FloatRect>,
+    visible: bool,
+    z_order: i32,
+    parent: Option<NodeId>,
+    /// Sorted by z-order
+    children: Vec<NodeId>,
+    world_transform: Cell<Transform>,
+    /// Whether `world_transform` must be recomputed
+    dirty: Cell<bool>,
+}
+
+impl fmt::Debug for SceneNode<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SceneNode")
+            .field("transformation", &self.transformation)
+            .field("drawable", &self.drawable.is_some())
+            .field("local_bounds", &self.local_bounds)
+            .field("visible", &self.visible)
+            .field("z_order", &self.z_order)
+            .field("parent", &self.parent)
+            .field("children", &self.children)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for SceneNode<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creation
+impl<'a> SceneNode<'a> {
+    /// Create a node without drawable, to group other nodes.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            transformation: Transformation::default(),
+            drawable: None,
+            local_bounds: None,
+            visible: true,
+            z_order: 0,
+            parent: None,
+            children: Vec::new(),
+            world_transform: Cell::new(Transform::IDENTITY),
+            dirty: Cell::new(true),
+        }
+    }
+
+    /// Create a node that draws `drawable`.
+    #[must_use]
+    pub fn with_drawable(drawable: impl Drawable + 'a) -> Self {
+        let mut node = Self::new();
+        node.drawable = Some(Box::new(drawable));
+        node
+    }
+}
+
+/// Properties
+impl<'a> SceneNode<'a> {
+    /// Set what the node draws, or `None` to only draw its children.
+    pub fn set_drawable(&mut self, drawable: Option<Box<dyn Drawable + 'a>>) {
+        self.drawable = drawable;
+    }
+
+    /// Get what the node draws.
+    #[must_use]
+    pub fn drawable(&self) -> Option<&(dyn Drawable + 'a)> {
+        self.drawable.as_deref()
+    }
+
+    /// Set the rectangle used to hit-test the node, in its local coordinates, or `None` to make
+    /// it ignored by [`SceneGraph::node_at`].
+    ///
+    /// This is usually the local bounds of the drawable, like [`Shape::local_bounds`].
+    ///
+    /// [`Shape::local_bounds`]: crate::graphics::Shape::local_bounds
+    pub fn set_local_bounds(&mut self, bounds: Option<FloatRect>) {
+        self.local_bounds = bounds;
+    }
+
+    /// Get the rectangle used to hit-test the node, in its local coordinates.
+    #[must_use]
+    pub fn local_bounds(&self) -> Option<FloatRect> {
+        self.local_bounds
+    }
+
+    /// Show or hide the node and its children.
+    ///
+    /// Hidden nodes are not drawn, and are ignored by [`SceneGraph::node_at`].
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// Tell whether the node is visible. Its parent can still be hidden.
+    #[must_use]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Get the z-order of the node among its siblings. See [`SceneGraph::set_z_order`].
+    #[must_use]
+    pub fn z_order(&self) -> i32 {
+        self.z_order
+    }
+
+    /// Get the parent of the node, or `None` for the root.
+    #[must_use]
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// Get the children of the node, in drawing order.
+    #[must_use]
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+}
+
+impl_transformable_via!(SceneNode<'_>, transformation);
+
+struct Slot<'a> {
+    generation: u32,
+    node: Option<SceneNode<'a>>,
+}
+
+/// A tree of nodes with transforms relative to their parents, and optional drawables.
+///
+/// The world transform of a node is the transform of its parent combined with its own. World
+/// transforms are cached, and recomputed for the nodes whose transform, or whose ancestors'
+/// transform, changed.
+///
+/// Nodes are drawn in depth-first order: a node is drawn before its children, so children are
+/// drawn over their parent, and siblings are drawn by increasing z-order. A hidden node hides
+/// its children.
+///
+/// The graph always has a root node, which can be transformed like the others to move the
+/// whole scene.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::{cpp::FBox, graphics::*, system::*};
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let body_texture: FBox<Texture> = unimplemented!();
+/// # let arm_texture: FBox<Texture> = unimplemented!();
+/// let mut scene = SceneGraph::new();
+/// let body = scene.add(scene.root(), SceneNode::with_drawable(Sprite::with_texture(&body_texture)));
+/// let arm = scene.add(body, SceneNode::with_drawable(Sprite::with_texture(&arm_texture)));
+/// scene[arm].set_position((20., 8.));
+/// scene[arm].set_local_bounds(Some(FloatRect::new((0., 0.).into(), (16., 40.).into())));
+/// // The arm follows the body
+/// scene[body].move_((5., 0.));
+/// window.draw(&scene);
+/// let mouse = window.map_pixel_to_coords_current_view(window.mouse_position());
+/// if scene.node_at(mouse) == Some(arm) {
+///     println!("Clicked the arm");
+/// }
+/// ```
+pub struct SceneGraph<'a> {
+    slots: Vec<Slot<'a>>,
+    free: Vec<usize>,
+    root: NodeId,
+}
+
+impl fmt::Debug for SceneGraph<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SceneGraph")
+            .field("root", &self.root)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for SceneGraph<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creation and structure
+impl<'a> SceneGraph<'a> {
+    /// Create a graph with only a root node.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            slots: vec![Slot {
+                generation: 0,
+                node: Some(SceneNode::new()),
+            }],
+            free: Vec::new(),
+            root: NodeId {
+                index: 0,
+                generation: 0,
+            },
+        }
+    }
+
+    /// Get the root node.
+    #[must_use]
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    /// Get the number of nodes, including the root.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Tell whether the graph only has its root node.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 1
+    }
+
+    /// Tell whether `id` refers to a node of the graph.
+    #[must_use]
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Add `node` as the last child of `parent` with its z-order, and get its identifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parent` is not a node of the graph.
+    pub fn add(&mut self, parent: NodeId, mut node: SceneNode<'a>) -> NodeId {
+        assert!(self.contains(parent), "parent is not a node of the graph");
+        node.parent = Some(parent);
+        node.children.clear();
+        node.dirty.set(true);
+        let id = match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.node = Some(node);
+                NodeId {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    node: Some(node),
+                });
+                NodeId {
+                    index: self.slots.len() - 1,
+                    generation: 0,
+                }
+            }
+        };
+        self.node_mut(parent).children.push(id);
+        self.sort_children(parent);
+        id
+    }
+
+    /// Remove a node and its children, and get the removed node.
+    ///
+    /// Returns `None` if `id` is not a node of the graph, or is the root.
+    pub fn remove(&mut self, id: NodeId) -> Option<SceneNode<'a>> {
+        let parent = self.get(id)?.parent?;
+        self.node_mut(parent).children.retain(|&child| child != id);
+        let mut node = self.take(id);
+        let mut pending = std::mem::take(&mut node.children);
+        while let Some(descendant) = pending.pop() {
+            pending.extend(self.take(descendant).children);
+        }
+        node.parent = None;
+        Some(node)
+    }
+
+    /// Move a node and its children under `parent`, keeping its local transform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` or `parent` is not a node of the graph, if `id` is the root, or if
+    /// `parent` is `id` or one of its descendants.
+    pub fn set_parent(&mut self, id: NodeId, parent: NodeId) {
+        assert!(self.contains(parent), "parent is not a node of the graph");
+        let old_parent = self[id].parent.expect("the root can't have a parent");
+        let mut ancestor = Some(parent);
+        while let Some(current) = ancestor {
+            assert!(current != id, "a node can't be moved under itself");
+            ancestor = self.node(current).parent;
+        }
+        self.node_mut(old_parent)
+            .children
+            .retain(|&child| child != id);
+        let node = self.node_mut(id);
+        node.parent = Some(parent);
+        node.dirty.set(true);
+        self.node_mut(parent).children.push(id);
+        self.sort_children(parent);
+    }
+
+    /// Set the z-order of a node among its siblings. Siblings are drawn by increasing z-order,
+    /// and in the order they were added when their z-orders are equal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not a node of the graph.
+    pub fn set_z_order(&mut self, id: NodeId, z_order: i32) {
+        let node = &mut self[id];
+        node.z_order = z_order;
+        if let Some(parent) = node.parent {
+            self.sort_children(parent);
+        }
+    }
+
+    /// Get a node.
+    #[must_use]
+    pub fn get(&self, id: NodeId) -> Option<&SceneNode<'a>> {
+        let slot = self.slots.get(id.index)?;
+        if slot.generation == id.generation {
+            slot.node.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Get a node, to change it.
+    ///
+    /// The world transforms of the node and its children are recomputed when they are needed
+    /// next.
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut SceneNode<'a>> {
+        let slot = self.slots.get_mut(id.index)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        let node = slot.node.as_mut()?;
+        node.dirty.set(true);
+        Some(node)
+    }
+
+    fn node(&self, id: NodeId) -> &SceneNode<'a> {
+        &self[id]
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> &mut SceneNode<'a> {
+        let slot = &mut self.slots[id.index];
+        slot.node
+            .as_mut()
+            .expect("node ids refer to existing nodes")
+    }
+
+    fn take(&mut self, id: NodeId) -> SceneNode<'a> {
+        let slot = &mut self.slots[id.index];
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+        slot.node.take().expect("node ids refer to existing nodes")
+    }
+
+    fn sort_children(&mut self, parent: NodeId) {
+        let mut children = std::mem::take(&mut self.node_mut(parent).children);
+        children.sort_by_key(|&child| self.node(child).z_order);
+        self.node_mut(parent).children = children;
+    }
+}
+
+/// Transforms and hit-testing
+impl SceneGraph<'_> {
+    /// Get the world transform of a node: its transform combined with the ones of its
+    /// ancestors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not a node of the graph.
+    #[must_use]
+    pub fn world_transform(&self, id: NodeId) -> Transform {
+        let node = &self[id];
+        let parent_transform = node
+            .parent
+            .map_or(Transform::IDENTITY, |parent| self.world_transform(parent));
+        self.update_world_transform(node, parent_transform)
+    }
+
+    /// Get the topmost visible node whose [local bounds] contain `point`, given in the
+    /// coordinates of the scene, like the ones from [`RenderTarget::map_pixel_to_coords`].
+    ///
+    /// The point is brought into the local coordinates of each node with its inverse world
+    /// transform, so rotated and scaled nodes are tested exactly.
+    ///
+    /// [local bounds]: SceneNode::set_local_bounds
+    #[must_use]
+    pub fn node_at(&self, point: Vector2f) -> Option<NodeId> {
+        self.hit_test(self.root, Transform::IDENTITY, point)
+    }
+
+    fn hit_test(&self, id: NodeId, parent_transform: Transform, point: Vector2f) -> Option<NodeId> {
+        let node = self.node(id);
+        if !node.visible {
+            return None;
+        }
+        let transform = self.update_world_transform(node, parent_transform);
+        // Children are drawn over their parent, and later siblings over earlier ones
+        if let Some(hit) = node
+            .children
+            .iter()
+            .rev()
+            .find_map(|&child| self.hit_test(child, transform, point))
+        {
+            return Some(hit);
+        }
+        let bounds = node.local_bounds?;
+        bounds
+            .contains(transform.inverse().transform_point(point))
+            .then_some(id)
+    }
+
+    /// Get the world transform of `node`, recomputing it if needed, knowing the world transform
+    /// of its parent is up to date.
+    fn update_world_transform(
+        &self,
+        node: &SceneNode<'_>,
+        parent_transform: Transform,
+    ) -> Transform {
+        if node.dirty.get() {
+            node.world_transform
+                .set(parent_transform * *node.transformation.transform());
+            node.dirty.set(false);
+            for &child in &node.children {
+                self.node(child).dirty.set(true);
+            }
+        }
+        node.world_transform.get()
+    }
+
+    fn draw_node<'texture, 'shader, 'shader_texture>(
+        &self,
+        id: NodeId,
+        parent_transform: Transform,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        let node = self.node(id);
+        if !node.visible {
+            return;
+        }
+        let transform = self.update_world_transform(node, parent_transform);
+        if let Some(drawable) = &node.drawable {
+            let states = RenderStates {
+                transform: states.transform * transform,
+                ..*states
+            };
+            target.draw_with_renderstates(&**drawable, &states);
+        }
+        for &child in &node.children {
+            self.draw_node(child, transform, target, states);
+        }
+    }
+}
+
+impl<'a> std::ops::Index<NodeId> for SceneGraph<'a> {
+    type Output = SceneNode<'a>;
+
+    /// # Panics
+    ///
+    /// Panics if `id` is not a node of the graph.
+    fn index(&self, id: NodeId) -> &Self::Output {
+        self.get(id).expect("not a node of the scene graph")
+    }
+}
+
+impl<'a> std::ops::IndexMut<NodeId> for SceneGraph<'a> {
+    /// # Panics
+    ///
+    /// Panics if `id` is not a node of the graph.
+    fn index_mut(&mut self, id: NodeId) -> &mut Self::Output {
+        self.get_mut(id).expect("not a node of the scene graph")
+    }
+}
+
+impl Drawable for SceneGraph<'_> {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        self.draw_node(self.root, Transform::IDENTITY, target, states);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_transforms_follow_parents() {
+        let mut scene = SceneGraph::new();
+        let parent = scene.add(scene.root(), SceneNode::new());
+        let child = scene.add(parent, SceneNode::new());
+        scene[child].set_position((1., 2.));
+        scene[parent].set_position((10., 0.));
+        let origin = Vector2f::new(0., 0.);
+        assert_eq!(
+            scene.world_transform(child).transform_point(origin),
+            Vector2f::new(11., 2.)
+        );
+        scene[parent].set_scale(2.);
+        assert_eq!(
+            scene.world_transform(child).transform_point(origin),
+            Vector2f::new(12., 4.)
+        );
+    }
+
+    #[test]
+    fn hit_test_topmost() {
+        let mut scene = SceneGraph::new();
+        let bounds = Some(FloatRect::new((0., 0.).into(), (10., 10.).into()));
+        let below = scene.add(scene.root(), SceneNode::new());
+        let above = scene.add(scene.root(), SceneNode::new());
+        scene[below].set_local_bounds(bounds);
+        scene[above].set_local_bounds(bounds);
+        scene[above].set_position((5., 0.));
+        assert_eq!(scene.node_at((7., 5.).into()), Some(above));
+        assert_eq!(scene.node_at((2., 5.).into()), Some(below));
+        scene.set_z_order(below, 1);
+        assert_eq!(scene.node_at((7., 5.).into()), Some(below));
+        let removed = scene.remove(below);
+        assert!(removed.is_some());
+        assert!(!scene.contains(below));
+        assert_eq!(scene.node_at((7., 5.).into()), Some(above));
+    }
+}
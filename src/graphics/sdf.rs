@@ -0,0 +1,759 @@
+//! Signed distance field text rendering.
+//!
+//! Regular [`Text`] is rasterized for one character size, and gets blurry when it is scaled
+//! up with a transform or a [`View`] zoom. Text drawn from a signed distance field atlas
+//! stays crisp at any scale: each texel of the atlas stores the distance to the outline of
+//! the glyph, and [`SdfShader`] turns that distance into sharp, anti-aliased edges.
+//!
+//! An [`SdfFont`] is generated from a [`Font`], by rasterizing its glyphs at a large size
+//! and computing the distance field of each of them. It is then drawn with [`SdfText`].
+//! The shader can also draw an outline and a glow around the text.
+//!
+//! Generating the atlas reads back the glyph textures of the font, so it requires an
+//! active OpenGL context, like any texture operation.
+//!
+//! # Usage example
+//!
+//! ```no_run
+//! use sfml::graphics::{
+//!     Color, Font, RenderTarget, RenderWindow, Transformable,
+//!     sdf::{SdfFont, SdfShader, SdfText},
+//! };
+//! # let mut window: RenderWindow = unimplemented!();
+//!
+//! let font = Font::from_file("examples/resources/sansation.ttf").unwrap();
+//! let sdf_font = SdfFont::ascii(&font, Default::default()).unwrap();
+//! let mut shader = SdfShader::new(&sdf_font).unwrap();
+//! shader.set_outline(Color::BLACK, 3.).unwrap();
+//!
+//! let mut text = SdfText::new("Zoom me!", &sdf_font, 24.);
+//! text.set_shader(Some(&shader));
+//! text.set_scale(10.);
+//! window.draw(&text);
+//! ```
+//!
+//! [`Text`]: crate::graphics::Text
+//! [`View`]: crate::graphics::View
+
+use {
+    crate::{
+        SfError, SfResult,
+        cpp::FBox,
+        graphics::{
+            Color, Drawable, FloatRect, Font, IntRect, PrimitiveType, RenderStates, RenderTarget,
+            Shader, ShaderType, Texture, Transformable, Vertex,
+            transformable::{Transformation, impl_transformable_via},
+        },
+        system::{Vector2f, Vector2i, Vector2u},
+    },
+    std::collections::HashMap,
+};
+
+/// Source of the fragment shader used to render signed distance field text.
+///
+/// The distance is read from the alpha channel of the texture: 0.5 is the outline of
+/// the glyph, larger values are inside. The shader has these uniforms:
+///
+/// * `texture`: the atlas of the [`SdfFont`]
+/// * `outline_color`, `outline_width`: color and width of the outline, in distance units
+/// * `glow_color`, `glow_width`: color and width of the glow around the outline,
+///   in distance units
+///
+/// The fill color is the vertex color.
+pub const SDF_FRAGMENT_SHADER: &str = r"uniform sampler2D texture;
+uniform vec4 outline_color;
+uniform float outline_width;
+uniform vec4 glow_color;
+uniform float glow_width;
+
+void main()
+{
+    float distance = texture2D(texture, gl_TexCoord[0].xy).a;
+    float smoothing = max(fwidth(distance) * 0.75, 0.0001);
+    float fill = smoothstep(0.5 - smoothing, 0.5 + smoothing, distance);
+    float outline_edge = 0.5 - outline_width;
+    float outline = smoothstep(outline_edge - smoothing, outline_edge + smoothing, distance);
+    vec4 layer = mix(outline_color, gl_Color, outline > 0.0 ? fill / outline : 1.0);
+    layer.a *= outline;
+    float glow_edge = outline_edge - glow_width;
+    float glow = glow_width > 0.0 ? smoothstep(glow_edge, outline_edge, distance) : 0.0;
+    float glow_alpha = glow_color.a * glow * (1.0 - layer.a);
+    float alpha = layer.a + glow_alpha;
+    vec3 color = alpha > 0.0 ? (layer.rgb * layer.a + glow_color.rgb * glow_alpha) / alpha : layer.rgb;
+    gl_FragColor = vec4(color, alpha);
+}
+";
+
+/// Settings for the generation of an [`SdfFont`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SdfFontSettings {
+    /// Character size the glyphs are rasterized at.
+    ///
+    /// Larger sizes preserve more details, at the cost of a larger atlas.
+    pub base_size: u32,
+    /// Maximum distance stored in the field, in pixels at the base size.
+    ///
+    /// This limits the width of outlines and glows.
+    pub spread: u32,
+}
+
+impl Default for SdfFontSettings {
+    fn default() -> Self {
+        Self {
+            base_size: 64,
+            spread: 8,
+        }
+    }
+}
+
+/// A glyph of an [`SdfFont`], in pixels at the base size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SdfGlyph {
+    /// Offset to move horizontally to the next character
+    pub advance: f32,
+    /// Bounding rectangle of the distance field of the glyph, relative to the baseline.
+    ///
+    /// This includes the spread around the glyph.
+    pub bounds: FloatRect,
+    /// Rectangle of the distance field of the glyph in the atlas
+    pub texture_rect: IntRect,
+}
+
+/// A signed distance field atlas generated from a [`Font`].
+///
+/// See the [module documentation](self) for more information.
+#[derive(Debug)]
+pub struct SdfFont<'f> {
+    font: &'f Font,
+    settings: SdfFontSettings,
+    glyphs: HashMap<char, SdfGlyph>,
+    packer: ShelfPacker,
+    /// RGBA pixels of the atlas
+    pixels: Vec<u8>,
+    texture: FBox<Texture>,
+}
+
+/// Width of the atlas, in pixels
+const ATLAS_WIDTH: u32 = 1024;
+
+/// Creation
+impl<'f> SdfFont<'f> {
+    /// Generate a distance field atlas for `chars`.
+    pub fn new(
+        font: &'f Font,
+        chars: impl IntoIterator<Item = char>,
+        settings: SdfFontSettings,
+    ) -> SfResult<Self> {
+        let mut texture = Texture::new()?;
+        texture.set_smooth(true);
+        let mut sdf_font = Self {
+            font,
+            settings,
+            glyphs: HashMap::new(),
+            packer: ShelfPacker::new(ATLAS_WIDTH),
+            pixels: Vec::new(),
+            texture,
+        };
+        sdf_font.add_glyphs(chars)?;
+        Ok(sdf_font)
+    }
+
+    /// Generate a distance field atlas for the printable ASCII characters.
+    pub fn ascii(font: &'f Font, settings: SdfFontSettings) -> SfResult<Self> {
+        Self::new(font, ' '..='~', settings)
+    }
+
+    /// Add glyphs to the atlas. Characters already in the atlas are skipped.
+    pub fn add_glyphs(&mut self, chars: impl IntoIterator<Item = char>) -> SfResult<()> {
+        let size = self.settings.base_size;
+        let mut new_chars: Vec<char> = chars
+            .into_iter()
+            .filter(|c| !self.glyphs.contains_key(c))
+            .collect();
+        new_chars.sort_unstable();
+        new_chars.dedup();
+        if new_chars.is_empty() {
+            return Ok(());
+        }
+        // Rasterize all the glyphs first, then read back the font texture once
+        let glyphs: Vec<_> = new_chars
+            .iter()
+            .map(|&c| self.font.glyph(u32::from(c), size, false, 0.))
+            .collect();
+        let font_image = self.font.texture(size).copy_to_image()?;
+        let font_width = font_image.size().x as usize;
+        let font_pixels = font_image.pixel_data();
+
+        #[expect(clippy::cast_possible_wrap)]
+        let spread = self.settings.spread as i32;
+        for (&c, glyph) in new_chars.iter().zip(&glyphs) {
+            let rect = glyph.texture_rect();
+            let bounds = glyph.bounds();
+            if rect.size.x <= 0 || rect.size.y <= 0 {
+                let _ = self.glyphs.insert(
+                    c,
+                    SdfGlyph {
+                        advance: glyph.advance(),
+                        bounds: FloatRect::default(),
+                        texture_rect: IntRect::default(),
+                    },
+                );
+                continue;
+            }
+            let field_size = rect.size + Vector2i::new(2 * spread, 2 * spread);
+            #[expect(clippy::cast_sign_loss)]
+            let (width, height) = (field_size.x as usize, field_size.y as usize);
+            let mut coverage = vec![false; width * height];
+            #[expect(clippy::cast_sign_loss)]
+            for y in 0..rect.size.y as usize {
+                for x in 0..rect.size.x as usize {
+                    #[expect(clippy::cast_sign_loss)]
+                    let src =
+                        (rect.position.y as usize + y) * font_width + rect.position.x as usize + x;
+                    let alpha = font_pixels[src * 4 + 3];
+                    coverage[(y + spread as usize) * width + x + spread as usize] = alpha >= 128;
+                }
+            }
+            let field = distance_field(&coverage, width, height, spread as f32);
+            #[expect(clippy::cast_possible_truncation)]
+            let position = self.pack(width as u32, height as u32)?;
+            for (y, row) in field.chunks(width).enumerate() {
+                let start =
+                    ((position.y as usize + y) * ATLAS_WIDTH as usize + position.x as usize) * 4;
+                for (x, &value) in row.iter().enumerate() {
+                    self.pixels[start + x * 4..start + x * 4 + 4]
+                        .copy_from_slice(&[255, 255, 255, value]);
+                }
+            }
+            let padding = spread as f32;
+            let _ = self.glyphs.insert(
+                c,
+                SdfGlyph {
+                    advance: glyph.advance(),
+                    bounds: FloatRect::new(
+                        bounds.position - Vector2f::new(padding, padding),
+                        field_size.as_other(),
+                    ),
+                    texture_rect: IntRect::new(position.as_other(), field_size),
+                },
+            );
+        }
+        self.upload()
+    }
+
+    /// Find room for a `width` by `height` rectangle, growing the atlas if needed
+    fn pack(&mut self, width: u32, height: u32) -> SfResult<Vector2u> {
+        let position = self.packer.pack(width, height).ok_or(SfError::CallFailed)?;
+        let required = (self.packer.height as usize) * ATLAS_WIDTH as usize * 4;
+        if self.pixels.len() < required {
+            let new_height = self.packer.height.next_power_of_two();
+            if new_height > Texture::maximum_size() {
+                return Err(SfError::CallFailed);
+            }
+            self.pixels
+                .resize(new_height as usize * ATLAS_WIDTH as usize * 4, 0);
+        }
+        Ok(position)
+    }
+
+    fn upload(&mut self) -> SfResult<()> {
+        #[expect(clippy::cast_possible_truncation)]
+        let size = Vector2u::new(
+            ATLAS_WIDTH,
+            (self.pixels.len() / (ATLAS_WIDTH as usize * 4)) as u32,
+        );
+        if self.texture.size() != size && !self.texture.resize(size, false) {
+            return Err(SfError::CallFailed);
+        }
+        self.texture
+            .update_from_pixels(&self.pixels, size, Vector2u::default());
+        Ok(())
+    }
+}
+
+/// Glyphs and metrics
+impl<'f> SdfFont<'f> {
+    /// Get the font the atlas was generated from.
+    #[must_use]
+    pub fn font(&self) -> &'f Font {
+        self.font
+    }
+
+    /// Get the settings the atlas was generated with.
+    #[must_use]
+    pub fn settings(&self) -> SdfFontSettings {
+        self.settings
+    }
+
+    /// Get the glyph of `c`, if it is in the atlas.
+    #[must_use]
+    pub fn glyph(&self, c: char) -> Option<&SdfGlyph> {
+        self.glyphs.get(&c)
+    }
+
+    /// Get the atlas texture.
+    #[must_use]
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+}
+
+/// Packs rectangles in rows of increasing height
+#[derive(Debug)]
+struct ShelfPacker {
+    width: u32,
+    /// Total height of the shelves
+    height: u32,
+    shelves: Vec<Shelf>,
+}
+
+#[derive(Debug)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    used_width: u32,
+}
+
+impl ShelfPacker {
+    fn new(width: u32) -> Self {
+        Self {
+            width,
+            height: 0,
+            shelves: Vec::new(),
+        }
+    }
+
+    fn pack(&mut self, width: u32, height: u32) -> Option<Vector2u> {
+        if width > self.width {
+            return None;
+        }
+        // Use the first shelf where the rectangle fits without wasting too much height
+        let shelf = self.shelves.iter_mut().find(|shelf| {
+            height <= shelf.height
+                && height * 10 >= shelf.height * 7
+                && shelf.used_width + width <= self.width
+        });
+        let shelf = match shelf {
+            Some(shelf) => shelf,
+            None => {
+                self.shelves.push(Shelf {
+                    y: self.height,
+                    height,
+                    used_width: 0,
+                });
+                self.height += height;
+                self.shelves.last_mut()?
+            }
+        };
+        let position = Vector2u::new(shelf.used_width, shelf.y);
+        shelf.used_width += width;
+        Some(position)
+    }
+}
+
+/// Compute the signed distance field of a coverage mask, mapped to `0..=255`.
+///
+/// 128 is on the outline, larger values are inside, and `spread` pixels is the largest
+/// distance that can be represented.
+fn distance_field(coverage: &[bool], width: usize, height: usize, spread: f32) -> Vec<u8> {
+    let outside = squared_distances(coverage, width, height, true);
+    let inside = squared_distances(coverage, width, height, false);
+    coverage
+        .iter()
+        .zip(outside.iter().zip(&inside))
+        .map(|(&covered, (&to_inside, &to_outside))| {
+            // Distances are between pixel centers, the outline is halfway
+            let signed = if covered {
+                -(to_outside.sqrt() - 0.5)
+            } else {
+                to_inside.sqrt() - 0.5
+            };
+            let value = (0.5 - signed / (2. * spread)).clamp(0., 1.);
+            #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let value = (value * 255.).round() as u8;
+            value
+        })
+        .collect()
+}
+
+/// Squared distance from each pixel to the nearest pixel whose coverage is `target`
+fn squared_distances(coverage: &[bool], width: usize, height: usize, target: bool) -> Vec<f32> {
+    let mut grid: Vec<f32> = coverage
+        .iter()
+        .map(|&covered| if covered == target { 0. } else { f32::INFINITY })
+        .collect();
+    let len = width.max(height);
+    let mut f = vec![0.; len];
+    let mut d = vec![0.; len];
+    let mut v = vec![0; len];
+    let mut z = vec![0.; len + 1];
+    for x in 0..width {
+        for y in 0..height {
+            f[y] = grid[y * width + x];
+        }
+        distance_transform_1d(&f[..height], &mut d, &mut v, &mut z);
+        for y in 0..height {
+            grid[y * width + x] = d[y];
+        }
+    }
+    for y in 0..height {
+        f[..width].copy_from_slice(&grid[y * width..(y + 1) * width]);
+        distance_transform_1d(&f[..width], &mut d, &mut v, &mut z);
+        grid[y * width..(y + 1) * width].copy_from_slice(&d[..width]);
+    }
+    grid
+}
+
+/// One-dimensional squared euclidean distance transform, from
+/// "Distance Transforms of Sampled Functions" by Felzenszwalb and Huttenlocher.
+#[expect(clippy::cast_precision_loss)]
+fn distance_transform_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+    let n = f.len();
+    if f.iter().all(|value| value.is_infinite()) {
+        d[..n].fill(f32::INFINITY);
+        return;
+    }
+    // Index of the rightmost parabola in the lower envelope
+    let mut k = 0;
+    let first = f.iter().position(|value| value.is_finite()).unwrap_or(0);
+    v[0] = first;
+    z[0] = f32::NEG_INFINITY;
+    z[1] = f32::INFINITY;
+    for q in first + 1..n {
+        if f[q].is_infinite() {
+            continue;
+        }
+        // z[0] is -inf, so this stops at the first parabola at the latest
+        let mut s = intersection(f, v[k], q);
+        while s <= z[k] {
+            k -= 1;
+            s = intersection(f, v[k], q);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f32::INFINITY;
+    }
+    k = 0;
+    for (q, distance) in d.iter_mut().enumerate().take(n) {
+        while z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let p = v[k];
+        let offset = q as f32 - p as f32;
+        *distance = offset * offset + f[p];
+    }
+}
+
+/// Horizontal position of the intersection of the parabolas rooted at `p` and `q`
+#[expect(clippy::cast_precision_loss)]
+fn intersection(f: &[f32], p: usize, q: usize) -> f32 {
+    ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2. * (q - p) as f32)
+}
+
+/// The shader that renders [`SdfText`], with optional outline and glow.
+///
+/// An `SdfShader` is tied to the spread of the [`SdfFont`] it was created for, which
+/// limits the width of the outline and glow.
+#[derive(Debug)]
+pub struct SdfShader {
+    shader: FBox<Shader<'static>>,
+    /// Distance units per pixel at the base size
+    scale: f32,
+}
+
+impl SdfShader {
+    /// Compile the shader for `font`, without outline or glow.
+    ///
+    /// This fails if shaders are not available, see [`Shader::is_available`].
+    pub fn new(font: &SdfFont) -> SfResult<Self> {
+        let mut shader = Shader::from_memory(SDF_FRAGMENT_SHADER, ShaderType::Fragment)?;
+        shader.set_uniform_current_texture("texture")?;
+        let mut sdf_shader = Self {
+            shader,
+            scale: 1. / (2. * font.settings.spread as f32),
+        };
+        sdf_shader.set_outline(Color::TRANSPARENT, 0.)?;
+        sdf_shader.set_glow(Color::TRANSPARENT, 0.)?;
+        Ok(sdf_shader)
+    }
+
+    /// Set the outline of the text.
+    ///
+    /// `width` is in pixels at the base size of the font, so the actual outline is
+    /// `width * character_size / base_size` pixels wide. Together with the glow, it can't
+    /// be wider than the spread of the font.
+    pub fn set_outline(&mut self, color: Color, width: f32) -> SfResult<()> {
+        self.shader.set_uniform_vec4("outline_color", color)?;
+        self.shader
+            .set_uniform_float("outline_width", width * self.scale)
+    }
+
+    /// Set the glow around the outline of the text.
+    ///
+    /// `width` is in pixels at the base size of the font, like for [`set_outline`].
+    ///
+    /// [`set_outline`]: Self::set_outline
+    pub fn set_glow(&mut self, color: Color, width: f32) -> SfResult<()> {
+        self.shader.set_uniform_vec4("glow_color", color)?;
+        self.shader
+            .set_uniform_float("glow_width", width * self.scale)
+    }
+
+    /// Get the underlying shader.
+    #[must_use]
+    pub fn shader(&self) -> &Shader<'static> {
+        &self.shader
+    }
+}
+
+/// Text drawn from an [`SdfFont`], which stays crisp at any scale.
+///
+/// The character size is a float, since the glyphs are not rasterized for a specific size.
+/// `SdfText` must be drawn with an [`SdfShader`], see [`SdfText::set_shader`].
+#[derive(Debug, Clone)]
+pub struct SdfText<'s, 'f> {
+    string: String,
+    font: &'s SdfFont<'f>,
+    shader: Option<&'s SdfShader>,
+    character_size: f32,
+    fill_color: Color,
+    letter_spacing: f32,
+    line_spacing: f32,
+    transformation: Transformation,
+    vertices: Vec<Vertex>,
+    bounds: FloatRect,
+}
+
+impl<'s, 'f> SdfText<'s, 'f> {
+    /// Create a new text. Characters missing from the atlas are skipped.
+    #[must_use]
+    pub fn new(string: &str, font: &'s SdfFont<'f>, character_size: f32) -> Self {
+        let mut text = Self {
+            string: string.to_owned(),
+            font,
+            shader: None,
+            character_size,
+            fill_color: Color::WHITE,
+            letter_spacing: 1.,
+            line_spacing: 1.,
+            transformation: Transformation::default(),
+            vertices: Vec::new(),
+            bounds: FloatRect::default(),
+        };
+        text.update();
+        text
+    }
+
+    /// Set the string of the text.
+    pub fn set_string(&mut self, string: &str) {
+        string.clone_into(&mut self.string);
+        self.update();
+    }
+
+    /// Get the string of the text.
+    #[must_use]
+    pub fn string(&self) -> &str {
+        &self.string
+    }
+
+    /// Set the font of the text.
+    pub fn set_font(&mut self, font: &'s SdfFont<'f>) {
+        self.font = font;
+        self.update();
+    }
+
+    /// Get the font of the text.
+    #[must_use]
+    pub fn font(&self) -> &'s SdfFont<'f> {
+        self.font
+    }
+
+    /// Set the shader used to draw the text.
+    ///
+    /// Without a shader, the text is drawn with the shader of the render states, if any.
+    pub fn set_shader(&mut self, shader: Option<&'s SdfShader>) {
+        self.shader = shader;
+    }
+
+    /// Get the shader used to draw the text.
+    #[must_use]
+    pub fn shader(&self) -> Option<&'s SdfShader> {
+        self.shader
+    }
+
+    /// Set the size of the characters, in pixels.
+    pub fn set_character_size(&mut self, size: f32) {
+        self.character_size = size;
+        self.update();
+    }
+
+    /// Get the size of the characters, in pixels.
+    #[must_use]
+    pub fn character_size(&self) -> f32 {
+        self.character_size
+    }
+
+    /// Set the fill color of the text. By default, it is opaque white.
+    pub fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+        for vertex in &mut self.vertices {
+            vertex.color = color;
+        }
+    }
+
+    /// Get the fill color of the text.
+    #[must_use]
+    pub fn fill_color(&self) -> Color {
+        self.fill_color
+    }
+
+    /// Set the letter spacing factor. See [`Text::set_letter_spacing`].
+    ///
+    /// [`Text::set_letter_spacing`]: crate::graphics::Text::set_letter_spacing
+    pub fn set_letter_spacing(&mut self, factor: f32) {
+        self.letter_spacing = factor;
+        self.update();
+    }
+
+    /// Get the letter spacing factor.
+    #[must_use]
+    pub fn letter_spacing(&self) -> f32 {
+        self.letter_spacing
+    }
+
+    /// Set the line spacing factor. See [`Text::set_line_spacing`].
+    ///
+    /// [`Text::set_line_spacing`]: crate::graphics::Text::set_line_spacing
+    pub fn set_line_spacing(&mut self, factor: f32) {
+        self.line_spacing = factor;
+        self.update();
+    }
+
+    /// Get the line spacing factor.
+    #[must_use]
+    pub fn line_spacing(&self) -> f32 {
+        self.line_spacing
+    }
+
+    /// Get the local bounding rectangle of the text, including the spread of the glyphs.
+    #[must_use]
+    pub fn local_bounds(&self) -> FloatRect {
+        self.bounds
+    }
+
+    /// Get the global bounding rectangle of the text.
+    #[must_use]
+    pub fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(self.bounds)
+    }
+
+    fn update(&mut self) {
+        let base_size = self.font.settings.base_size;
+        let scale = self.character_size / base_size as f32;
+        let font = self.font.font;
+        let space = self.font.glyph(' ').map_or(0., |glyph| glyph.advance) * scale;
+        let letter_spacing = (space / 3.) * (self.letter_spacing - 1.);
+        let line_height = font.line_spacing(base_size) * scale * self.line_spacing;
+
+        self.vertices.clear();
+        let mut min = Vector2f::new(f32::INFINITY, f32::INFINITY);
+        let mut max = Vector2f::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+        let mut pen = Vector2f::new(0., self.character_size);
+        let mut prev = None;
+        for c in self.string.chars() {
+            if let Some(prev) = prev {
+                pen.x += font.kerning(u32::from(prev), u32::from(c), base_size) * scale;
+            }
+            prev = Some(c);
+            match c {
+                ' ' => pen.x += space + letter_spacing,
+                '\t' => pen.x += (space + letter_spacing) * 4.,
+                '\n' => {
+                    pen = Vector2f::new(0., pen.y + line_height);
+                    prev = None;
+                }
+                '\r' => {}
+                _ => {
+                    let Some(glyph) = self.font.glyph(c) else {
+                        continue;
+                    };
+                    if glyph.texture_rect.size.x > 0 {
+                        let top_left = pen + glyph.bounds.position * scale;
+                        let bottom_right = top_left + glyph.bounds.size * scale;
+                        let rect = glyph.texture_rect.as_other::<f32>();
+                        let uv_min = rect.position;
+                        let uv_max = rect.position + rect.size;
+                        let vertex = |x: f32, y: f32, u: f32, v: f32| {
+                            Vertex::new(Vector2f::new(x, y), self.fill_color, Vector2f::new(u, v))
+                        };
+                        let tl = vertex(top_left.x, top_left.y, uv_min.x, uv_min.y);
+                        let tr = vertex(bottom_right.x, top_left.y, uv_max.x, uv_min.y);
+                        let bl = vertex(top_left.x, bottom_right.y, uv_min.x, uv_max.y);
+                        let br = vertex(bottom_right.x, bottom_right.y, uv_max.x, uv_max.y);
+                        self.vertices.extend([tl, tr, bl, bl, tr, br]);
+                        min = Vector2f::new(min.x.min(top_left.x), min.y.min(top_left.y));
+                        max = Vector2f::new(max.x.max(bottom_right.x), max.y.max(bottom_right.y));
+                    }
+                    pen.x += glyph.advance * scale + letter_spacing;
+                }
+            }
+        }
+        self.bounds = if min.x <= max.x {
+            FloatRect::new(min, max - min)
+        } else {
+            FloatRect::default()
+        };
+    }
+}
+
+impl_transformable_via!(SdfText<'_, '_>, transformation);
+
+impl Drawable for SdfText<'_, '_> {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        let states = RenderStates {
+            transform: states.transform * *self.transform(),
+            texture: Some(&self.font.texture),
+            shader: self.shader.map(SdfShader::shader).or(states.shader),
+            ..*states
+        };
+        target.draw_primitives(&self.vertices, PrimitiveType::TRIANGLES, &states);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_transform_1d_single_point() {
+        let f = [f32::INFINITY, f32::INFINITY, 0., f32::INFINITY];
+        let mut d = [0.; 4];
+        let mut v = [0; 4];
+        let mut z = [0.; 5];
+        distance_transform_1d(&f, &mut d, &mut v, &mut z);
+        assert_eq!(d, [4., 1., 0., 1.]);
+    }
+
+    #[test]
+    fn distance_field_edges() {
+        // A 3x3 square in the middle of a 9x9 mask
+        let mut coverage = vec![false; 81];
+        for y in 3..6 {
+            for x in 3..6 {
+                coverage[y * 9 + x] = true;
+            }
+        }
+        let field = distance_field(&coverage, 9, 9, 4.);
+        // Inside is above the threshold, outside below, and it decreases with distance
+        assert!(field[4 * 9 + 4] > 128);
+        assert!(field[4 * 9 + 3] > 128);
+        assert!(field[4 * 9 + 2] < 128);
+        assert!(field[4 * 9 + 1] < field[4 * 9 + 2]);
+        assert!(field[0] < field[4 * 9 + 1]);
+    }
+}
@@ -5,7 +5,7 @@ use {
         cpp::FBox,
         ffi::graphics as ffi,
         graphics::{Texture, glsl},
-        system::InputStream,
+        system::{InputStream, err::diagnose},
     },
     std::{
         ffi::CString,
@@ -158,8 +158,10 @@ impl<'texture> Shader<'texture> {
     pub fn from_file(path: &str, type_: ShaderType) -> SfResult<FBox<Self>> {
         let path = CString::new(path)?;
         let mut new = Self::new()?;
-        unsafe { ffi::sfShader_loadFromFile_1(new.raw_mut(), path.as_ptr(), type_) }
-            .into_sf_result()?;
+        diagnose(|| {
+            unsafe { ffi::sfShader_loadFromFile_1(new.raw_mut(), path.as_ptr(), type_) }
+                .into_sf_result()
+        })?;
         Ok(new)
     }
 
@@ -173,10 +175,12 @@ impl<'texture> Shader<'texture> {
         let vert = CString::new(vert)?;
         let frag = CString::new(frag)?;
         let mut new = Self::new()?;
-        unsafe {
-            ffi::sfShader_loadFromFile_vert_frag(new.raw_mut(), vert.as_ptr(), frag.as_ptr())
-        }
-        .into_sf_result()?;
+        diagnose(|| {
+            unsafe {
+                ffi::sfShader_loadFromFile_vert_frag(new.raw_mut(), vert.as_ptr(), frag.as_ptr())
+            }
+            .into_sf_result()
+        })?;
         Ok(new)
     }
 
@@ -191,15 +195,17 @@ impl<'texture> Shader<'texture> {
         let geom = CString::new(geom)?;
         let frag = CString::new(frag)?;
         let mut new = Self::new()?;
-        unsafe {
-            ffi::sfShader_loadFromFile_all(
-                new.raw_mut(),
-                vert.as_ptr(),
-                geom.as_ptr(),
-                frag.as_ptr(),
-            )
-        }
-        .into_sf_result()?;
+        diagnose(|| {
+            unsafe {
+                ffi::sfShader_loadFromFile_all(
+                    new.raw_mut(),
+                    vert.as_ptr(),
+                    geom.as_ptr(),
+                    frag.as_ptr(),
+                )
+            }
+            .into_sf_result()
+        })?;
         Ok(new)
     }
 
@@ -213,8 +219,10 @@ impl<'texture> Shader<'texture> {
     pub fn from_memory(contents: &str, type_: ShaderType) -> SfResult<FBox<Self>> {
         let contents = CString::new(contents)?;
         let mut new = Self::new()?;
-        unsafe { ffi::sfShader_loadFromMemory_1(new.raw_mut(), contents.as_ptr(), type_) }
-            .into_sf_result()?;
+        diagnose(|| {
+            unsafe { ffi::sfShader_loadFromMemory_1(new.raw_mut(), contents.as_ptr(), type_) }
+                .into_sf_result()
+        })?;
         Ok(new)
     }
 
@@ -229,10 +237,12 @@ impl<'texture> Shader<'texture> {
         let frag = CString::new(frag)?;
         let mut new = Self::new()?;
 
-        unsafe {
-            ffi::sfShader_loadFromMemory_vert_frag(new.raw_mut(), vert.as_ptr(), frag.as_ptr())
-        }
-        .into_sf_result()?;
+        diagnose(|| {
+            unsafe {
+                ffi::sfShader_loadFromMemory_vert_frag(new.raw_mut(), vert.as_ptr(), frag.as_ptr())
+            }
+            .into_sf_result()
+        })?;
         Ok(new)
     }
 
@@ -247,15 +257,17 @@ impl<'texture> Shader<'texture> {
         let geom = CString::new(geom)?;
         let frag = CString::new(frag)?;
         let mut new = Self::new()?;
-        unsafe {
-            ffi::sfShader_loadFromMemory_all(
-                new.raw_mut(),
-                vert.as_ptr(),
-                geom.as_ptr(),
-                frag.as_ptr(),
-            )
-        }
-        .into_sf_result()?;
+        diagnose(|| {
+            unsafe {
+                ffi::sfShader_loadFromMemory_all(
+                    new.raw_mut(),
+                    vert.as_ptr(),
+                    geom.as_ptr(),
+                    frag.as_ptr(),
+                )
+            }
+            .into_sf_result()
+        })?;
         Ok(new)
     }
 
@@ -268,8 +280,12 @@ impl<'texture> Shader<'texture> {
     pub fn from_stream<T: Read + Seek>(mut source: T, type_: ShaderType) -> SfResult<FBox<Self>> {
         let source = InputStream::new(&mut source);
         let mut new = Self::new()?;
-        unsafe { ffi::sfShader_loadFromStream_1(new.raw_mut(), source.stream.0.as_ptr(), type_) }
-            .into_sf_result()?;
+        diagnose(|| {
+            unsafe {
+                ffi::sfShader_loadFromStream_1(new.raw_mut(), source.stream.0.as_ptr(), type_)
+            }
+            .into_sf_result()
+        })?;
         Ok(new)
     }
 
@@ -288,14 +304,16 @@ impl<'texture> Shader<'texture> {
         let frag = InputStream::new(&mut frag);
         let mut new = Self::new()?;
 
-        unsafe {
-            ffi::sfShader_loadFromStream_vert_frag(
-                new.raw_mut(),
-                vert.stream.0.as_ptr(),
-                frag.stream.0.as_ptr(),
-            )
-        }
-        .into_sf_result()?;
+        diagnose(|| {
+            unsafe {
+                ffi::sfShader_loadFromStream_vert_frag(
+                    new.raw_mut(),
+                    vert.stream.0.as_ptr(),
+                    frag.stream.0.as_ptr(),
+                )
+            }
+            .into_sf_result()
+        })?;
         Ok(new)
     }
 
@@ -315,15 +333,17 @@ impl<'texture> Shader<'texture> {
         let geom = InputStream::new(&mut geom);
         let frag = InputStream::new(&mut frag);
         let mut new = Self::new()?;
-        unsafe {
-            ffi::sfShader_loadFromStream_all(
-                new.raw_mut(),
-                vert.stream.0.as_ptr(),
-                geom.stream.0.as_ptr(),
-                frag.stream.0.as_ptr(),
-            )
-        }
-        .into_sf_result()?;
+        diagnose(|| {
+            unsafe {
+                ffi::sfShader_loadFromStream_all(
+                    new.raw_mut(),
+                    vert.stream.0.as_ptr(),
+                    geom.stream.0.as_ptr(),
+                    frag.stream.0.as_ptr(),
+                )
+            }
+            .into_sf_result()
+        })?;
         Ok(new)
     }
 
@@ -672,7 +692,7 @@ impl<'texture> Shader<'texture> {
         let ptr: *const Self = self;
         ptr.cast()
     }
-    fn raw_mut(&mut self) -> *mut ffi::sfShader {
+    pub(super) fn raw_mut(&mut self) -> *mut ffi::sfShader {
         let ptr: *mut Self = self;
         ptr.cast()
     }
@@ -0,0 +1,635 @@
+use {
+    crate::{
+        SfError,
+        cpp::FBox,
+        graphics::{Shader, ShaderType},
+    },
+    std::{
+        error::Error,
+        fmt::{self, Write as _},
+        fs, io,
+        path::{Path, PathBuf},
+        time::SystemTime,
+    },
+};
+
+/// Resolves `#include` directives and injects `#define` directives in GLSL sources.
+///
+/// `#include "file.glsl"` is searched relative to the directory of the including file, then in
+/// [`include_dirs`]. `#include <file.glsl>` is only searched in [`include_dirs`].
+/// A file containing `#pragma once` is included only once.
+///
+/// The [`defines`] are added at the top of the source, after its `#version` directive.
+///
+/// The preprocessor adds `#line` directives where files start and end, so that the line
+/// numbers in compile errors are the ones of the original files. GLSL only accepts numbers as
+/// file names in `#line`, so each file is identified by its index in a [`SourceMap`], which
+/// [`SourceMap::translate`] turns back into paths.
+///
+/// [`include_dirs`]: ShaderPreprocessor::include_dirs
+/// [`defines`]: ShaderPreprocessor::defines
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// let mut preprocessor = ShaderPreprocessor::default();
+/// preprocessor.include_dirs.push("shaders/common".into());
+/// preprocessor.defines.push(("BLUR_SAMPLES".into(), "9".into()));
+/// let mut source_map = SourceMap::default();
+/// let source = preprocessor.process_file("shaders/blur.frag", &mut source_map).unwrap();
+/// let shader = Shader::from_memory(&source, ShaderType::Fragment).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ShaderPreprocessor {
+    /// Directories searched for included files
+    pub include_dirs: Vec<PathBuf>,
+    /// Macros defined at the top of the source, as name and value
+    pub defines: Vec<(String, String)>,
+}
+
+impl ShaderPreprocessor {
+    /// Read the file at `path`, and preprocess it.
+    ///
+    /// The files that are read are added to `source_map`. Use the same source map for all the
+    /// stages of a shader, so that it can translate the errors of all of them.
+    pub fn process_file(
+        &self,
+        path: impl AsRef<Path>,
+        source_map: &mut SourceMap,
+    ) -> Result<String, ShaderSourceError> {
+        let path = path.as_ref();
+        let source = read(path)?;
+        self.process_str(&source, path, source_map)
+    }
+
+    /// Preprocess `source`, as if it was read from `path`.
+    ///
+    /// `path` doesn't have to exist: it is used to find the files included with quotes, and
+    /// to name `source` in `source_map`.
+    pub fn process_str(
+        &self,
+        source: &str,
+        path: impl AsRef<Path>,
+        source_map: &mut SourceMap,
+    ) -> Result<String, ShaderSourceError> {
+        let path = path.as_ref();
+        let mut output = String::with_capacity(source.len());
+        // The #version directive must come before anything else
+        let version = source
+            .lines()
+            .enumerate()
+            .find(|(_, line)| line.trim_start().starts_with('#'))
+            .filter(|(_, line)| directive(line).0 == "version");
+        let mut state = State {
+            source_map,
+            stack: Vec::new(),
+            once: Vec::new(),
+            // Before GLSL 3.30, `#line n` makes the next line n + 1
+            line_offset: 1,
+        };
+        let mut first_line = 0;
+        if let Some((index, line)) = version {
+            let mut words = directive(line).1.split_whitespace();
+            let number: u32 = words
+                .next()
+                .and_then(|word| word.parse().ok())
+                .unwrap_or(110);
+            let es = words.next() == Some("es");
+            if number >= 330 || (es && number >= 300) {
+                state.line_offset = 0;
+            }
+            for line in source.lines().take(index + 1) {
+                output.push_str(line);
+                output.push('\n');
+            }
+            first_line = index + 1;
+        }
+        for (name, value) in &self.defines {
+            let _ = writeln!(output, "#define {name} {value}");
+        }
+        self.expand(source, path, first_line, &mut state, &mut output)?;
+        Ok(output)
+    }
+
+    /// Append the lines of `source` from `first_line` to `output`, replacing the includes.
+    fn expand(
+        &self,
+        source: &str,
+        path: &Path,
+        first_line: usize,
+        state: &mut State,
+        output: &mut String,
+    ) -> Result<(), ShaderSourceError> {
+        let index = state.source_map.add(path);
+        state.line_directive(first_line, index, output);
+        state.stack.push(canonical(path));
+        for (number, line) in source.lines().enumerate().skip(first_line) {
+            match directive(line) {
+                ("include", argument) => {
+                    let error = |message: String| ShaderSourceError::Include {
+                        path: path.to_owned(),
+                        line: number + 1,
+                        message,
+                    };
+                    let (name, quoted) = match argument.chars().next() {
+                        Some('"') => (argument[1..].split_once('"'), true),
+                        Some('<') => (argument[1..].split_once('>'), false),
+                        _ => (None, false),
+                    };
+                    let Some((name, _)) = name else {
+                        return Err(error(format!("malformed include `{argument}`")));
+                    };
+                    let Some(included) = self.resolve(path, name, quoted) else {
+                        return Err(error(format!("cannot find `{name}`")));
+                    };
+                    let key = canonical(&included);
+                    if state.stack.contains(&key) {
+                        return Err(error(format!("`{name}` includes itself")));
+                    }
+                    if !state.once.contains(&key) {
+                        let source = read(&included)?;
+                        self.expand(&source, &included, 0, state, output)?;
+                        state.line_directive(number + 1, index, output);
+                    } else {
+                        // Keep the line count of the file
+                        output.push('\n');
+                    }
+                }
+                ("pragma", "once") => {
+                    if let Some(key) = state.stack.last() {
+                        state.once.push(key.clone());
+                    }
+                    output.push('\n');
+                }
+                _ => {
+                    output.push_str(line);
+                    output.push('\n');
+                }
+            }
+        }
+        let _ = state.stack.pop();
+        Ok(())
+    }
+
+    fn resolve(&self, including: &Path, name: &str, quoted: bool) -> Option<PathBuf> {
+        let local = quoted.then(|| including.parent().unwrap_or(Path::new("")).join(name));
+        local
+            .into_iter()
+            .chain(self.include_dirs.iter().map(|dir| dir.join(name)))
+            .find(|path| path.is_file())
+    }
+}
+
+struct State<'a> {
+    source_map: &'a mut SourceMap,
+    /// Canonical paths of the files being included, to detect recursion
+    stack: Vec<PathBuf>,
+    /// Canonical paths of the files containing `#pragma once`
+    once: Vec<PathBuf>,
+    line_offset: usize,
+}
+
+impl State<'_> {
+    /// Make the next line of `output` line `line` (from 0) of file `index`.
+    fn line_directive(&self, line: usize, index: usize, output: &mut String) {
+        let _ = writeln!(output, "#line {} {index}", line + 1 - self.line_offset);
+    }
+}
+
+/// Split a preprocessor directive into its name and argument, or return empty strings if
+/// `line` isn't one.
+fn directive(line: &str) -> (&str, &str) {
+    let Some(rest) = line.trim_start().strip_prefix('#') else {
+        return ("", "");
+    };
+    let rest = rest.trim_start();
+    let end = rest
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(rest.len());
+    (&rest[..end], rest[end..].trim())
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_owned())
+}
+
+fn read(path: &Path) -> Result<String, ShaderSourceError> {
+    fs::read_to_string(path).map_err(|error| ShaderSourceError::Io {
+        path: path.to_owned(),
+        error,
+    })
+}
+
+/// The files that make up preprocessed shader sources, by index.
+///
+/// The `#line` directives added by [`ShaderPreprocessor`] identify files by their index here.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    files: Vec<PathBuf>,
+}
+
+impl SourceMap {
+    /// Get the files, in index order.
+    #[must_use]
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Get the file with the index `index`.
+    #[must_use]
+    pub fn file(&self, index: usize) -> Option<&Path> {
+        self.files.get(index).map(PathBuf::as_path)
+    }
+
+    /// Replace the locations in a shader compile log by file paths.
+    ///
+    /// Locations are written `file:line` or `file(line)` by the GLSL compilers, where `file` is
+    /// an index in this source map. They become `path:line`.
+    ///
+    /// ```
+    /// # use sfml::graphics::{ShaderPreprocessor, SourceMap};
+    /// let mut source_map = SourceMap::default();
+    /// ShaderPreprocessor::default()
+    ///     .process_str("void main() {}", "blur.frag", &mut source_map)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     source_map.translate("ERROR: 0:12: 'vec' : undeclared identifier"),
+    ///     "ERROR: blur.frag:12: 'vec' : undeclared identifier",
+    /// );
+    /// assert_eq!(
+    ///     source_map.translate("0(12) : error C0000: syntax error"),
+    ///     "blur.frag:12 : error C0000: syntax error",
+    /// );
+    /// ```
+    #[must_use]
+    pub fn translate(&self, log: &str) -> String {
+        let mut output = String::with_capacity(log.len());
+        let mut rest = log;
+        let mut boundary = true;
+        while let Some(c) = rest.chars().next() {
+            if boundary && c.is_ascii_digit() {
+                if let Some((path, line, len)) = parse_location(rest)
+                    .and_then(|(file, line, len)| Some((self.file(file)?, line, len)))
+                {
+                    let _ = write!(output, "{}:{line}", path.display());
+                    rest = &rest[len..];
+                    boundary = false;
+                    continue;
+                }
+            }
+            boundary = !(c.is_alphanumeric() || c == '_' || c == '.');
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        output
+    }
+
+    /// Add `path` if it's not there yet, and get its index.
+    fn add(&mut self, path: &Path) -> usize {
+        if let Some(index) = self.files.iter().position(|file| file == path) {
+            return index;
+        }
+        self.files.push(path.to_owned());
+        self.files.len() - 1
+    }
+}
+
+/// Parse a `file:line` or `file(line)` location at the start of `s`, and get its file, line
+/// and length.
+fn parse_location(s: &str) -> Option<(usize, usize, usize)> {
+    let digits = |s: &str| s.bytes().take_while(u8::is_ascii_digit).count();
+    let file_len = digits(s);
+    let file = s[..file_len].parse().ok()?;
+    let rest = &s[file_len..];
+    let parenthesized = match rest.as_bytes().first()? {
+        b':' => false,
+        b'(' => true,
+        _ => return None,
+    };
+    let rest = &rest[1..];
+    let line_len = digits(rest);
+    let line = rest[..line_len].parse().ok()?;
+    let mut len = file_len + 1 + line_len;
+    if parenthesized {
+        if rest.as_bytes().get(line_len) != Some(&b')') {
+            return None;
+        }
+        len += 1;
+    }
+    Some((file, line, len))
+}
+
+/// A [`Shader`] built from preprocessed source files, rebuilt when they change.
+///
+/// Call [`poll`] regularly, for example once per frame, to check the modification times of
+/// the files, including the ones that are included. When a file changed, the shader is
+/// rebuilt. If the new shader fails to compile, the old one is kept, so that a typo doesn't
+/// break the running program.
+///
+/// The uniforms are not carried over to the rebuilt shader: set them again when [`poll`]
+/// returns `Ok(true)`, and create new [`UniformBinding`]s for it.
+///
+/// [`UniformBinding`]: crate::graphics::UniformBinding
+///
+/// [`poll`]: ShaderWatcher::poll
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # let mut window: RenderWindow = unimplemented!();
+/// # let sprite: Sprite = unimplemented!();
+/// let mut watcher = ShaderWatcher::from_file(
+///     ShaderPreprocessor::default(),
+///     "shaders/wave.frag",
+///     ShaderType::Fragment,
+/// )
+/// .unwrap();
+/// loop {
+///     match watcher.poll() {
+///         Ok(true) => println!("Shader reloaded"),
+///         Ok(false) => {}
+///         Err(error) => eprintln!("Keeping the old shader: {error}"),
+///     }
+///     let states = RenderStates {
+///         shader: Some(watcher.shader()),
+///         ..Default::default()
+///     };
+///     window.draw_with_renderstates(&sprite, &states);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ShaderWatcher<'texture> {
+    preprocessor: ShaderPreprocessor,
+    stages: Stages,
+    shader: FBox<Shader<'texture>>,
+    source_map: SourceMap,
+    watched: Vec<PathBuf>,
+    modified: Vec<Option<SystemTime>>,
+}
+
+#[derive(Debug)]
+enum Stages {
+    Single(PathBuf, ShaderType),
+    VertFrag(PathBuf, PathBuf),
+    All(PathBuf, PathBuf, PathBuf),
+}
+
+/// Creation
+impl<'texture> ShaderWatcher<'texture> {
+    /// Build a vertex, geometry or fragment shader from a file, and watch it.
+    pub fn from_file(
+        preprocessor: ShaderPreprocessor,
+        path: impl Into<PathBuf>,
+        type_: ShaderType,
+    ) -> Result<Self, ShaderSourceError> {
+        Self::new(preprocessor, Stages::Single(path.into(), type_))
+    }
+
+    /// Build a shader from vertex and fragment shader files, and watch them.
+    pub fn from_file_vert_frag(
+        preprocessor: ShaderPreprocessor,
+        vert: impl Into<PathBuf>,
+        frag: impl Into<PathBuf>,
+    ) -> Result<Self, ShaderSourceError> {
+        Self::new(preprocessor, Stages::VertFrag(vert.into(), frag.into()))
+    }
+
+    /// Build a shader from vertex, geometry and fragment shader files, and watch them.
+    pub fn from_file_all(
+        preprocessor: ShaderPreprocessor,
+        vert: impl Into<PathBuf>,
+        geom: impl Into<PathBuf>,
+        frag: impl Into<PathBuf>,
+    ) -> Result<Self, ShaderSourceError> {
+        Self::new(
+            preprocessor,
+            Stages::All(vert.into(), geom.into(), frag.into()),
+        )
+    }
+
+    fn new(preprocessor: ShaderPreprocessor, stages: Stages) -> Result<Self, ShaderSourceError> {
+        let mut source_map = SourceMap::default();
+        let shader = build(&preprocessor, &stages, &mut source_map)?;
+        let watched = source_map.files().to_vec();
+        let modified = watched.iter().map(|path| modified(path)).collect();
+        Ok(Self {
+            preprocessor,
+            stages,
+            shader,
+            source_map,
+            watched,
+            modified,
+        })
+    }
+}
+
+/// Reloading
+impl<'texture> ShaderWatcher<'texture> {
+    /// Rebuild the shader if one of its files was modified since the last check.
+    ///
+    /// Returns whether the shader was rebuilt. On error, the old shader is kept, and the
+    /// files are not rebuilt again until they change.
+    pub fn poll(&mut self) -> Result<bool, ShaderSourceError> {
+        let modified: Vec<_> = self.watched.iter().map(|path| modified(path)).collect();
+        if modified == self.modified {
+            return Ok(false);
+        }
+        self.modified = modified;
+        self.reload().map(|()| true)
+    }
+
+    /// Rebuild the shader now, for example after changing the [`preprocessor`].
+    ///
+    /// On error, the old shader is kept.
+    ///
+    /// [`preprocessor`]: ShaderWatcher::preprocessor_mut
+    pub fn reload(&mut self) -> Result<(), ShaderSourceError> {
+        let mut source_map = SourceMap::default();
+        let result = build(&self.preprocessor, &self.stages, &mut source_map);
+        if let Err(ShaderSourceError::Io { .. } | ShaderSourceError::Include { .. }) = result {
+            // The source map is incomplete, keep watching the old files too
+            for path in source_map.files() {
+                if !self.watched.contains(path) {
+                    self.watched.push(path.clone());
+                    self.modified.push(modified(path));
+                }
+            }
+        } else {
+            self.watched = source_map.files().to_vec();
+            self.modified = self.watched.iter().map(|path| modified(path)).collect();
+            self.source_map = source_map;
+        }
+        self.shader = result?;
+        Ok(())
+    }
+}
+
+/// Access
+impl<'texture> ShaderWatcher<'texture> {
+    /// Get the current shader.
+    #[must_use]
+    pub fn shader(&self) -> &Shader<'texture> {
+        &self.shader
+    }
+
+    /// Get the current shader, to set its uniforms.
+    pub fn shader_mut(&mut self) -> &mut Shader<'texture> {
+        &mut self.shader
+    }
+
+    /// Get the source map of the last build, to translate compile errors.
+    #[must_use]
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    /// Get the preprocessor, to change its defines or include directories.
+    ///
+    /// The changes are taken into account by the next build. Call [`ShaderWatcher::reload`]
+    /// to rebuild at once.
+    pub fn preprocessor_mut(&mut self) -> &mut ShaderPreprocessor {
+        &mut self.preprocessor
+    }
+}
+
+fn build<'texture>(
+    preprocessor: &ShaderPreprocessor,
+    stages: &Stages,
+    source_map: &mut SourceMap,
+) -> Result<FBox<Shader<'texture>>, ShaderSourceError> {
+    let mut process = |path: &Path| preprocessor.process_file(path, source_map);
+    match stages {
+        Stages::Single(path, type_) => Shader::from_memory(&process(path)?, *type_),
+        Stages::VertFrag(vert, frag) => {
+            Shader::from_memory_vert_frag(&process(vert)?, &process(frag)?)
+        }
+        Stages::All(vert, geom, frag) => {
+            Shader::from_memory_all(&process(vert)?, &process(geom)?, &process(frag)?)
+        }
+    }
+    .map_err(|error| match error {
+        SfError::Diagnostic(log) => {
+            ShaderSourceError::Compile(SfError::Diagnostic(source_map.translate(&log)))
+        }
+        error => ShaderSourceError::Compile(error),
+    })
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Error that can occur when preprocessing or building a shader.
+#[derive(Debug)]
+pub enum ShaderSourceError {
+    /// A source file could not be read
+    Io {
+        /// Path of the file
+        path: PathBuf,
+        /// Error that happened when reading it
+        error: io::Error,
+    },
+    /// An `#include` directive is malformed, its file can't be found, or it includes itself
+    Include {
+        /// Path of the file containing the directive
+        path: PathBuf,
+        /// Line of the directive, from 1
+        line: usize,
+        /// What is wrong with the directive
+        message: String,
+    },
+    /// The preprocessed shader failed to compile.
+    ///
+    /// The file locations in the compile log of [`SfError::Diagnostic`] are translated with
+    /// the [`SourceMap`].
+    Compile(SfError),
+}
+
+impl Error for ShaderSourceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::Include { .. } => None,
+            Self::Compile(error) => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for ShaderSourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "failed to read `{}`: {error}", path.display()),
+            Self::Include {
+                path,
+                line,
+                message,
+            } => write!(f, "{}:{line}: {message}", path.display()),
+            Self::Compile(error) => write!(f, "failed to compile shader: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defines_after_version() -> Result<(), Box<dyn Error>> {
+        let preprocessor = ShaderPreprocessor {
+            include_dirs: Vec::new(),
+            defines: vec![("SAMPLES".into(), "4".into())],
+        };
+        let mut source_map = SourceMap::default();
+        let source = "// Blur\n#version 330\nvoid main() {}\n";
+        let output = preprocessor.process_str(source, "blur.frag", &mut source_map)?;
+        assert_eq!(
+            output,
+            "// Blur\n#version 330\n#define SAMPLES 4\n#line 3 0\nvoid main() {}\n"
+        );
+        assert_eq!(source_map.files(), [PathBuf::from("blur.frag")]);
+        Ok(())
+    }
+
+    #[test]
+    fn includes_keep_line_numbers() -> Result<(), Box<dyn Error>> {
+        let dir = std::env::temp_dir().join(format!("sfml-shader-include-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        fs::write(
+            dir.join("common.glsl"),
+            "#pragma once\nfloat luma(vec3 c);\n",
+        )?;
+        let source = "#include \"common.glsl\"\n#include \"common.glsl\"\nvoid main() {}\n";
+        let mut source_map = SourceMap::default();
+        let output = ShaderPreprocessor::default().process_str(
+            source,
+            dir.join("main.frag"),
+            &mut source_map,
+        );
+        fs::remove_dir_all(&dir)?;
+        // Without #version, `#line n` numbers the next line n + 1
+        assert_eq!(
+            output?,
+            "#line 0 0\n#line 0 1\n\nfloat luma(vec3 c);\n#line 1 0\n\nvoid main() {}\n"
+        );
+        assert_eq!(source_map.files().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_include() {
+        let mut source_map = SourceMap::default();
+        let result = ShaderPreprocessor::default().process_str(
+            "\n#include <missing.glsl>\n",
+            "main.frag",
+            &mut source_map,
+        );
+        let Err(error) = result else {
+            panic!("the include should be missing");
+        };
+        assert_eq!(error.to_string(), "main.frag:2: cannot find `missing.glsl`");
+    }
+}
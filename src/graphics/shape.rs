@@ -98,4 +98,14 @@ pub trait Shape<'texture>: Drawable + Transformable {
             }
         })
     }
+
+    /// Returns an iterator over all points, in global coordinates
+    ///
+    /// Unlike [`Shape::points`], the points take into account the transformations of the shape.
+    #[must_use]
+    fn world_points(&self) -> impl Iterator<Item = Vector2f> {
+        let transform = *self.transform();
+        self.points()
+            .map(move |point| transform.transform_point(point))
+    }
 }
@@ -0,0 +1,141 @@
+use {
+    crate::{
+        graphics::{CustomShape, Shape, Texture, custom_shape::impl_shape_via},
+        system::Vector2f,
+    },
+    std::f32::consts::{FRAC_PI_2, PI},
+};
+
+/// Specialized shape representing a star.
+///
+/// The star has a number of branches, whose tips are on a circle of the outer radius and
+/// whose valleys are on a circle of the inner radius. The first branch points up.
+///
+/// The local origin of the star is the top-left corner of the bounding box of its outer
+/// circle, like for [`CircleShape`].
+///
+/// [`CircleShape`]: crate::graphics::CircleShape
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut star = StarShape::new(5, 50., 20.);
+/// star.set_fill_color(Color::YELLOW);
+/// window.draw(&star);
+/// ```
+#[derive(Debug)]
+pub struct StarShape<'s> {
+    shape: CustomShape<'s>,
+    branch_count: usize,
+    outer_radius: f32,
+    inner_radius: f32,
+}
+
+/// Creation
+impl<'s> StarShape<'s> {
+    /// Create a star with `branch_count` branches.
+    #[must_use]
+    pub fn new(branch_count: usize, outer_radius: f32, inner_radius: f32) -> Self {
+        let mut shape = Self {
+            shape: CustomShape::new(Box::new(Vec::new())),
+            branch_count,
+            outer_radius,
+            inner_radius,
+        };
+        shape.update();
+        shape
+    }
+
+    /// Create a star with a texture.
+    #[must_use]
+    pub fn with_texture(
+        branch_count: usize,
+        outer_radius: f32,
+        inner_radius: f32,
+        texture: &'s Texture,
+    ) -> Self {
+        let mut shape = Self::new(branch_count, outer_radius, inner_radius);
+        shape.set_texture(texture, true);
+        shape
+    }
+}
+
+/// Properties
+impl StarShape<'_> {
+    /// Set the number of branches of the star.
+    pub fn set_branch_count(&mut self, count: usize) {
+        self.branch_count = count;
+        self.update();
+    }
+
+    /// Get the number of branches of the star.
+    #[must_use]
+    pub fn branch_count(&self) -> usize {
+        self.branch_count
+    }
+
+    /// Set the radius of the circle going through the tips of the branches.
+    pub fn set_outer_radius(&mut self, radius: f32) {
+        self.outer_radius = radius;
+        self.update();
+    }
+
+    /// Get the radius of the circle going through the tips of the branches.
+    #[must_use]
+    pub fn outer_radius(&self) -> f32 {
+        self.outer_radius
+    }
+
+    /// Set the radius of the circle going through the valleys between the branches.
+    pub fn set_inner_radius(&mut self, radius: f32) {
+        self.inner_radius = radius;
+        self.update();
+    }
+
+    /// Get the radius of the circle going through the valleys between the branches.
+    #[must_use]
+    pub fn inner_radius(&self) -> f32 {
+        self.inner_radius
+    }
+
+    fn update(&mut self) {
+        let points = points(self.outer_radius, self.inner_radius, self.branch_count);
+        self.shape.set_points(Box::new(points));
+    }
+}
+
+impl_shape_via!(StarShape, shape);
+
+fn points(outer_radius: f32, inner_radius: f32, branch_count: usize) -> Vec<Vector2f> {
+    let center = Vector2f::new(outer_radius, outer_radius);
+    (0..2 * branch_count)
+        .map(|i| {
+            let radius = if i % 2 == 0 {
+                outer_radius
+            } else {
+                inner_radius
+            };
+            let (sin, cos) = (i as f32 * PI / branch_count as f32 - FRAC_PI_2).sin_cos();
+            center + Vector2f::new(cos, sin) * radius
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tips_and_valleys() {
+        let points = points(20., 8., 5);
+        assert_eq!(points.len(), 10);
+        assert!((points[0] - Vector2f::new(20., 0.)).length() < 1e-4);
+        let center = Vector2f::new(20., 20.);
+        for (i, point) in points.into_iter().enumerate() {
+            let expected = if i % 2 == 0 { 20. } else { 8. };
+            assert!(((point - center).length() - expected).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! Golden-image tests for drawing code.
+//!
+//! A [`GoldenTest`] renders a closure into a [`RenderTexture`] of a fixed size, and compares
+//! the result with a reference PNG image, allowing small differences between GPUs and
+//! drivers. When the images differ, the rendered image and an image highlighting the
+//! differences are written next to each other, to see what changed.
+//!
+//! The reference images are created by running the tests with the `SFML_UPDATE_GOLDEN`
+//! environment variable set to `1`, which overwrites them with the rendered images. Check
+//! them before committing them.
+//!
+//! Rendering requires an OpenGL context, but no GPU: on a headless CI machine, run the tests
+//! under a virtual X server like Xvfb, with Mesa's llvmpipe software renderer
+//! (`LIBGL_ALWAYS_SOFTWARE=1 xvfb-run cargo test`).
+//!
+//! # Usage example
+//!
+//! ```no_run
+//! use sfml::graphics::{CircleShape, Color, RenderTarget, Shape, testing::GoldenTest};
+//!
+//! #[test]
+//! fn red_circle() {
+//!     let mut circle = CircleShape::new(20., 30);
+//!     circle.set_fill_color(Color::RED);
+//!     GoldenTest::new((64, 64)).assert("red_circle", |target| target.draw(&circle));
+//! }
+//! ```
+
+use {
+    crate::{
+        SfError, SfResult,
+        cpp::FBox,
+        graphics::{Color, Image, RenderTarget, RenderTexture},
+        system::Vector2u,
+    },
+    std::{
+        env,
+        error::Error,
+        fmt, fs, io,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Renders a closure and compares the result with a reference image.
+///
+/// The settings are public fields, which can be changed after [`GoldenTest::new`].
+#[derive(Debug, Clone)]
+pub struct GoldenTest {
+    /// Size of the render texture, in pixels
+    pub size: Vector2u,
+    /// Color the render texture is cleared with before rendering
+    pub clear_color: Color,
+    /// Largest difference allowed between two components of a pixel, for the pixels to be
+    /// considered the same
+    pub tolerance: u8,
+    /// Number of pixels that can differ before the test fails
+    pub max_differing_pixels: usize,
+    /// Directory of the reference images
+    pub reference_dir: PathBuf,
+    /// Directory where the rendered and difference images are written when a test fails
+    pub output_dir: PathBuf,
+}
+
+impl GoldenTest {
+    /// Create a test rendering into a render texture of `size`.
+    ///
+    /// By default, the render texture is cleared in transparent black, the tolerance is 2, no
+    /// pixel can differ, the reference images are in `tests/golden`, and the failures are
+    /// written to `target/golden`, both relative to the current directory, which is the
+    /// package directory when running `cargo test`.
+    #[must_use]
+    pub fn new(size: impl Into<Vector2u>) -> Self {
+        Self {
+            size: size.into(),
+            clear_color: Color::TRANSPARENT,
+            tolerance: 2,
+            max_differing_pixels: 0,
+            reference_dir: PathBuf::from("tests/golden"),
+            output_dir: PathBuf::from("target/golden"),
+        }
+    }
+
+    /// Render `draw` into a new render texture, and copy the result into an image.
+    pub fn render(&self, draw: impl FnOnce(&mut RenderTexture)) -> SfResult<FBox<Image>> {
+        let mut target = RenderTexture::new(self.size)?;
+        target.clear(self.clear_color);
+        draw(&mut target);
+        target.display();
+        target.texture().copy_to_image()
+    }
+
+    /// Render `draw`, and compare the result with the reference image `name.png`.
+    ///
+    /// If the images differ, or if the reference is missing, the rendered image is written to
+    /// `name.actual.png` in the output directory. If they differ, the differing pixels are
+    /// also drawn in red over the reference in `name.diff.png`.
+    ///
+    /// If the `SFML_UPDATE_GOLDEN` environment variable is `1`, the reference image is
+    /// overwritten with the rendered one instead.
+    pub fn check(
+        &self,
+        name: &str,
+        draw: impl FnOnce(&mut RenderTexture),
+    ) -> Result<(), GoldenError> {
+        let actual = self.render(draw)?;
+        let reference = self.reference_dir.join(format!("{name}.png"));
+        if env::var_os("SFML_UPDATE_GOLDEN").is_some_and(|value| value == "1") {
+            save(&actual, &reference)?;
+            return Ok(());
+        }
+        if !reference.exists() {
+            save(&actual, &self.output_path(name, "actual"))?;
+            return Err(GoldenError::MissingReference(reference));
+        }
+        let expected = Image::from_file(path_str(&reference)?)?;
+        if expected.size() != actual.size() {
+            save(&actual, &self.output_path(name, "actual"))?;
+            return Err(GoldenError::SizeMismatch {
+                expected: expected.size(),
+                actual: actual.size(),
+            });
+        }
+        let comparison = compare(actual.pixel_data(), expected.pixel_data(), self.tolerance);
+        if comparison.differing_pixels <= self.max_differing_pixels {
+            return Ok(());
+        }
+        save(&actual, &self.output_path(name, "actual"))?;
+        let diff_path = self.output_path(name, "diff");
+        let mut diff = Image::new()?;
+        // SAFETY: the comparison has 4 bytes for each pixel of `actual`, which has the same size
+        // as `expected`
+        unsafe { diff.resize_with_pixels_unchecked(actual.size(), &comparison.diff) };
+        save(&diff, &diff_path)?;
+        Err(GoldenError::Mismatch {
+            differing_pixels: comparison.differing_pixels,
+            max_difference: comparison.max_difference,
+            diff: diff_path,
+        })
+    }
+
+    /// Like [`GoldenTest::check`], but panics with a readable message if the check fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`GoldenTest::check`] fails.
+    #[track_caller]
+    pub fn assert(&self, name: &str, draw: impl FnOnce(&mut RenderTexture)) {
+        if let Err(error) = self.check(name, draw) {
+            panic!("golden test `{name}` failed: {error}");
+        }
+    }
+
+    fn output_path(&self, name: &str, kind: &str) -> PathBuf {
+        self.output_dir.join(format!("{name}.{kind}.png"))
+    }
+}
+
+fn path_str(path: &Path) -> Result<&str, GoldenError> {
+    path.to_str()
+        .ok_or_else(|| GoldenError::Io(io::Error::other("the path is not valid UTF-8")))
+}
+
+fn save(image: &Image, path: &Path) -> Result<(), GoldenError> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(GoldenError::Io)?;
+    }
+    Ok(image.save_to_file(path_str(path)?)?)
+}
+
+struct Comparison {
+    differing_pixels: usize,
+    max_difference: u8,
+    /// The differing pixels in red, over the expected image in faded gray
+    diff: Vec<u8>,
+}
+
+fn compare(actual: &[u8], expected: &[u8], tolerance: u8) -> Comparison {
+    let mut comparison = Comparison {
+        differing_pixels: 0,
+        max_difference: 0,
+        diff: Vec::with_capacity(expected.len()),
+    };
+    for (actual, expected) in actual.chunks_exact(4).zip(expected.chunks_exact(4)) {
+        let difference = actual
+            .iter()
+            .zip(expected)
+            .map(|(a, e)| a.abs_diff(*e))
+            .max()
+            .unwrap_or(0);
+        comparison.max_difference = comparison.max_difference.max(difference);
+        if difference > tolerance {
+            comparison.differing_pixels += 1;
+            comparison.diff.extend([255, 0, 0, 255]);
+        } else {
+            let gray =
+                (u16::from(expected[0]) + u16::from(expected[1]) + u16::from(expected[2])) / 3;
+            let faded = u8::try_from(gray / 4 + 192).unwrap_or(u8::MAX);
+            comparison.diff.extend([faded, faded, faded, 255]);
+        }
+    }
+    comparison
+}
+
+/// Error that can happen in a [`GoldenTest`]
+#[derive(Debug)]
+pub enum GoldenError {
+    /// Rendering, or loading or saving an image, failed
+    Sf(SfError),
+    /// Creating the output directory failed
+    Io(io::Error),
+    /// The reference image doesn't exist
+    MissingReference(PathBuf),
+    /// The rendered image doesn't have the size of the reference image
+    SizeMismatch {
+        /// Size of the reference image
+        expected: Vector2u,
+        /// Size of the rendered image
+        actual: Vector2u,
+    },
+    /// More pixels than allowed differ from the reference image
+    Mismatch {
+        /// Number of pixels that differ by more than the tolerance
+        differing_pixels: usize,
+        /// Largest difference between two components of a pixel
+        max_difference: u8,
+        /// Path of the image highlighting the differences
+        diff: PathBuf,
+    },
+}
+
+impl From<SfError> for GoldenError {
+    fn from(error: SfError) -> Self {
+        Self::Sf(error)
+    }
+}
+
+impl Error for GoldenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Sf(error) => Some(error),
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sf(error) => write!(f, "{error}"),
+            Self::Io(error) => write!(f, "failed to write the output: {error}"),
+            Self::MissingReference(path) => write!(
+                f,
+                "reference image {} is missing, run with SFML_UPDATE_GOLDEN=1 to create it",
+                path.display()
+            ),
+            Self::SizeMismatch { expected, actual } => write!(
+                f,
+                "rendered image is {}x{}, but the reference is {}x{}",
+                actual.x, actual.y, expected.x, expected.y
+            ),
+            Self::Mismatch {
+                differing_pixels,
+                max_difference,
+                diff,
+            } => write!(
+                f,
+                "{differing_pixels} pixels differ from the reference, by up to {max_difference}, \
+                 see {}",
+                diff.display()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_with_tolerance() {
+        let expected = [100, 100, 100, 255, 0, 0, 0, 255, 50, 50, 50, 255];
+        let actual = [102, 99, 100, 255, 0, 10, 0, 255, 50, 50, 50, 255];
+        let comparison = compare(&actual, &expected, 2);
+        assert_eq!(comparison.differing_pixels, 1);
+        assert_eq!(comparison.max_difference, 10);
+        assert_eq!(&comparison.diff[4..8], [255, 0, 0, 255]);
+        assert_eq!(compare(&actual, &expected, 10).differing_pixels, 0);
+    }
+}
@@ -2,8 +2,8 @@ use {
     crate::{
         ffi::graphics as ffi,
         graphics::{
-            Color, Drawable, FloatRect, Font, RenderStates, RenderTarget, TextStyle, Transform,
-            Transformable,
+            Color, Drawable, FloatRect, Font, FontFamily, RenderStates, RenderTarget, TextLayout,
+            TextStyle, Transform, Transformable,
         },
         system::{Angle, SfStr, SfStrConv, Vector2f},
     },
@@ -21,10 +21,16 @@ use {
 /// and draw all the text you want with it. You can change its properties using
 /// `set_font`, `set_position`, `set_string`, etc., before drawing it, as many times as you need
 /// to.
+///
+/// By default, a `Text` renders all its characters with a single font: characters missing
+/// from the font are drawn as the font's replacement glyph. To render them with fallback
+/// fonts, give the text a [`FontFamily`] with [`Text::set_font_family`].
 #[derive(Debug)]
 pub struct Text<'s> {
     handle: NonNull<ffi::sfText>,
     font: PhantomData<&'s Font>,
+    /// Glyphs laid out with the font family, if one is set
+    fallback: Option<TextLayout<'s>>,
 }
 
 impl<'s> Text<'s> {
@@ -48,6 +54,7 @@ impl<'s> Text<'s> {
         Text {
             handle: NonNull::new(text).expect("Failed to create Text"),
             font: PhantomData,
+            fallback: None,
         }
     }
 
@@ -61,6 +68,10 @@ impl<'s> Text<'s> {
         string.with_as_sfstr(|sfstr| unsafe {
             ffi::sfText_setUnicodeString(self.handle.as_ptr(), sfstr.as_ptr());
         });
+        if self.fallback.is_some() {
+            let string = self.string().to_rust_string();
+            self.update_fallback(|layout| layout.set_string(&string));
+        }
     }
 
     /// Get the string of a text
@@ -89,9 +100,43 @@ impl<'s> Text<'s> {
     /// If the font is destroyed and the text tries to
     /// use it, the behaviour is undefined.
     ///
+    /// This removes the font family set with [`Text::set_font_family`], if any.
+    ///
     /// font - New font
     pub fn set_font(&mut self, font: &'s Font) {
         unsafe { ffi::sfText_setFont(self.handle.as_ptr(), font) }
+        self.fallback = None;
+    }
+
+    /// Set a font family, to render the characters missing from its primary font with
+    /// its fallback fonts.
+    ///
+    /// [`Text::font`] returns the primary font of the family. The text is then drawn and
+    /// measured like a [`TextLayout`] without width limit, so draw it with
+    /// [`RenderTarget::draw`] rather than [`RenderTarget::draw_text`], which only uses the
+    /// primary font.
+    ///
+    /// The family is removed by [`Text::set_font`].
+    pub fn set_font_family(&mut self, family: FontFamily<'s>) {
+        unsafe { ffi::sfText_setFont(self.handle.as_ptr(), family.primary()) }
+        let mut layout = TextLayout::new(
+            &self.string().to_rust_string(),
+            family,
+            self.character_size(),
+        );
+        layout.set_style(self.style());
+        layout.set_fill_color(self.fill_color());
+        layout.set_outline_color(self.outline_color());
+        layout.set_outline_thickness(self.outline_thickness());
+        layout.set_letter_spacing(self.letter_spacing());
+        layout.set_line_spacing(self.line_spacing());
+        self.fallback = Some(layout);
+    }
+
+    /// Get the font family set with [`Text::set_font_family`], if any.
+    #[must_use]
+    pub fn font_family(&self) -> Option<&FontFamily<'s>> {
+        self.fallback.as_ref().map(TextLayout::font_family)
     }
 
     /// Set the style of a text
@@ -104,6 +149,7 @@ impl<'s> Text<'s> {
     /// * style - New style
     pub fn set_style(&mut self, style: TextStyle) {
         unsafe { ffi::sfText_setStyle(self.handle.as_ptr(), style.bits()) }
+        self.update_fallback(|layout| layout.set_style(style));
     }
 
     /// Set the size of the characters of a text
@@ -114,6 +160,7 @@ impl<'s> Text<'s> {
     /// * size - The new character size, in pixels
     pub fn set_character_size(&mut self, size: u32) {
         unsafe { ffi::sfText_setCharacterSize(self.handle.as_ptr(), size) }
+        self.update_fallback(|layout| layout.set_character_size(size));
     }
 
     /// Get the style of a text
@@ -139,6 +186,7 @@ impl<'s> Text<'s> {
     /// color with an outline will cause the outline to be displayed in the fill area of the text.
     pub fn set_fill_color(&mut self, color: Color) {
         unsafe { ffi::sfText_setFillColor(self.handle.as_ptr(), color) }
+        self.update_fallback(|layout| layout.set_fill_color(color));
     }
 
     /// Set the outline color of the text.
@@ -146,6 +194,7 @@ impl<'s> Text<'s> {
     /// By default, the text's outline color is opaque black.
     pub fn set_outline_color(&mut self, color: Color) {
         unsafe { ffi::sfText_setOutlineColor(self.handle.as_ptr(), color) }
+        self.update_fallback(|layout| layout.set_outline_color(color));
     }
 
     /// Set the thickness of the text's outline.
@@ -156,6 +205,7 @@ impl<'s> Text<'s> {
     /// rendering.
     pub fn set_outline_thickness(&mut self, thickness: f32) {
         unsafe { ffi::sfText_setOutlineThickness(self.handle.as_ptr(), thickness) }
+        self.update_fallback(|layout| layout.set_outline_thickness(thickness));
     }
 
     /// Returns the fill color of the text.
@@ -191,6 +241,11 @@ impl<'s> Text<'s> {
     /// Return the position of the character
     #[must_use]
     pub fn find_character_pos(&self, index: usize) -> Vector2f {
+        if let Some(layout) = &self.fallback {
+            return self
+                .transform()
+                .transform_point(layout.find_character_pos(index));
+        }
         unsafe { ffi::sfText_findCharacterPos(self.handle.as_ptr(), index) }
     }
 
@@ -205,6 +260,9 @@ impl<'s> Text<'s> {
     /// Return the local bounding rectangle of the entity
     #[must_use]
     pub fn local_bounds(&self) -> FloatRect {
+        if let Some(layout) = &self.fallback {
+            return layout.local_bounds();
+        }
         unsafe { ffi::sfText_getLocalBounds(self.handle.as_ptr()) }
     }
 
@@ -219,6 +277,9 @@ impl<'s> Text<'s> {
     /// Return the global bounding rectangle of the entity
     #[must_use]
     pub fn global_bounds(&self) -> FloatRect {
+        if let Some(layout) = &self.fallback {
+            return self.transform().transform_rect(layout.local_bounds());
+        }
         unsafe { ffi::sfText_getGlobalBounds(self.handle.as_ptr()) }
     }
     /// Get the size of the line spacing factor.
@@ -233,6 +294,7 @@ impl<'s> Text<'s> {
     /// By default the line spacing factor is 1.
     pub fn set_line_spacing(&mut self, factor: f32) {
         unsafe { ffi::sfText_setLineSpacing(self.handle.as_ptr(), factor) }
+        self.update_fallback(|layout| layout.set_line_spacing(factor));
     }
     /// Get the size of the letter spacing factor.
     #[must_use]
@@ -248,10 +310,16 @@ impl<'s> Text<'s> {
     /// characters closer to each other. By default the letter spacing factor is 1.
     pub fn set_letter_spacing(&mut self, factor: f32) {
         unsafe { ffi::sfText_setLetterSpacing(self.handle.as_ptr(), factor) }
+        self.update_fallback(|layout| layout.set_letter_spacing(factor));
     }
     pub(super) fn raw(&self) -> *const ffi::sfText {
         self.handle.as_ptr()
     }
+    fn update_fallback(&mut self, update: impl FnOnce(&mut TextLayout<'s>)) {
+        if let Some(layout) = &mut self.fallback {
+            update(layout);
+        }
+    }
 }
 
 impl<'s> Clone for Text<'s> {
@@ -261,6 +329,7 @@ impl<'s> Clone for Text<'s> {
         Text {
             handle: NonNull::new(sp).expect("Not enough memory to clone Text"),
             font: PhantomData,
+            fallback: self.fallback.clone(),
         }
     }
 }
@@ -271,7 +340,16 @@ impl Drawable for Text<'_> {
         target: &mut dyn RenderTarget,
         states: &RenderStates<'texture, 'shader, 'shader_texture>,
     ) {
-        target.draw_text(self, states);
+        match &self.fallback {
+            Some(layout) => {
+                let states = RenderStates {
+                    transform: states.transform * *self.transform(),
+                    ..*states
+                };
+                layout.draw(target, &states);
+            }
+            None => target.draw_text(self, states),
+        }
     }
 }
 
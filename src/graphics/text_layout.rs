@@ -0,0 +1,1132 @@
+use crate::{
+    graphics::{
+        Color, Drawable, FloatRect, Font, FontFamily, Glyph, PrimitiveType, RenderStates,
+        RenderTarget, TextStyle, Transformable, Vertex,
+        transformable::{Transformation, impl_transformable_via},
+    },
+    system::Vector2f,
+};
+
+/// Horizontal alignment of the lines of a [`TextLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAlignment {
+    /// Lines start at the left edge
+    #[default]
+    Left,
+    /// Lines are centered
+    Center,
+    /// Lines end at the right edge
+    Right,
+    /// Spaces are stretched so that lines span the whole width.
+    ///
+    /// The last line of each paragraph is aligned to the left.
+    Justify,
+}
+
+/// Metrics of a line of a [`TextLayout`], in local coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMetrics {
+    /// Index of the first character of the line in the string
+    pub start: usize,
+    /// Index one past the last character of the line in the string.
+    ///
+    /// Whitespace at which the line was wrapped is not part of any line.
+    pub end: usize,
+    /// Horizontal position of the start of the line, after alignment
+    pub left: f32,
+    /// Vertical position of the top of the line
+    pub top: f32,
+    /// Vertical position of the baseline of the line
+    pub baseline: f32,
+    /// Width of the line, without trailing whitespace
+    pub width: f32,
+    /// Height of the line, which is the line spacing of the font
+    pub height: f32,
+}
+
+/// Text laid out in a block: wrapped to a maximum width, aligned, and optionally truncated.
+///
+/// `TextLayout` offers the same styling options as [`Text`], and uses the metrics and
+/// kerning of its [`Font`] to wrap the string at word boundaries.
+/// It can also render characters missing from the font with fallback fonts,
+/// see [`FontFamily`].
+/// Words that don't fit on a line by themselves are broken between characters.
+///
+/// The geometry is recomputed every time a property changes, so it's cheap to draw a
+/// `TextLayout` many times, but not to modify it every frame.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # use sfml::graphics::*;
+/// # use sfml::system::Vector2f;
+/// # let font = Font::from_file("examples/resources/sansation.ttf").unwrap();
+/// # let mut window: RenderWindow = unimplemented!();
+/// let mut layout = TextLayout::new("Some long text that needs wrapping", &font, 20);
+/// layout.set_max_width(Some(150.));
+/// layout.set_alignment(TextAlignment::Center);
+/// for line in layout.lines() {
+///     println!("{}..{} is {} pixels wide", line.start, line.end, line.width);
+/// }
+/// // Which character is under the mouse?
+/// let index = layout.character_index_at(Vector2f::new(40., 30.));
+/// window.draw(&layout);
+/// ```
+///
+/// [`Text`]: crate::graphics::Text
+#[derive(Debug, Clone)]
+pub struct TextLayout<'s> {
+    string: String,
+    font: FontFamily<'s>,
+    character_size: u32,
+    style: TextStyle,
+    fill_color: Color,
+    outline_color: Color,
+    outline_thickness: f32,
+    letter_spacing: f32,
+    line_spacing: f32,
+    max_width: Option<f32>,
+    alignment: TextAlignment,
+    max_lines: Option<usize>,
+    ellipsis: bool,
+    transformation: Transformation,
+    lines: Vec<LineMetrics>,
+    /// Position of the pen before each character (and after the last one), local coordinates
+    char_positions: Vec<Vector2f>,
+    /// Position of the pen after the last character of each line, in local coordinates
+    line_ends: Vec<f32>,
+    batches: Vec<GlyphBatch<'s>>,
+    bounds: FloatRect,
+}
+
+impl<'s> TextLayout<'s> {
+    /// Create a new text layout, without any width limit.
+    ///
+    /// `font` is either a [`Font`] or a [`FontFamily`].
+    #[must_use]
+    pub fn new(string: &str, font: impl Into<FontFamily<'s>>, character_size: u32) -> Self {
+        let mut layout = Self {
+            string: string.to_owned(),
+            font: font.into(),
+            character_size,
+            style: TextStyle::REGULAR,
+            fill_color: Color::WHITE,
+            outline_color: Color::BLACK,
+            outline_thickness: 0.,
+            letter_spacing: 1.,
+            line_spacing: 1.,
+            max_width: None,
+            alignment: TextAlignment::Left,
+            max_lines: None,
+            ellipsis: false,
+            transformation: Transformation::default(),
+            lines: Vec::new(),
+            char_positions: Vec::new(),
+            line_ends: Vec::new(),
+            batches: Vec::new(),
+            bounds: FloatRect::default(),
+        };
+        layout.update();
+        layout
+    }
+
+    /// Set the string to lay out.
+    pub fn set_string(&mut self, string: &str) {
+        string.clone_into(&mut self.string);
+        self.update();
+    }
+
+    /// Get the string that is laid out.
+    #[must_use]
+    pub fn string(&self) -> &str {
+        &self.string
+    }
+
+    /// Set the font used to lay out and render the text, without fallback fonts.
+    pub fn set_font(&mut self, font: &'s Font) {
+        self.set_font_family(font.into());
+    }
+
+    /// Get the primary font used to lay out and render the text.
+    #[must_use]
+    pub fn font(&self) -> &'s Font {
+        self.font.primary()
+    }
+
+    /// Set the font family used to lay out and render the text.
+    pub fn set_font_family(&mut self, family: FontFamily<'s>) {
+        self.font = family;
+        self.update();
+    }
+
+    /// Get the font family used to lay out and render the text.
+    #[must_use]
+    pub fn font_family(&self) -> &FontFamily<'s> {
+        &self.font
+    }
+
+    /// Set the size of the characters, in pixels.
+    pub fn set_character_size(&mut self, size: u32) {
+        self.character_size = size;
+        self.update();
+    }
+
+    /// Get the size of the characters, in pixels.
+    #[must_use]
+    pub fn character_size(&self) -> u32 {
+        self.character_size
+    }
+
+    /// Set the style of the text. The default style is [`TextStyle::REGULAR`].
+    pub fn set_style(&mut self, style: TextStyle) {
+        self.style = style;
+        self.update();
+    }
+
+    /// Get the style of the text.
+    #[must_use]
+    pub fn style(&self) -> TextStyle {
+        self.style
+    }
+
+    /// Set the fill color of the text. By default, it is opaque white.
+    pub fn set_fill_color(&mut self, color: Color) {
+        self.fill_color = color;
+        self.update();
+    }
+
+    /// Get the fill color of the text.
+    #[must_use]
+    pub fn fill_color(&self) -> Color {
+        self.fill_color
+    }
+
+    /// Set the outline color of the text. By default, it is opaque black.
+    pub fn set_outline_color(&mut self, color: Color) {
+        self.outline_color = color;
+        self.update();
+    }
+
+    /// Get the outline color of the text.
+    #[must_use]
+    pub fn outline_color(&self) -> Color {
+        self.outline_color
+    }
+
+    /// Set the thickness of the text's outline. By default, it is 0.
+    pub fn set_outline_thickness(&mut self, thickness: f32) {
+        self.outline_thickness = thickness;
+        self.update();
+    }
+
+    /// Get the thickness of the text's outline.
+    #[must_use]
+    pub fn outline_thickness(&self) -> f32 {
+        self.outline_thickness
+    }
+
+    /// Set the letter spacing factor. See [`Text::set_letter_spacing`].
+    ///
+    /// [`Text::set_letter_spacing`]: crate::graphics::Text::set_letter_spacing
+    pub fn set_letter_spacing(&mut self, factor: f32) {
+        self.letter_spacing = factor;
+        self.update();
+    }
+
+    /// Get the letter spacing factor.
+    #[must_use]
+    pub fn letter_spacing(&self) -> f32 {
+        self.letter_spacing
+    }
+
+    /// Set the line spacing factor. See [`Text::set_line_spacing`].
+    ///
+    /// [`Text::set_line_spacing`]: crate::graphics::Text::set_line_spacing
+    pub fn set_line_spacing(&mut self, factor: f32) {
+        self.line_spacing = factor;
+        self.update();
+    }
+
+    /// Get the line spacing factor.
+    #[must_use]
+    pub fn line_spacing(&self) -> f32 {
+        self.line_spacing
+    }
+
+    /// Set the width at which lines are wrapped, in local units.
+    ///
+    /// With `None`, lines are only broken at newline characters. This is the default.
+    pub fn set_max_width(&mut self, width: Option<f32>) {
+        self.max_width = width;
+        self.update();
+    }
+
+    /// Get the width at which lines are wrapped.
+    #[must_use]
+    pub fn max_width(&self) -> Option<f32> {
+        self.max_width
+    }
+
+    /// Set the horizontal alignment of the lines.
+    ///
+    /// Lines are aligned within the maximum width if there is one,
+    /// otherwise within the width of the longest line.
+    pub fn set_alignment(&mut self, alignment: TextAlignment) {
+        self.alignment = alignment;
+        self.update();
+    }
+
+    /// Get the horizontal alignment of the lines.
+    #[must_use]
+    pub fn alignment(&self) -> TextAlignment {
+        self.alignment
+    }
+
+    /// Set the maximum number of lines. Lines past the limit are not displayed.
+    ///
+    /// With `None`, the number of lines is unlimited. This is the default.
+    pub fn set_max_lines(&mut self, max_lines: Option<usize>) {
+        self.max_lines = max_lines;
+        self.update();
+    }
+
+    /// Get the maximum number of lines.
+    #[must_use]
+    pub fn max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+
+    /// Set whether text that exceeds the maximum number of lines ends with an ellipsis.
+    ///
+    /// The last displayed line is shortened so that the ellipsis fits in the maximum width.
+    pub fn set_ellipsis(&mut self, ellipsis: bool) {
+        self.ellipsis = ellipsis;
+        self.update();
+    }
+
+    /// Tell whether truncated text ends with an ellipsis.
+    #[must_use]
+    pub fn ellipsis(&self) -> bool {
+        self.ellipsis
+    }
+
+    /// Get the metrics of the displayed lines, from top to bottom.
+    #[must_use]
+    pub fn lines(&self) -> &[LineMetrics] {
+        &self.lines
+    }
+
+    /// Return the position of the `index`-th character, in global coordinates.
+    ///
+    /// This is the position of the top-left of the character's line box, at the pen position
+    /// of the character. If `index` is out of range, the position of the end of the
+    /// string is returned.
+    #[must_use]
+    pub fn find_character_pos(&self, index: usize) -> Vector2f {
+        let local = self
+            .char_positions
+            .get(index)
+            .or(self.char_positions.last())
+            .copied()
+            .unwrap_or_default();
+        self.transform().transform_point(local)
+    }
+
+    /// Return the index of the character boundary closest to `point`, in global coordinates.
+    ///
+    /// Points above the first line or below the last line map to that line.
+    /// The returned index is in range `0..=string.chars().count()`, and is suitable
+    /// for placing a text cursor.
+    #[must_use]
+    pub fn character_index_at(&self, point: Vector2f) -> usize {
+        let point = self.inverse_transform().transform_point(point);
+        let Some(line_index) = self
+            .lines
+            .iter()
+            .position(|line| point.y < line.top + line.height)
+            .or(self.lines.len().checked_sub(1))
+        else {
+            return 0;
+        };
+        let line = &self.lines[line_index];
+        // The position stored for `line.end` is on the next line if this one was wrapped
+        let boundaries = self.char_positions[line.start..line.end]
+            .iter()
+            .map(|position| position.x)
+            .chain([self.line_ends[line_index]]);
+        line.start + closest_boundary(boundaries, point.x)
+    }
+
+    /// Get the local bounding rectangle of the text.
+    #[must_use]
+    pub fn local_bounds(&self) -> FloatRect {
+        self.bounds
+    }
+
+    /// Get the global bounding rectangle of the text.
+    #[must_use]
+    pub fn global_bounds(&self) -> FloatRect {
+        self.transform().transform_rect(self.bounds)
+    }
+
+    fn update(&mut self) {
+        const ELLIPSIS: char = '\u{2026}';
+        let metrics = Metrics::new(
+            self.font.clone(),
+            self.character_size,
+            self.style,
+            self.letter_spacing,
+            self.line_spacing,
+        )
+        .with_outline(self.outline_thickness);
+        let chars: Vec<char> = self.string.chars().collect();
+        let advance = |prev: Option<char>, c: char| metrics.kerning(prev, c) + metrics.advance(c);
+        // Fall back to a period if no font has the ellipsis character
+        let ellipsis = self.ellipsis.then(|| {
+            if metrics.font.has_glyph(ELLIPSIS) {
+                ELLIPSIS
+            } else {
+                '.'
+            }
+        });
+        let (lines, ellipsis) =
+            layout_lines(&chars, &advance, self.max_width, self.max_lines, ellipsis);
+        let placed = place_lines(
+            &lines,
+            &chars,
+            self.alignment,
+            self.max_width,
+            metrics.line_spacing,
+            metrics.character_size,
+        );
+
+        let mut renderer = GlyphRenderer::new(&metrics, self.fill_color, self.outline_color);
+        self.lines.clear();
+        self.line_ends.clear();
+        self.char_positions.clear();
+        self.char_positions
+            .resize(chars.len() + 1, Vector2f::default());
+        let mut next_char = 0;
+        for (line_index, &(line, space_stretch)) in placed.iter().enumerate() {
+            // Characters skipped at the previous wrap sit at the start of this line
+            while next_char < line.start {
+                self.char_positions[next_char] = Vector2f::new(line.left, line.top);
+                next_char += 1;
+            }
+            let mut x = line.left;
+            let mut prev = None;
+            for (index, &c) in chars.iter().enumerate().take(line.end).skip(line.start) {
+                x += metrics.kerning(prev, c);
+                self.char_positions[index] = Vector2f::new(x, line.top);
+                renderer.add_char(c, Vector2f::new(x, line.baseline));
+                x += metrics.advance(c);
+                if c == ' ' {
+                    x += space_stretch;
+                }
+                prev = Some(c);
+            }
+            self.line_ends.push(x);
+            if line_index + 1 == placed.len() {
+                if let Some(ellipsis) = ellipsis {
+                    x += metrics.kerning(prev, ellipsis);
+                    renderer.add_char(ellipsis, Vector2f::new(x, line.baseline));
+                }
+            }
+            renderer.add_decorations(line.left, line.left + line.width, line.baseline);
+            self.lines.push(line);
+            next_char = line.end;
+        }
+        // Characters past the last displayed line share the end position
+        let end = self.lines.last().map_or(Vector2f::default(), |line| {
+            Vector2f::new(line.left + line.width, line.top)
+        });
+        for position in &mut self.char_positions[next_char..] {
+            *position = end;
+        }
+        self.bounds = batches_bounds(&renderer.batches);
+        self.batches = renderer.batches;
+    }
+}
+
+impl_transformable_via!(TextLayout<'_>, transformation);
+
+impl Drawable for TextLayout<'_> {
+    fn draw<'a: 'shader, 'texture, 'shader, 'shader_texture>(
+        &'a self,
+        target: &mut dyn RenderTarget,
+        states: &RenderStates<'texture, 'shader, 'shader_texture>,
+    ) {
+        let states = RenderStates {
+            transform: states.transform * *self.transform(),
+            ..*states
+        };
+        draw_batches(&self.batches, target, &states);
+    }
+}
+
+/// Vertices of glyphs sharing the same font texture
+#[derive(Debug, Clone)]
+pub(super) struct GlyphBatch<'f> {
+    pub(super) font: &'f Font,
+    pub(super) character_size: u32,
+    pub(super) vertices: Vec<Vertex>,
+    pub(super) outline_vertices: Vec<Vertex>,
+}
+
+impl<'f> GlyphBatch<'f> {
+    /// Find the batch of `font` at `character_size`, or add it.
+    pub(super) fn find_or_insert<'b>(
+        batches: &'b mut Vec<Self>,
+        font: &'f Font,
+        character_size: u32,
+    ) -> &'b mut Self {
+        let position = batches
+            .iter()
+            .position(|batch| {
+                std::ptr::eq(batch.font, font) && batch.character_size == character_size
+            })
+            .unwrap_or_else(|| {
+                batches.push(Self {
+                    font,
+                    character_size,
+                    vertices: Vec::new(),
+                    outline_vertices: Vec::new(),
+                });
+                batches.len() - 1
+            });
+        &mut batches[position]
+    }
+}
+
+/// Draw batches with one call per font texture, outlines below all the glyphs
+pub(super) fn draw_batches(
+    batches: &[GlyphBatch],
+    target: &mut dyn RenderTarget,
+    states: &RenderStates,
+) {
+    for outline in [true, false] {
+        for batch in batches {
+            let vertices = if outline {
+                &batch.outline_vertices
+            } else {
+                &batch.vertices
+            };
+            if vertices.is_empty() {
+                continue;
+            }
+            let states = RenderStates {
+                texture: Some(batch.font.texture(batch.character_size)),
+                ..*states
+            };
+            target.draw_primitives(vertices, PrimitiveType::TRIANGLES, &states);
+        }
+    }
+}
+
+/// Bounding rectangle of all the batches, including outlines
+pub(super) fn batches_bounds(batches: &[GlyphBatch]) -> FloatRect {
+    let mut positions = batches
+        .iter()
+        .flat_map(|batch| {
+            let vertices = if batch.outline_vertices.is_empty() {
+                &batch.vertices
+            } else {
+                &batch.outline_vertices
+            };
+            vertices.iter().map(|vertex| vertex.position)
+        })
+        .peekable();
+    let Some(&first) = positions.peek() else {
+        return FloatRect::default();
+    };
+    let (min, max) = positions.fold((first, first), |(min, max), p| {
+        (
+            Vector2f::new(min.x.min(p.x), min.y.min(p.y)),
+            Vector2f::new(max.x.max(p.x), max.y.max(p.y)),
+        )
+    });
+    FloatRect::new(min, max - min)
+}
+
+/// Font metrics needed to lay out and render a string.
+pub(super) struct Metrics<'f> {
+    font: FontFamily<'f>,
+    size: u32,
+    pub(super) character_size: f32,
+    bold: bool,
+    italic_shear: f32,
+    underlined: bool,
+    strike_through: bool,
+    outline_thickness: f32,
+    whitespace_width: f32,
+    letter_spacing: f32,
+    pub(super) line_spacing: f32,
+}
+
+impl<'f> Metrics<'f> {
+    /// Same computations as `sf::Text::ensureGeometryUpdate`
+    pub(super) fn new(
+        font: FontFamily<'f>,
+        size: u32,
+        style: TextStyle,
+        letter_spacing_factor: f32,
+        line_spacing_factor: f32,
+    ) -> Self {
+        let bold = style.contains(TextStyle::BOLD);
+        let primary = font.primary();
+        let mut whitespace_width = primary.glyph(u32::from(' '), size, bold, 0.).advance();
+        let line_spacing = primary.line_spacing(size) * line_spacing_factor;
+        let letter_spacing = (whitespace_width / 3.) * (letter_spacing_factor - 1.);
+        whitespace_width += letter_spacing;
+        Self {
+            font,
+            size,
+            character_size: size as f32,
+            bold,
+            // 12 degrees
+            italic_shear: if style.contains(TextStyle::ITALIC) {
+                0.209
+            } else {
+                0.
+            },
+            underlined: style.contains(TextStyle::UNDERLINED),
+            strike_through: style.contains(TextStyle::STRIKETHROUGH),
+            outline_thickness: 0.,
+            whitespace_width,
+            letter_spacing,
+            line_spacing,
+        }
+    }
+
+    pub(super) fn with_outline(mut self, thickness: f32) -> Self {
+        self.outline_thickness = thickness;
+        self
+    }
+
+    pub(super) fn glyph(&self, c: char, outline_thickness: f32) -> Glyph {
+        self.font
+            .resolve(c)
+            .glyph(u32::from(c), self.size, self.bold, outline_thickness)
+    }
+
+    /// Kerning between two characters, which is 0 if they come from different fonts
+    pub(super) fn kerning(&self, prev: Option<char>, c: char) -> f32 {
+        let Some(prev) = prev else {
+            return 0.;
+        };
+        let font = self.font.resolve(c);
+        if !std::ptr::eq(font, self.font.resolve(prev)) {
+            return 0.;
+        }
+        if self.bold {
+            font.bold_kerning(u32::from(prev), u32::from(c), self.size)
+        } else {
+            font.kerning(u32::from(prev), u32::from(c), self.size)
+        }
+    }
+
+    pub(super) fn advance(&self, c: char) -> f32 {
+        match c {
+            ' ' => self.whitespace_width,
+            '\t' => self.whitespace_width * 4.,
+            '\n' | '\r' => 0.,
+            _ => self.glyph(c, 0.).advance() + self.letter_spacing,
+        }
+    }
+}
+
+/// Index of the position closest to `x`, the first one if several are as close.
+fn closest_boundary(positions: impl Iterator<Item = f32>, x: f32) -> usize {
+    let mut best = 0;
+    let mut best_distance = f32::INFINITY;
+    for (index, position) in positions.enumerate() {
+        let distance = (position - x).abs();
+        if distance < best_distance {
+            best = index;
+            best_distance = distance;
+        }
+    }
+    best
+}
+
+/// A line being built by [`break_lines`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Line {
+    start: usize,
+    end: usize,
+    width: f32,
+    ends_paragraph: bool,
+}
+
+fn is_break_space(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// Width of `chars`, where `advance(prev, c)` is the kerning and advance of `c` after `prev`.
+fn width(chars: &[char], advance: &impl Fn(Option<char>, char) -> f32) -> f32 {
+    let mut prev = None;
+    let mut width = 0.;
+    for &c in chars {
+        width += advance(prev, c);
+        prev = Some(c);
+    }
+    width
+}
+
+/// Break `chars` into lines, keep at most `max_lines` of them, and shorten the last one so that
+/// `ellipsis` fits after it if some were dropped.
+///
+/// Returns the lines, and the ellipsis if it must be drawn.
+fn layout_lines(
+    chars: &[char],
+    advance: &impl Fn(Option<char>, char) -> f32,
+    max_width: Option<f32>,
+    max_lines: Option<usize>,
+    ellipsis: Option<char>,
+) -> (Vec<Line>, Option<char>) {
+    let mut lines = break_lines(chars, advance, max_width);
+    let Some(max_lines) = max_lines.filter(|&max_lines| lines.len() > max_lines) else {
+        return (lines, None);
+    };
+    lines.truncate(max_lines);
+    match (ellipsis, lines.last_mut()) {
+        (Some(ellipsis), Some(last)) => {
+            truncate_with_ellipsis(last, chars, advance, ellipsis, max_width);
+            (lines, Some(ellipsis))
+        }
+        _ => (lines, None),
+    }
+}
+
+/// Split `chars` into lines no wider than `max_width`, breaking at whitespace if possible.
+fn break_lines(
+    chars: &[char],
+    advance: &impl Fn(Option<char>, char) -> f32,
+    max_width: Option<f32>,
+) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut paragraph_start = 0;
+    for paragraph in chars.split(|&c| c == '\n') {
+        let paragraph_end = paragraph_start + paragraph.len();
+        let mut line = Line {
+            start: paragraph_start,
+            end: paragraph_start,
+            width: 0.,
+            ends_paragraph: false,
+        };
+        let mut i = paragraph_start;
+        while i < paragraph_end {
+            // Next token: a run of whitespace followed by a word
+            let space_end = (i..paragraph_end)
+                .find(|&j| !is_break_space(chars[j]))
+                .unwrap_or(paragraph_end);
+            let word_end = (space_end..paragraph_end)
+                .find(|&j| is_break_space(chars[j]))
+                .unwrap_or(paragraph_end);
+            let with_space = width(&chars[line.start..word_end], advance);
+            match max_width {
+                Some(max) if with_space > max && line.end > line.start => {
+                    // Wrap before the word, dropping the whitespace
+                    lines.push(line);
+                    line = Line {
+                        start: space_end,
+                        end: space_end,
+                        width: 0.,
+                        ends_paragraph: false,
+                    };
+                    i = space_end;
+                }
+                Some(max) if with_space > max => {
+                    // The word doesn't fit on a line by itself, break it between characters
+                    let mut end = line.start + 1;
+                    while end < word_end && width(&chars[line.start..=end], advance) <= max {
+                        end += 1;
+                    }
+                    line.end = end;
+                    line.width = width(&chars[line.start..end], advance);
+                    lines.push(line);
+                    line = Line {
+                        start: end,
+                        end,
+                        width: 0.,
+                        ends_paragraph: false,
+                    };
+                    i = end;
+                }
+                _ => {
+                    line.end = word_end;
+                    line.width = with_space;
+                    i = word_end;
+                }
+            }
+        }
+        // Trailing whitespace doesn't count towards the width
+        let mut content_end = line.end;
+        while content_end > line.start && is_break_space(chars[content_end - 1]) {
+            content_end -= 1;
+        }
+        if content_end != line.end {
+            line.width = width(&chars[line.start..content_end], advance);
+        }
+        line.ends_paragraph = true;
+        lines.push(line);
+        paragraph_start = paragraph_end + 1;
+    }
+    lines
+}
+
+/// Shorten `line` so that `ellipsis` fits after it.
+fn truncate_with_ellipsis(
+    line: &mut Line,
+    chars: &[char],
+    advance: &impl Fn(Option<char>, char) -> f32,
+    ellipsis: char,
+    max_width: Option<f32>,
+) {
+    let ellipsis_width = advance(None, ellipsis);
+    let max = max_width.unwrap_or(f32::INFINITY);
+    while line.end > line.start
+        && (width(&chars[line.start..line.end], advance) + ellipsis_width > max
+            || is_break_space(chars[line.end - 1]))
+    {
+        line.end -= 1;
+    }
+    line.width = width(&chars[line.start..line.end], advance) + ellipsis_width;
+    line.ends_paragraph = true;
+}
+
+/// Align the lines in a block of `max_width`, or of the width of the widest line, and stack
+/// them.
+///
+/// Returns the metrics of each line, and how much its spaces are stretched to justify it.
+fn place_lines(
+    lines: &[Line],
+    chars: &[char],
+    alignment: TextAlignment,
+    max_width: Option<f32>,
+    line_spacing: f32,
+    character_size: f32,
+) -> Vec<(LineMetrics, f32)> {
+    let block_width = max_width.unwrap_or_else(|| lines.iter().map(|l| l.width).fold(0., f32::max));
+    lines
+        .iter()
+        .enumerate()
+        .map(|(line_index, line)| {
+            let top = line_index as f32 * line_spacing;
+            let extra = block_width - line.width;
+            let (left, space_stretch) = match alignment {
+                TextAlignment::Left => (0., 0.),
+                TextAlignment::Center => (extra / 2., 0.),
+                TextAlignment::Right => (extra, 0.),
+                TextAlignment::Justify => {
+                    let spaces = chars[line.start..line.end]
+                        .iter()
+                        .filter(|&&c| c == ' ')
+                        .count();
+                    if line.ends_paragraph || spaces == 0 || extra <= 0. {
+                        (0., 0.)
+                    } else {
+                        (0., extra / spaces as f32)
+                    }
+                }
+            };
+            let metrics = LineMetrics {
+                start: line.start,
+                end: line.end,
+                left,
+                top,
+                baseline: top + character_size,
+                width: line.width,
+                height: line_spacing,
+            };
+            (metrics, space_stretch)
+        })
+        .collect()
+}
+
+/// Builds the vertices of glyph quads and decoration lines, like `sf::Text` does.
+pub(super) struct GlyphRenderer<'m, 'f> {
+    metrics: &'m Metrics<'f>,
+    fill_color: Color,
+    outline_color: Color,
+    pub(super) batches: Vec<GlyphBatch<'f>>,
+}
+
+impl<'m, 'f> GlyphRenderer<'m, 'f> {
+    pub(super) fn new(metrics: &'m Metrics<'f>, fill_color: Color, outline_color: Color) -> Self {
+        Self {
+            metrics,
+            fill_color,
+            outline_color,
+            batches: Vec::new(),
+        }
+    }
+
+    fn batch(&mut self, font: &'f Font) -> &mut GlyphBatch<'f> {
+        GlyphBatch::find_or_insert(&mut self.batches, font, self.metrics.size)
+    }
+
+    /// Add the quad of `c`, with the pen at `position` on the baseline.
+    pub(super) fn add_char(&mut self, c: char, position: Vector2f) {
+        if c.is_whitespace() {
+            return;
+        }
+        let shear = self.metrics.italic_shear;
+        let (fill_color, outline_color) = (self.fill_color, self.outline_color);
+        let outline_glyph = (self.metrics.outline_thickness != 0.)
+            .then(|| self.metrics.glyph(c, self.metrics.outline_thickness));
+        let glyph = self.metrics.glyph(c, 0.);
+        let batch = self.batch(self.metrics.font.resolve(c));
+        if let Some(outline_glyph) = outline_glyph {
+            add_glyph_quad(
+                &mut batch.outline_vertices,
+                position,
+                outline_color,
+                &outline_glyph,
+                shear,
+            );
+        }
+        add_glyph_quad(&mut batch.vertices, position, fill_color, &glyph, shear);
+    }
+
+    /// Add underline and strike through lines spanning `left..right` on a baseline.
+    pub(super) fn add_decorations(&mut self, left: f32, right: f32, baseline: f32) {
+        let font = self.metrics.font.primary();
+        let size = self.metrics.size;
+        let thickness = font.underline_thickness(size);
+        let outline = self.metrics.outline_thickness;
+        let mut offsets = Vec::new();
+        if self.metrics.underlined {
+            offsets.push(font.underline_position(size));
+        }
+        if self.metrics.strike_through {
+            let x_bounds = self.metrics.glyph('x', 0.).bounds();
+            offsets.push(x_bounds.position.y + x_bounds.size.y / 2.);
+        }
+        let (fill_color, outline_color) = (self.fill_color, self.outline_color);
+        let batch = self.batch(font);
+        for offset in offsets {
+            if outline != 0. {
+                add_line(
+                    &mut batch.outline_vertices,
+                    left..right,
+                    baseline + offset,
+                    thickness,
+                    outline,
+                    outline_color,
+                );
+            }
+            add_line(
+                &mut batch.vertices,
+                left..right,
+                baseline + offset,
+                thickness,
+                0.,
+                fill_color,
+            );
+        }
+    }
+}
+
+fn add_glyph_quad(
+    vertices: &mut Vec<Vertex>,
+    position: Vector2f,
+    color: Color,
+    glyph: &Glyph,
+    italic_shear: f32,
+) {
+    let padding = 1.;
+    let bounds = glyph.bounds();
+    let rect = glyph.texture_rect().as_other::<f32>();
+    let left = bounds.position.x - padding;
+    let top = bounds.position.y - padding;
+    let right = bounds.position.x + bounds.size.x + padding;
+    let bottom = bounds.position.y + bounds.size.y + padding;
+    let u1 = rect.position.x - padding;
+    let v1 = rect.position.y - padding;
+    let u2 = rect.position.x + rect.size.x + padding;
+    let v2 = rect.position.y + rect.size.y + padding;
+    let vertex = |x: f32, y: f32, u: f32, v: f32| {
+        Vertex::new(
+            Vector2f::new(position.x + x - italic_shear * y, position.y + y),
+            color,
+            Vector2f::new(u, v),
+        )
+    };
+    let top_left = vertex(left, top, u1, v1);
+    let top_right = vertex(right, top, u2, v1);
+    let bottom_left = vertex(left, bottom, u1, v2);
+    let bottom_right = vertex(right, bottom, u2, v2);
+    vertices.extend([
+        top_left,
+        top_right,
+        bottom_left,
+        bottom_left,
+        top_right,
+        bottom_right,
+    ]);
+}
+
+fn add_line(
+    vertices: &mut Vec<Vertex>,
+    span: std::ops::Range<f32>,
+    line_top: f32,
+    thickness: f32,
+    outline: f32,
+    color: Color,
+) {
+    let top = (line_top - thickness / 2. + 0.5).floor();
+    let bottom = top + (thickness + 0.5).floor();
+    // The font texture has a white square in its top-left corner
+    let tex = Vector2f::new(1., 1.);
+    let corner = |x: f32, y: f32| Vertex::new(Vector2f::new(x, y), color, tex);
+    let (left, right) = (span.start - outline, span.end + outline);
+    let (top, bottom) = (top - outline, bottom + outline);
+    vertices.extend([
+        corner(left, top),
+        corner(right, top),
+        corner(left, bottom),
+        corner(left, bottom),
+        corner(right, top),
+        corner(right, bottom),
+    ]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hit_test_past_the_end_of_a_wrapped_line() {
+        // "ab cd" wrapped after "ab", centered: the first line spans 40..60
+        let chars = [40., 50.];
+        let end = 60.;
+        assert_eq!(closest_boundary(chars.into_iter().chain([end]), 100.), 2);
+        assert_eq!(closest_boundary(chars.into_iter().chain([end]), 56.), 2);
+        assert_eq!(closest_boundary(chars.into_iter().chain([end]), 54.), 1);
+        assert_eq!(closest_boundary(chars.into_iter().chain([end]), 0.), 0);
+    }
+
+    /// Every character is 10 wide, except spaces which are 5 wide, and "AV" which is kerned
+    /// by -2.
+    fn advance(prev: Option<char>, c: char) -> f32 {
+        let kerning = if prev == Some('A') && c == 'V' {
+            -2.
+        } else {
+            0.
+        };
+        kerning + if c == ' ' { 5. } else { 10. }
+    }
+
+    fn chars(string: &str) -> Vec<char> {
+        string.chars().collect()
+    }
+
+    fn spans(lines: &[Line]) -> Vec<(usize, usize)> {
+        lines.iter().map(|line| (line.start, line.end)).collect()
+    }
+
+    #[test]
+    fn word_wrapping() {
+        let text = chars("ab cd  ef\nghijklmnop");
+        let lines = break_lines(&text, &advance, Some(55.));
+        // "ab cd" fits in 55, the spaces before "ef" are dropped, and the long word is broken
+        assert_eq!(spans(&lines), [(0, 5), (7, 9), (10, 15), (15, 20)]);
+        let widths: Vec<_> = lines.iter().map(|line| line.width).collect();
+        assert_eq!(widths, [45., 20., 50., 50.]);
+        let ends: Vec<_> = lines.iter().map(|line| line.ends_paragraph).collect();
+        assert_eq!(ends, [false, true, false, true]);
+        // Without a maximum width, only newlines break lines
+        let lines = break_lines(&text, &advance, None);
+        assert_eq!(spans(&lines), [(0, 9), (10, 20)]);
+        // Kerning is part of the width, and trailing whitespace isn't
+        let lines = break_lines(&chars("AV  "), &advance, None);
+        assert_eq!(lines[0].width, 18.);
+    }
+
+    #[test]
+    fn max_lines_and_ellipsis() {
+        let text = chars("aaa bbb ccc");
+        let (lines, ellipsis) = layout_lines(&text, &advance, Some(40.), Some(2), None);
+        assert_eq!((spans(&lines), ellipsis), (vec![(0, 3), (4, 7)], None));
+        // "bbb" and the ellipsis are 40 wide, so "bbb" is shortened to "bb"
+        let (lines, ellipsis) = layout_lines(&text, &advance, Some(35.), Some(2), Some('.'));
+        assert_eq!((spans(&lines), ellipsis), (vec![(0, 3), (4, 6)], Some('.')));
+        assert_eq!(lines[1].width, 30.);
+        assert!(lines[1].ends_paragraph);
+        // No ellipsis when everything fits
+        let (lines, ellipsis) = layout_lines(&text, &advance, Some(40.), Some(3), Some('.'));
+        assert_eq!((lines.len(), ellipsis), (3, None));
+    }
+
+    #[test]
+    fn ellipsis_drops_trailing_spaces() {
+        let text = chars("ab cd");
+        let mut line = Line {
+            start: 0,
+            end: 5,
+            width: 45.,
+            ends_paragraph: false,
+        };
+        // "ab c" doesn't leave room for the ellipsis, and "ab " ends with a space
+        truncate_with_ellipsis(&mut line, &text, &advance, '.', Some(44.));
+        assert_eq!((line.end, line.width), (2, 30.));
+    }
+
+    #[test]
+    fn alignment_and_line_metrics() {
+        let text = chars("a b c\nd");
+        let lines = break_lines(&text, &advance, Some(70.));
+        let place = |alignment| place_lines(&lines, &text, alignment, Some(70.), 20., 16.);
+        let left: Vec<_> = place(TextAlignment::Left)
+            .iter()
+            .map(|(line, stretch)| (line.left, *stretch))
+            .collect();
+        assert_eq!(left, [(0., 0.), (0., 0.)]);
+        let right: Vec<_> = place(TextAlignment::Right)
+            .iter()
+            .map(|(line, _)| line.left)
+            .collect();
+        assert_eq!(right, [30., 60.]);
+        let center: Vec<_> = place(TextAlignment::Center)
+            .iter()
+            .map(|(line, _)| line.left)
+            .collect();
+        assert_eq!(center, [15., 30.]);
+        let placed = place(TextAlignment::Left);
+        assert_eq!(
+            placed[1].0,
+            LineMetrics {
+                start: 6,
+                end: 7,
+                left: 0.,
+                top: 20.,
+                baseline: 36.,
+                width: 10.,
+                height: 20.,
+            }
+        );
+        // Without a maximum width, the block is as wide as the widest line
+        let placed = place_lines(&lines, &text, TextAlignment::Right, None, 20., 16.);
+        assert_eq!((placed[0].0.left, placed[1].0.left), (0., 30.));
+    }
+
+    #[test]
+    fn justify() {
+        let text = chars("a b c d e\nf g");
+        let lines = break_lines(&text, &advance, Some(50.));
+        assert_eq!(spans(&lines), [(0, 5), (6, 9), (10, 13)]);
+        let placed = place_lines(&lines, &text, TextAlignment::Justify, Some(50.), 20., 16.);
+        let stretches: Vec<_> = placed.iter().map(|(_, stretch)| *stretch).collect();
+        // "a b c" is 40 wide with 2 spaces; the last lines of the paragraphs aren't stretched
+        assert_eq!(stretches, [5., 0., 0.]);
+        assert!(placed.iter().all(|(line, _)| line.left == 0.));
+    }
+
+    #[test]
+    fn closest_boundary_prefers_the_first() {
+        assert_eq!(closest_boundary([0., 10., 10.].into_iter(), 10.), 1);
+        assert_eq!(closest_boundary([0., 10.].into_iter(), 5.), 0);
+        assert_eq!(closest_boundary(std::iter::empty(), 5.), 0);
+    }
+}
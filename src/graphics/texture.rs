@@ -5,7 +5,7 @@ use {
         cpp::FBox,
         ffi::graphics::{self as ffi},
         graphics::{Image, IntRect, RenderWindow},
-        system::{InputStream, Vector2u},
+        system::{InputStream, Vector2u, err::diagnose},
         window::Window,
     },
     std::{
@@ -86,10 +86,10 @@ impl Texture {
     /// * mem - Pointer to the file data in memory
     /// * area - Area of the image to load
     pub fn load_from_memory(&mut self, mem: &[u8], srgb: bool, area: IntRect) -> SfResult<()> {
-        unsafe {
+        diagnose(|| unsafe {
             ffi::sfTexture_loadFromMemory(self, mem.as_ptr().cast(), mem.len(), srgb, area)
                 .into_sf_result()
-        }
+        })
     }
 
     /// Load texture from a stream (a struct implementing Read + Seek)
@@ -109,10 +109,10 @@ impl Texture {
         area: IntRect,
     ) -> SfResult<()> {
         let mut input_stream = InputStream::new(stream);
-        unsafe {
+        diagnose(|| unsafe {
             ffi::sfTexture_loadFromStream(self, &raw mut *input_stream.stream, srgb, area)
                 .into_sf_result()
-        }
+        })
     }
 
     /// Load texture from a file
@@ -121,7 +121,9 @@ impl Texture {
     /// * filename - Path of the image file to load
     pub fn load_from_file(&mut self, filename: &str, srgb: bool, area: IntRect) -> SfResult<()> {
         let c_str = CString::new(filename)?;
-        unsafe { ffi::sfTexture_loadFromFile(self, c_str.as_ptr(), srgb, area).into_sf_result() }
+        diagnose(|| unsafe {
+            ffi::sfTexture_loadFromFile(self, c_str.as_ptr(), srgb, area).into_sf_result()
+        })
     }
 
     /// Convenience method to easily create and load a `Texture` from a file.
@@ -148,7 +150,9 @@ impl Texture {
     ///   If the area rectangle crosses the bounds of the image,
     ///   it is adjusted to fit the image size.
     pub fn load_from_image(&mut self, image: &Image, srgb: bool, area: IntRect) -> SfResult<()> {
-        unsafe { ffi::sfTexture_loadFromImage(self, image, srgb, area).into_sf_result() }
+        diagnose(|| unsafe {
+            ffi::sfTexture_loadFromImage(self, image, srgb, area).into_sf_result()
+        })
     }
 }
 
@@ -61,3 +61,143 @@ pub trait Transformable {
     /// Gets the inverse combined transform of the object.
     fn inverse_transform(&self) -> &Transform;
 }
+
+/// Position, rotation, scale and origin stored on the Rust side, with the combined
+/// transform kept up to date.
+///
+/// This is the equivalent of SFML's `sf::Transformable` class, for drawables
+/// implemented in pure Rust.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Transformation {
+    position: Vector2f,
+    rotation: Angle,
+    scale: Vector2f,
+    origin: Vector2f,
+    transform: Transform,
+    inverse_transform: Transform,
+}
+
+impl Default for Transformation {
+    fn default() -> Self {
+        Self {
+            position: Vector2f::new(0., 0.),
+            rotation: Angle::radians(0.),
+            scale: Vector2f::new(1., 1.),
+            origin: Vector2f::new(0., 0.),
+            transform: Transform::IDENTITY,
+            inverse_transform: Transform::IDENTITY,
+        }
+    }
+}
+
+impl Transformation {
+    fn update(&mut self) {
+        // Same computation as `sf::Transformable::getTransform`
+        let angle = -self.rotation.as_radians();
+        let (sine, cosine) = angle.sin_cos();
+        let sxc = self.scale.x * cosine;
+        let syc = self.scale.y * cosine;
+        let sxs = self.scale.x * sine;
+        let sys = self.scale.y * sine;
+        let tx = -self.origin.x * sxc - self.origin.y * sys + self.position.x;
+        let ty = self.origin.x * sxs - self.origin.y * syc + self.position.y;
+        self.transform = Transform::new(sxc, sys, tx, -sxs, syc, ty, 0., 0., 1.);
+        self.inverse_transform = self.transform.inverse();
+    }
+}
+
+impl Transformable for Transformation {
+    fn set_position<P: Into<Vector2f>>(&mut self, position: P) {
+        self.position = position.into();
+        self.update();
+    }
+    fn set_rotation(&mut self, angle: Angle) {
+        self.rotation = angle.wrap_unsigned();
+        self.update();
+    }
+    fn set_scale<S: Into<Vector2f>>(&mut self, scale: S) {
+        self.scale = scale.into();
+        self.update();
+    }
+    fn set_origin<O: Into<Vector2f>>(&mut self, origin: O) {
+        self.origin = origin.into();
+        self.update();
+    }
+    fn position(&self) -> Vector2f {
+        self.position
+    }
+    fn rotation(&self) -> Angle {
+        self.rotation
+    }
+    fn get_scale(&self) -> Vector2f {
+        self.scale
+    }
+    fn origin(&self) -> Vector2f {
+        self.origin
+    }
+    fn move_<O: Into<Vector2f>>(&mut self, offset: O) {
+        self.set_position(self.position + offset.into());
+    }
+    fn rotate(&mut self, angle: Angle) {
+        self.set_rotation(self.rotation + angle);
+    }
+    fn scale<F: Into<Vector2f>>(&mut self, factors: F) {
+        self.set_scale(self.scale.cwise_mul(factors.into()));
+    }
+    fn transform(&self) -> &Transform {
+        &self.transform
+    }
+    fn inverse_transform(&self) -> &Transform {
+        &self.inverse_transform
+    }
+}
+
+/// Implements [`Transformable`] for a type by forwarding to one of its
+/// [`Transformation`] fields.
+macro_rules! impl_transformable_via {
+    ($ty:ty, $field:ident) => {
+        impl $crate::graphics::Transformable for $ty {
+            fn set_position<P: Into<$crate::system::Vector2f>>(&mut self, position: P) {
+                self.$field.set_position(position);
+            }
+            fn set_rotation(&mut self, angle: $crate::system::Angle) {
+                self.$field.set_rotation(angle);
+            }
+            fn set_scale<S: Into<$crate::system::Vector2f>>(&mut self, scale: S) {
+                self.$field.set_scale(scale);
+            }
+            fn set_origin<O: Into<$crate::system::Vector2f>>(&mut self, origin: O) {
+                self.$field.set_origin(origin);
+            }
+            fn position(&self) -> $crate::system::Vector2f {
+                self.$field.position()
+            }
+            fn rotation(&self) -> $crate::system::Angle {
+                self.$field.rotation()
+            }
+            fn get_scale(&self) -> $crate::system::Vector2f {
+                self.$field.get_scale()
+            }
+            fn origin(&self) -> $crate::system::Vector2f {
+                self.$field.origin()
+            }
+            fn move_<O: Into<$crate::system::Vector2f>>(&mut self, offset: O) {
+                self.$field.move_(offset);
+            }
+            fn rotate(&mut self, angle: $crate::system::Angle) {
+                self.$field.rotate(angle);
+            }
+            fn scale<F: Into<$crate::system::Vector2f>>(&mut self, factors: F) {
+                self.$field.scale(factors);
+            }
+            fn transform(&self) -> &$crate::graphics::Transform {
+                self.$field.transform()
+            }
+            fn inverse_transform(&self) -> &$crate::graphics::Transform {
+                self.$field.inverse_transform()
+            }
+        }
+    };
+}
+
+pub(crate) use impl_transformable_via;
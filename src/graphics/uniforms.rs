@@ -0,0 +1,336 @@
+use {
+    crate::{
+        ffi::graphics as ffi,
+        graphics::{Color, Shader, Texture, glsl},
+    },
+    std::{
+        error::Error,
+        ffi::{CStr, c_int},
+        fmt,
+        marker::PhantomData,
+    },
+};
+
+/// A Rust value that can be uploaded to a shader uniform.
+///
+/// This is implemented for the scalar, vector and matrix types of the [`glsl`] module,
+/// [`Color`] (as a `vec4`), textures, [`CurrentTexture`], and arrays, slices and vectors of
+/// `f32`, [`glsl::Vec2`], [`glsl::Vec3`], [`glsl::Vec4`], [`glsl::Mat3`] and [`glsl::Mat4`].
+pub trait UniformValue<'texture> {
+    /// Set `uniform` of `shader` to this value.
+    fn set_uniform(&self, shader: &mut Shader<'texture>, uniform: UniformLocation);
+}
+
+/// A uniform of a shader, with its location looked up by [`Shader::bind_uniforms`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniformLocation {
+    name: &'static CStr,
+    location: c_int,
+}
+
+impl UniformLocation {
+    /// The name of the uniform.
+    #[must_use]
+    pub fn name(self) -> &'static CStr {
+        self.name
+    }
+}
+
+/// A set of uniforms that can be uploaded to a shader at once.
+///
+/// Implement it with `#[derive(Uniforms)]`, which requires the `derive` feature.
+/// Each field of the struct is a uniform of the same name, and its type must implement
+/// [`UniformValue`]. A field can be renamed with `#[uniform(name = "...")]`, and skipped
+/// with `#[uniform(skip)]`.
+///
+/// Use [`Shader::bind_uniforms`] to check that a shader has all the uniforms and look up their
+/// locations, and [`UniformBinding::upload`] to upload them. Uniforms are then set by location,
+/// except textures, which SFML tracks by name.
+///
+/// # Usage example
+///
+/// ```no_run
+/// # #[cfg(feature = "derive")] {
+/// use sfml::{
+///     graphics::{Color, CurrentTexture, Shader, ShaderType, Texture, Uniforms},
+///     system::Vector2f,
+/// };
+///
+/// #[derive(Uniforms)]
+/// struct Wave<'t> {
+///     texture: CurrentTexture,
+///     noise: &'t Texture,
+///     time: f32,
+///     amplitude: Vector2f,
+///     #[uniform(name = "tint_color")]
+///     tint: Color,
+/// }
+///
+/// let noise = Texture::from_file("noise.png").unwrap();
+/// let mut shader = Shader::from_file("wave.frag", ShaderType::Fragment).unwrap();
+/// // Fails if the shader misses one of the uniforms
+/// let binding = shader.bind_uniforms::<Wave>().unwrap();
+/// let wave = Wave {
+///     texture: CurrentTexture,
+///     noise: &noise,
+///     time: 0.5,
+///     amplitude: Vector2f::new(4., 2.),
+///     tint: Color::WHITE,
+/// };
+/// binding.upload(&mut shader, &wave).unwrap();
+/// # }
+/// ```
+pub trait Uniforms<'texture> {
+    /// The names of the uniforms, in the order they are uploaded.
+    const NAMES: &'static [&'static CStr];
+
+    /// Upload the uniforms to `shader`, without checking that it has them.
+    ///
+    /// `locations` are the uniforms of [`Uniforms::NAMES`], in the same order.
+    fn set_uniforms(&self, shader: &mut Shader<'texture>, locations: &[UniformLocation]);
+}
+
+/// Uploads [`Uniforms`] to the shader that was checked to have them.
+///
+/// Created by [`Shader::bind_uniforms`].
+#[derive(Debug)]
+pub struct UniformBinding<U> {
+    /// Id of the shader, which unlike its OpenGL program name is never reused
+    shader: u64,
+    locations: Vec<UniformLocation>,
+    uniforms: PhantomData<fn(&U)>,
+}
+
+impl<U> UniformBinding<U> {
+    /// Upload `uniforms` to `shader`.
+    ///
+    /// This fails if `shader` is not the shader this binding was created for. A shader rebuilt
+    /// by a [`ShaderWatcher`](crate::graphics::ShaderWatcher) is a new shader, which needs a new
+    /// binding.
+    pub fn upload<'texture>(
+        &self,
+        shader: &mut Shader<'texture>,
+        uniforms: &U,
+    ) -> Result<(), UniformError>
+    where
+        U: Uniforms<'texture>,
+    {
+        if shader.id() != self.shader {
+            return Err(UniformError::OtherShader);
+        }
+        uniforms.set_uniforms(shader, &self.locations);
+        Ok(())
+    }
+}
+
+impl<'texture> Shader<'texture> {
+    /// Tell whether the shader has an active uniform named `name`.
+    ///
+    /// Uniforms that the shader declares but doesn't use are removed by the GLSL compiler,
+    /// so they are not active.
+    #[must_use]
+    pub fn has_uniform(&self, name: &CStr) -> bool {
+        self.uniform_locations(&[name])[0] != -1
+    }
+
+    /// Check that the shader has all the uniforms of `U`, and create a binding to upload them.
+    ///
+    /// The locations of the uniforms are looked up once, here, rather than on every upload.
+    ///
+    /// Fails with [`UniformError::Unknown`], listing the missing uniforms, if the shader doesn't
+    /// have some of them. See [`Shader::has_uniform`].
+    pub fn bind_uniforms<U: Uniforms<'texture>>(&self) -> Result<UniformBinding<U>, UniformError> {
+        let locations = self.uniform_locations(U::NAMES);
+        let unknown: Vec<String> = U::NAMES
+            .iter()
+            .zip(&locations)
+            .filter(|&(_, &location)| location == -1)
+            .map(|(name, _)| name.to_string_lossy().into_owned())
+            .collect();
+        if !unknown.is_empty() {
+            return Err(UniformError::Unknown(unknown));
+        }
+        Ok(UniformBinding {
+            shader: self.id(),
+            locations: U::NAMES
+                .iter()
+                .zip(locations)
+                .map(|(&name, location)| UniformLocation { name, location })
+                .collect(),
+            uniforms: PhantomData,
+        })
+    }
+
+    fn id(&self) -> u64 {
+        unsafe { ffi::sfShader_getId(self.raw()) }
+    }
+
+    /// Look up the locations of the uniforms `names`, -1 for those the shader doesn't have.
+    fn uniform_locations(&self, names: &[&CStr]) -> Vec<c_int> {
+        let names: Vec<_> = names.iter().map(|name| name.as_ptr()).collect();
+        let mut locations = vec![-1; names.len()];
+        unsafe {
+            ffi::sfShader_getUniformLocations(
+                self.raw(),
+                names.as_ptr(),
+                names.len(),
+                locations.as_mut_ptr(),
+            );
+        }
+        locations
+    }
+
+    /// Set the uniform at `location` to `values`, made of vectors of `components` floats.
+    fn set_floats_at<T>(&mut self, location: c_int, values: &[T], components: usize) {
+        debug_assert_eq!(size_of::<T>(), components * size_of::<f32>());
+        unsafe {
+            ffi::sfShader_setFloatUniformsAt(
+                self.raw_mut(),
+                location,
+                values.as_ptr().cast(),
+                components,
+                values.len(),
+            );
+        }
+    }
+
+    /// Set the uniform at `location` to `values`, vectors of `components` integers.
+    fn set_ints_at(&mut self, location: c_int, values: &[c_int], components: usize) {
+        unsafe {
+            ffi::sfShader_setIntUniformsAt(
+                self.raw_mut(),
+                location,
+                values.as_ptr(),
+                components,
+                values.len() / components,
+            );
+        }
+    }
+
+    /// Set the uniform at `location` to `values`, `size`×`size` matrices.
+    fn set_matrices_at<T>(&mut self, location: c_int, values: &[T], size: usize) {
+        debug_assert_eq!(size_of::<T>(), size * size * size_of::<f32>());
+        unsafe {
+            ffi::sfShader_setMatrixUniformsAt(
+                self.raw_mut(),
+                location,
+                values.as_ptr().cast(),
+                size,
+                values.len(),
+            );
+        }
+    }
+}
+
+/// Error that can occur when binding or uploading [`Uniforms`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UniformError {
+    /// The shader doesn't have these uniforms
+    Unknown(Vec<String>),
+    /// The uniforms were uploaded to another shader than the one they were bound to
+    OtherShader,
+}
+
+impl Error for UniformError {}
+
+impl fmt::Display for UniformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(names) => {
+                write!(f, "The shader has no active uniform named ")?;
+                for (i, name) in names.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "`{name}`")?;
+                }
+                Ok(())
+            }
+            Self::OtherShader => write!(f, "The uniforms were bound to another shader"),
+        }
+    }
+}
+
+/// The texture of the object being drawn, as a `sampler2D` uniform.
+///
+/// See [`Shader::set_uniform_current_texture`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CurrentTexture;
+
+impl UniformValue<'_> for CurrentTexture {
+    fn set_uniform(&self, shader: &mut Shader<'_>, uniform: UniformLocation) {
+        let name = uniform.name.as_ptr();
+        unsafe { ffi::sfShader_setCurrentTextureUniform(shader.raw_mut(), name) }
+    }
+}
+
+impl<'texture, 'r: 'texture> UniformValue<'texture> for &'r Texture {
+    fn set_uniform(&self, shader: &mut Shader<'texture>, uniform: UniformLocation) {
+        let name = uniform.name.as_ptr();
+        unsafe { ffi::sfShader_setTextureUniform(shader.raw_mut(), name, *self) }
+    }
+}
+
+macro_rules! impl_uniform_value {
+    ($($ty:ty => |$value:ident| $set:ident($data:expr, $components:literal);)*) => {
+        $(
+            impl UniformValue<'_> for $ty {
+                fn set_uniform(&self, shader: &mut Shader<'_>, uniform: UniformLocation) {
+                    let $value = *self;
+                    shader.$set(uniform.location, &$data, $components);
+                }
+            }
+        )*
+    };
+}
+
+impl_uniform_value! {
+    f32 => |value| set_floats_at([value], 1);
+    glsl::Vec2 => |value| set_floats_at([value], 2);
+    glsl::Vec3 => |value| set_floats_at([value], 3);
+    glsl::Vec4 => |value| set_floats_at([value], 4);
+    Color => |value| set_floats_at([glsl::Vec4::from(value)], 4);
+    i32 => |value| set_ints_at([value], 1);
+    glsl::IVec2 => |value| set_ints_at([value.x, value.y], 2);
+    glsl::IVec3 => |value| set_ints_at([value.x, value.y, value.z], 3);
+    glsl::IVec4 => |value| set_ints_at([value.x, value.y, value.z, value.w], 4);
+    bool => |value| set_ints_at([value].map(i32::from), 1);
+    glsl::BVec2 => |value| set_ints_at([value.x, value.y].map(i32::from), 2);
+    glsl::BVec3 => |value| set_ints_at([value.x, value.y, value.z].map(i32::from), 3);
+    glsl::BVec4 => |value| set_ints_at([value.x, value.y, value.z, value.w].map(i32::from), 4);
+    glsl::Mat3 => |value| set_matrices_at([value], 3);
+    glsl::Mat4 => |value| set_matrices_at([value], 4);
+}
+
+macro_rules! impl_uniform_array {
+    ($($ty:ty => $set:ident($components:literal);)*) => {
+        $(
+            impl UniformValue<'_> for [$ty] {
+                fn set_uniform(&self, shader: &mut Shader<'_>, uniform: UniformLocation) {
+                    shader.$set(uniform.location, self, $components);
+                }
+            }
+
+            impl<const N: usize> UniformValue<'_> for [$ty; N] {
+                fn set_uniform(&self, shader: &mut Shader<'_>, uniform: UniformLocation) {
+                    self.as_slice().set_uniform(shader, uniform);
+                }
+            }
+
+            impl UniformValue<'_> for Vec<$ty> {
+                fn set_uniform(&self, shader: &mut Shader<'_>, uniform: UniformLocation) {
+                    self.as_slice().set_uniform(shader, uniform);
+                }
+            }
+        )*
+    };
+}
+
+impl_uniform_array! {
+    f32 => set_floats_at(1);
+    glsl::Vec2 => set_floats_at(2);
+    glsl::Vec3 => set_floats_at(3);
+    glsl::Vec4 => set_floats_at(4);
+    glsl::Mat3 => set_matrices_at(3);
+    glsl::Mat4 => set_matrices_at(4);
+}
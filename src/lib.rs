@@ -38,12 +38,28 @@ pub mod window;
 use std::{error::Error, fmt::Display};
 
 /// An SFML operation has failed
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum SfError {
     /// An string argument passed had interior nul bytes
     NulInStr,
     /// Call to SFML function returned an error
     CallFailed,
+    /// Call to SFML function returned an error, and SFML explained why.
+    ///
+    /// Contains what SFML wrote to its error output during the call, like the compile log of
+    /// a shader, or the reason why an image failed to load.
+    Diagnostic(String),
+}
+
+impl SfError {
+    /// Get what SFML wrote to its error output when the call failed, if anything.
+    #[must_use]
+    pub fn diagnostic(&self) -> Option<&str> {
+        match self {
+            SfError::Diagnostic(text) => Some(text),
+            _ => None,
+        }
+    }
 }
 
 impl Display for SfError {
@@ -51,6 +67,9 @@ impl Display for SfError {
         match self {
             SfError::NulInStr => write!(f, "Interior nul byte in string passed to SFML"),
             SfError::CallFailed => write!(f, "Call to SFML function returned an error"),
+            SfError::Diagnostic(text) => {
+                write!(f, "Call to SFML function returned an error: {text}")
+            }
         }
     }
 }
@@ -0,0 +1,261 @@
+use {
+    crate::{SfError, SfResult, cpp::FBox, ffi::system as ffi},
+    std::{
+        cell::Cell,
+        ffi::c_char,
+        fmt,
+        panic::{self, AssertUnwindSafe, Location},
+        path::{Component, Path},
+        sync::{Arc, PoisonError, RwLock},
+    },
+};
+
+/// Where the diagnostics that SFML writes to its error output go.
+///
+/// SFML explains what went wrong in its error output, for example why an image failed to
+/// load, or the compile log of a shader. By default, it writes to the standard error output.
+///
+/// See [`set_err_output`].
+pub enum ErrOutput {
+    /// Write the diagnostics to the standard error output, like SFML does by default
+    Stderr,
+    /// Discard the diagnostics
+    Silent,
+    /// Send each diagnostic to the [`log`](https://docs.rs/log) crate, as a record whose target
+    /// is the SFML module that wrote it, like `sfml::graphics`
+    #[cfg(feature = "log")]
+    Log,
+    /// Send each diagnostic to the [`tracing`](https://docs.rs/tracing) crate, as an event whose
+    /// target is the SFML module that wrote it, like `sfml::graphics`
+    #[cfg(feature = "tracing")]
+    Tracing,
+    /// Call a function with each diagnostic.
+    ///
+    /// The function is called on the thread that wrote the diagnostic.
+    Callback(Box<dyn Fn(&ErrRecord<'_>) + Send + Sync>),
+}
+
+impl fmt::Debug for ErrOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stderr => write!(f, "Stderr"),
+            Self::Silent => write!(f, "Silent"),
+            #[cfg(feature = "log")]
+            Self::Log => write!(f, "Log"),
+            #[cfg(feature = "tracing")]
+            Self::Tracing => write!(f, "Tracing"),
+            Self::Callback(_) => write!(f, "Callback(..)"),
+        }
+    }
+}
+
+/// A diagnostic written by SFML to its error output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrRecord<'a> {
+    /// The SFML module that wrote the diagnostic, like `sfml::graphics`, or `sfml` if unknown
+    pub target: &'static str,
+    /// The diagnostic, without its trailing newline. It can span several lines.
+    pub message: &'a str,
+}
+
+impl ErrRecord<'_> {
+    /// Tell whether the diagnostic is a warning rather than an error.
+    #[must_use]
+    pub fn is_warning(&self) -> bool {
+        self.message
+            .get(..7)
+            .is_some_and(|start| start.eq_ignore_ascii_case("warning"))
+    }
+}
+
+type Callback = Arc<dyn Fn(&ErrRecord<'_>) + Send + Sync>;
+
+static CALLBACK: RwLock<Option<Callback>> = RwLock::new(None);
+
+thread_local! {
+    /// The module of the SFML call in progress on this thread
+    static TARGET: Cell<&'static str> = const { Cell::new("sfml") };
+}
+
+/// Choose where the diagnostics that SFML writes to its error output go.
+///
+/// This applies to all threads. The diagnostics that are [captured](capture_err) also go to
+/// this output.
+///
+/// # Usage example
+///
+/// ```no_run
+/// use sfml::{graphics::Texture, system::{ErrOutput, set_err_output}};
+///
+/// set_err_output(ErrOutput::Callback(Box::new(|record| {
+///     eprintln!("[{}] {}", record.target, record.message);
+/// })));
+/// let _ = Texture::from_file("missing.png");
+/// set_err_output(ErrOutput::Silent);
+/// ```
+pub fn set_err_output(output: ErrOutput) {
+    let forward = matches!(output, ErrOutput::Stderr);
+    let callback: Option<Callback> = match output {
+        ErrOutput::Stderr | ErrOutput::Silent => None,
+        #[cfg(feature = "log")]
+        ErrOutput::Log => Some(Arc::new(log_record)),
+        #[cfg(feature = "tracing")]
+        ErrOutput::Tracing => Some(Arc::new(trace_record)),
+        ErrOutput::Callback(callback) => Some(Arc::from(callback)),
+    };
+    let c_callback: ffi::sfErrCallback = if callback.is_some() {
+        Some(forward_record)
+    } else {
+        None
+    };
+    *CALLBACK.write().unwrap_or_else(PoisonError::into_inner) = callback;
+    unsafe { ffi::sfErr_setOutput(forward, c_callback) }
+}
+
+/// Call `f`, and get what SFML wrote to its error output on the current thread during the
+/// call.
+///
+/// The diagnostics still go to the output chosen with [`set_err_output`].
+///
+/// ```no_run
+/// use sfml::{graphics::Image, system::capture_err};
+///
+/// let (result, diagnostics) = capture_err(|| Image::from_file("missing.png"));
+/// if result.is_err() {
+///     println!("Couldn't load the image:\n{diagnostics}");
+/// }
+/// ```
+pub fn capture_err<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let capture = Capture::begin("sfml");
+    let result = f();
+    (result, capture.finish())
+}
+
+/// Call `f`, and if it fails with [`SfError::CallFailed`], replace the error with what SFML
+/// wrote to `sf::err()` during the call.
+///
+/// The diagnostics written during the call are attributed to the module of the caller.
+#[track_caller]
+pub(crate) fn diagnose<T>(f: impl FnOnce() -> SfResult<T>) -> SfResult<T> {
+    let capture = Capture::begin(caller_module(Location::caller()));
+    let result = f();
+    let text = capture.finish();
+    match result {
+        Err(SfError::CallFailed) if !text.trim().is_empty() => {
+            Err(SfError::Diagnostic(text.trim_end().to_owned()))
+        }
+        result => result,
+    }
+}
+
+/// Captures what the current thread writes to `sf::err()`, until it's finished or dropped.
+struct Capture {
+    start: usize,
+    previous_target: &'static str,
+}
+
+impl Capture {
+    fn begin(target: &'static str) -> Self {
+        Self {
+            start: unsafe { ffi::sfErr_beginCapture() },
+            previous_target: TARGET.replace(target),
+        }
+    }
+
+    fn finish(self) -> String {
+        let text = self.end();
+        std::mem::forget(self);
+        text
+    }
+
+    fn end(&self) -> String {
+        TARGET.set(self.previous_target);
+        FBox::new(unsafe { ffi::sfErr_endCapture(self.start) })
+            .map(|text| text.to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        let _ = self.end();
+    }
+}
+
+/// Get the SFML module of a source file of this crate, from its path.
+fn caller_module(location: &Location<'_>) -> &'static str {
+    // The first directory under `src` is the module
+    let module = Path::new(location.file())
+        .components()
+        .rev()
+        .take_while(|component| *component != Component::Normal("src".as_ref()))
+        .last();
+    match module.and_then(|module| module.as_os_str().to_str()) {
+        Some("audio") => "sfml::audio",
+        Some("graphics") => "sfml::graphics",
+        Some("system") => "sfml::system",
+        Some("window") => "sfml::window",
+        _ => "sfml",
+    }
+}
+
+/// Called by the stream buffer behind `sf::err()` with each diagnostic.
+unsafe extern "C" fn forward_record(message: *const c_char, length: usize) {
+    let bytes = unsafe { std::slice::from_raw_parts(message.cast::<u8>(), length) };
+    let message = String::from_utf8_lossy(bytes);
+    let message = message.trim_end();
+    if message.is_empty() {
+        return;
+    }
+    let Some(callback) = CALLBACK
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+    else {
+        return;
+    };
+    let record = ErrRecord {
+        target: TARGET.get(),
+        message,
+    };
+    // Unwinding into C++ would abort
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| callback(&record)));
+}
+
+#[cfg(feature = "log")]
+fn log_record(record: &ErrRecord<'_>) {
+    let level = if record.is_warning() {
+        log::Level::Warn
+    } else {
+        log::Level::Error
+    };
+    log::log!(target: record.target, level, "{}", record.message);
+}
+
+#[cfg(feature = "tracing")]
+fn trace_record(record: &ErrRecord<'_>) {
+    // The target and level of tracing events must be constants
+    macro_rules! event {
+        ($($target:literal)*) => {
+            match (record.target, record.is_warning()) {
+                $(
+                    ($target, true) => tracing::warn!(target: $target, "{}", record.message),
+                    ($target, false) => tracing::error!(target: $target, "{}", record.message),
+                )*
+                (_, true) => tracing::warn!(target: "sfml", "{}", record.message),
+                (_, false) => tracing::error!(target: "sfml", "{}", record.message),
+            }
+        };
+    }
+    event!("sfml::audio" "sfml::graphics" "sfml::system" "sfml::window");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caller_module_is_the_directory_under_src() {
+        assert_eq!(caller_module(Location::caller()), "sfml::system");
+    }
+}
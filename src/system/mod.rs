@@ -6,6 +6,7 @@
 pub use self::{
     angle::Angle,
     clock::Clock,
+    err::{ErrOutput, ErrRecord, capture_err, set_err_output},
     input_stream::InputStream,
     sleep::sleep,
     string::{SfStr, SfStrConv, SfString},
@@ -17,6 +18,7 @@ pub use self::{
 mod angle;
 pub(crate) mod buffer;
 mod clock;
+pub(crate) mod err;
 mod input_stream;
 mod sleep;
 mod string;
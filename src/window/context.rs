@@ -1,5 +1,8 @@
 use {
-    crate::{IntoSfResult, SfResult, cpp::FBox, ffi::window as ffi, window::ContextSettings},
+    crate::{
+        IntoSfResult, SfResult, cpp::FBox, ffi::window as ffi, system::err::diagnose,
+        window::ContextSettings,
+    },
     std::ffi::CStr,
 };
 
@@ -40,7 +43,7 @@ impl Context {
     /// # Arguments
     /// * active - `true` to activate, `false` to deactivate
     pub fn set_active(&mut self, active: bool) -> SfResult<()> {
-        unsafe { ffi::sfContext_setActive(self, active) }.into_sf_result()
+        diagnose(|| unsafe { ffi::sfContext_setActive(self, active) }.into_sf_result())
     }
     /// Get the settings of the context.
     ///
@@ -0,0 +1,26 @@
+//! Golden-image tests, see `sfml::graphics::testing`.
+//!
+//! They need an OpenGL context, so they are skipped with the `ci-headless` feature, and CI runs
+//! them separately under Xvfb with Mesa's llvmpipe.
+
+use sfml::graphics::{
+    Color, RectangleShape, RenderTarget, Shape, Transformable, testing::GoldenTest,
+};
+
+#[cfg_attr(not(feature = "ci-headless"), test)]
+fn blended_rectangles() {
+    let mut red = RectangleShape::with_size((16., 8.).into());
+    red.set_position((8., 4.));
+    red.set_fill_color(Color::RED);
+    let mut blue = RectangleShape::with_size((12., 16.).into());
+    blue.set_position((16., 8.));
+    blue.set_fill_color(Color::rgba(0, 0, 255, 128));
+    let test = GoldenTest {
+        clear_color: Color::BLACK,
+        ..GoldenTest::new((32, 32))
+    };
+    test.assert("blended_rectangles", |target| {
+        target.draw(&red);
+        target.draw(&blue);
+    });
+}